@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use str_distance::{DistanceMetric, Jaccard, Jaro, Levenshtein};
+
+/// A pair of ASCII strs of `len` chars and the same pair with a single
+/// non-ASCII char, that takes the char path instead of the byte path.
+fn inputs(len: usize) -> ((String, String), (String, String)) {
+    let a: String = (0..len)
+        .map(|i| (b'a' + (i * 7 % 26) as u8) as char)
+        .collect();
+    let b: String = a
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i % 10 == 0 { '#' } else { c })
+        .collect();
+    let non_ascii = (a.replacen('a', "é", 1), b.replacen('a', "é", 1));
+    ((a, b), non_ascii)
+}
+
+fn bench_metric<D: DistanceMetric>(c: &mut Criterion, name: &str, dist: D) {
+    let mut group = c.benchmark_group(name);
+    for len in [16, 64, 500].iter() {
+        let (ascii, non_ascii) = inputs(*len);
+        group.bench_with_input(BenchmarkId::new("ascii", len), &ascii, |bench, (a, b)| {
+            bench.iter(|| dist.str_distance(black_box(a), black_box(b)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("non-ascii", len),
+            &non_ascii,
+            |bench, (a, b)| bench.iter(|| dist.str_distance(black_box(a), black_box(b))),
+        );
+    }
+    group.finish();
+}
+
+fn ascii_fast_path(c: &mut Criterion) {
+    bench_metric(c, "levenshtein", Levenshtein::default());
+    bench_metric(c, "bounded levenshtein", Levenshtein::with_max_distance(8));
+    bench_metric(c, "jaro", Jaro);
+    bench_metric(c, "jaccard", Jaccard::new(2));
+}
+
+criterion_group!(benches, ascii_fast_path);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use str_distance::{DistanceMetric, Jaro, JaroWinkler};
+
+/// `count` names of up to `max_len` chars.
+fn names(count: usize, max_len: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            let len = 3 + i % (max_len - 2);
+            (0..len)
+                .map(|j| (b'a' + ((i * 31 + j * 7) % 26) as u8) as char)
+                .collect()
+        })
+        .collect()
+}
+
+fn short_strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jaro short strings");
+    for max_len in [8, 32, 200].iter() {
+        let names = names(100, *max_len);
+        group.bench_with_input(BenchmarkId::new("jaro", max_len), &names, |bench, names| {
+            bench.iter(|| {
+                for a in names.iter() {
+                    for b in names.iter() {
+                        black_box(Jaro.str_distance(a, b));
+                    }
+                }
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("jaro winkler", max_len),
+            &names,
+            |bench, names| {
+                let jw = JaroWinkler::default();
+                bench.iter(|| {
+                    for a in names.iter() {
+                        for b in names.iter() {
+                            black_box(jw.str_distance(a, b));
+                        }
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, short_strings);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use str_distance::{DistanceMetric, Levenshtein};
+
+/// A str of `len` chars and a copy with an edit every `len / edits` chars.
+fn inputs(len: usize, edits: usize) -> (String, String) {
+    let a: String = (0..len)
+        .map(|i| (b'a' + (i * 7 % 26) as u8) as char)
+        .collect();
+    let b = a
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i % (len / edits) == 0 { '#' } else { c })
+        .collect();
+    (a, b)
+}
+
+fn bounded_levenshtein(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bounded levenshtein");
+    for len in [500, 2000].iter() {
+        let (a, b) = inputs(*len, 4);
+        group.bench_with_input(
+            BenchmarkId::new("unbounded", len),
+            &(&a, &b),
+            |bench, (a, b)| {
+                bench.iter(|| {
+                    Levenshtein::default().distance(black_box(a.chars()), black_box(b.chars()))
+                })
+            },
+        );
+        for max_dist in [2, 8].iter() {
+            let lev = Levenshtein::with_max_distance(*max_dist);
+            group.bench_with_input(
+                BenchmarkId::new(format!("max distance {}", max_dist), len),
+                &(&a, &b),
+                |bench, (a, b)| {
+                    bench.iter(|| lev.distance(black_box(a.chars()), black_box(b.chars())))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn blocked_myers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("blocked myers");
+    for len in [200, 1000].iter() {
+        let (a, b) = inputs(*len, 4);
+        group.bench_with_input(BenchmarkId::new("dp", len), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| {
+                Levenshtein::default().distance(black_box(a.chars()), black_box(b.chars()))
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("myers", len),
+            &(&a, &b),
+            |bench, (a, b)| {
+                bench.iter(|| Levenshtein::default().str_distance(black_box(a), black_box(b)))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bounded_levenshtein, blocked_myers);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use str_distance::{Cosine, DistanceMetric, Jaccard, QGram};
+
+/// A pseudo random str of `len` chars and a copy with an edit every
+/// `len / edits` chars.
+fn inputs(len: usize, edits: usize) -> (String, String) {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let a: String = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (b'a' + (state % 26) as u8) as char
+        })
+        .collect();
+    let b = a
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i % (len / edits) == 0 { '#' } else { c })
+        .collect();
+    (a, b)
+}
+
+/// Compares the generic distance, that matches the q-grams by equality, with
+/// the str distance, that counts them via hashing.
+fn bench_metric<D: DistanceMetric>(c: &mut Criterion, name: &str, dist: D) {
+    let mut group = c.benchmark_group(name);
+    for len in [100, 1000].iter() {
+        let (a, b) = inputs(*len, 4);
+        group.bench_with_input(
+            BenchmarkId::new("generic", len),
+            &(&a, &b),
+            |bench, (a, b)| {
+                bench.iter(|| dist.distance(black_box(a.chars()), black_box(b.chars())))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("hashed", len),
+            &(&a, &b),
+            |bench, (a, b)| bench.iter(|| dist.str_distance(black_box(a), black_box(b))),
+        );
+    }
+    group.finish();
+}
+
+fn qgram_hashing(c: &mut Criterion) {
+    bench_metric(c, "qgram", QGram::new(2));
+    bench_metric(c, "jaccard", Jaccard::new(3));
+    bench_metric(c, "cosine", Cosine::new(2));
+}
+
+criterion_group!(benches, qgram_hashing);
+criterion_main!(benches);
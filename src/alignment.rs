@@ -0,0 +1,665 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::DistanceMetric;
+
+/// A global alignment distance with affine gap penalties, evaluated with the
+/// three matrix dynamic programming algorithm of Gotoh.
+///
+/// Aligning two equal elements is rewarded with `match_score`, two unequal
+/// elements are penalized with `mismatch_penalty`. A gap of length `k` is
+/// penalized with `gap_open + (k - 1) * gap_extend`, so that a `gap_open`
+/// larger than `gap_extend` favours few long gaps over many short gaps.
+///
+/// The distance is the difference between the best possible score of the
+/// shorter input, `match_score * min(len_a, len_b)`, and the score of the
+/// optimal alignment. It is therefore non negative and `0.` for equal inputs.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Gotoh};
+/// let gotoh = Gotoh::new(1., 1., 2., 0.5);
+/// assert_eq!(gotoh.str_distance("kitten", "kitten"), 0.);
+/// // one gap of length two
+/// assert_eq!(gotoh.str_distance("abcdef", "abef"), 2.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gotoh {
+    /// The reward for aligning two equal elements.
+    match_score: f64,
+    /// The penalty for aligning two unequal elements.
+    mismatch_penalty: f64,
+    /// The penalty for the first element of a gap.
+    gap_open: f64,
+    /// The penalty for every further element of a gap.
+    gap_extend: f64,
+}
+
+impl Gotoh {
+    /// Creates a new [`Gotoh`] distance, that rewards equal elements with
+    /// `match_score`, penalizes unequal elements with `mismatch_penalty` and
+    /// a gap of length `k` with `gap_open + (k - 1) * gap_extend`.
+    ///
+    /// Like for [`SmithWaterman::new`] and [`NeedlemanWunsch::new`], the
+    /// reward comes first and all penalties are positive and subtracted from
+    /// the score.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any argument is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Gotoh};
+    /// // opening a gap costs 3, every further element 0.5
+    /// let gotoh = Gotoh::new(1., 1., 3., 0.5);
+    /// assert_eq!(gotoh.str_distance("abcdef", "af"), 4.5);
+    /// ```
+    pub fn new(match_score: f64, mismatch_penalty: f64, gap_open: f64, gap_extend: f64) -> Self {
+        assert_valid(&[match_score, mismatch_penalty, gap_open, gap_extend]);
+        Self {
+            match_score,
+            mismatch_penalty,
+            gap_open,
+            gap_extend,
+        }
+    }
+
+    /// The penalty of a gap of `len` elements.
+    fn gap_penalty(&self, len: usize) -> f64 {
+        if len == 0 {
+            0.
+        } else {
+            self.gap_open + (len - 1) as f64 * self.gap_extend
+        }
+    }
+
+    /// The score of the optimal global alignment of `a` and `b`.
+    fn score<A, B>(&self, a: &[A], b: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        if a.is_empty() || b.is_empty() {
+            return -self.gap_penalty(a.len() + b.len());
+        }
+
+        // best score of the prefixes ending with `a[i]` aligned to `b[j]`, `a[i]`
+        // aligned to a gap and `b[j]` aligned to a gap
+        let mut m = vec![f64::NEG_INFINITY; b.len() + 1];
+        let mut x = vec![f64::NEG_INFINITY; b.len() + 1];
+        let mut y: Vec<_> = (0..=b.len()).map(|j| -self.gap_penalty(j)).collect();
+        m[0] = 0.;
+        y[0] = f64::NEG_INFINITY;
+
+        for (i, c1) in a.iter().enumerate() {
+            let (mut diag_m, mut diag_x, mut diag_y) = (m[0], x[0], y[0]);
+            m[0] = f64::NEG_INFINITY;
+            x[0] = -self.gap_penalty(i + 1);
+            y[0] = f64::NEG_INFINITY;
+
+            for (j, c2) in b.iter().enumerate() {
+                let (up_m, up_x, up_y) = (m[j + 1], x[j + 1], y[j + 1]);
+                let score = if c1 == c2 {
+                    self.match_score
+                } else {
+                    -self.mismatch_penalty
+                };
+                m[j + 1] = diag_m.max(diag_x).max(diag_y) + score;
+                x[j + 1] = (up_m.max(up_y) - self.gap_open).max(up_x - self.gap_extend);
+                y[j + 1] = (m[j].max(x[j]) - self.gap_open).max(y[j] - self.gap_extend);
+                diag_m = up_m;
+                diag_x = up_x;
+                diag_y = up_y;
+            }
+        }
+        m[b.len()].max(x[b.len()]).max(y[b.len()])
+    }
+
+    /// The distance of the optimal alignment and an upper bound of the
+    /// distance for inputs of the same lengths.
+    fn distance_and_bound<A, B>(&self, a: &[A], b: &[B]) -> (f64, f64)
+    where
+        A: PartialEq<B>,
+    {
+        let min_len = cmp::min(a.len(), b.len());
+        let len_diff = a.len().abs_diff(b.len());
+        let best = self.match_score * min_len as f64;
+        // either all elements are gaps, or the shorter input is completely
+        // mismatched and the remaining elements are a single gap
+        let bound = (self.gap_penalty(a.len()) + self.gap_penalty(b.len()) + best).min(
+            (self.match_score + self.mismatch_penalty) * min_len as f64
+                + self.gap_penalty(len_diff),
+        );
+        (best - self.score(a, b), bound)
+    }
+}
+
+impl DistanceMetric for Gotoh {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.distance_and_bound(&a, &b).0
+    }
+
+    /// The distance divided by the distance of the worst possible alignment
+    /// of inputs of the same lengths.
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        let (dist, bound) = self.distance_and_bound(&a, &b);
+        if bound <= 0. {
+            0.
+        } else {
+            (dist / bound).clamp(0., 1.)
+        }
+    }
+}
+
+/// A local alignment distance, that evaluates the best matching region of
+/// both inputs with the algorithm of Smith and Waterman.
+///
+/// Aligning two equal elements is rewarded with `match_score`, unequal
+/// elements and every element aligned to a gap are penalized with
+/// `mismatch_penalty` and `gap_penalty`. The score of the optimal local
+/// alignment is converted into a distance via `1 - score / max_score`, where
+/// `max_score` is the score of the shorter input aligned to itself:
+/// `match_score * min(len_a, len_b)`.
+///
+/// The distance is `0.` if the shorter input is contained in the longer one
+/// and `1.` if no elements match, or exactly one input is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, SmithWaterman};
+/// let sw = SmithWaterman::new(1., 1., 1.);
+/// assert_eq!(sw.str_distance("needle", "haystack with a needle in it"), 0.);
+/// assert_eq!(sw.str_distance("abc", "xyz"), 1.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SmithWaterman {
+    /// The reward for aligning two equal elements.
+    match_score: f64,
+    /// The penalty for aligning two unequal elements.
+    mismatch_penalty: f64,
+    /// The penalty for every element aligned to a gap.
+    gap_penalty: f64,
+}
+
+impl SmithWaterman {
+    /// Creates a new [`SmithWaterman`] distance, that rewards equal elements
+    /// with `match_score` and penalizes unequal elements with
+    /// `mismatch_penalty` and every element aligned to a gap with
+    /// `gap_penalty`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any argument is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, SmithWaterman};
+    /// let sw = SmithWaterman::new(1., 1., 0.5);
+    /// // "abcd" is aligned with "abxcd" with a single gap
+    /// assert_eq!(sw.str_distance("abcd", "zzabxcdzz"), 0.125);
+    /// ```
+    pub fn new(match_score: f64, mismatch_penalty: f64, gap_penalty: f64) -> Self {
+        assert_valid(&[match_score, mismatch_penalty, gap_penalty]);
+        Self {
+            match_score,
+            mismatch_penalty,
+            gap_penalty,
+        }
+    }
+
+    /// The maximum score of all local alignments of `a` and `b`.
+    fn score<A, B>(&self, a: &[A], b: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        let mut cache = vec![0f64; b.len() + 1];
+        let mut max_score = 0f64;
+        for c1 in a.iter() {
+            let mut diagonal = 0.;
+            for (j, c2) in b.iter().enumerate() {
+                let above = cache[j + 1];
+                let substitution = if c1 == c2 {
+                    diagonal + self.match_score
+                } else {
+                    diagonal - self.mismatch_penalty
+                };
+                let gap = above.max(cache[j]) - self.gap_penalty;
+                cache[j + 1] = substitution.max(gap).max(0.);
+                max_score = max_score.max(cache[j + 1]);
+                diagonal = above;
+            }
+        }
+        max_score
+    }
+}
+
+impl DistanceMetric for SmithWaterman {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        let max_score = self.match_score * cmp::min(a.len(), b.len()) as f64;
+        if max_score <= 0. {
+            return if a.is_empty() && b.is_empty() { 0. } else { 1. };
+        }
+        (1. - self.score(&a, &b) / max_score).clamp(0., 1.)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+}
+
+/// A global alignment distance with a linear gap penalty, evaluated with the
+/// algorithm of Needleman and Wunsch.
+///
+/// Aligning two equal elements is rewarded with `match_score`, unequal
+/// elements and every element aligned to a gap are penalized with
+/// `mismatch_penalty` and `gap_penalty`. Like [`Gotoh`], the distance is the difference between the
+/// best possible score of the shorter input,
+/// `match_score * min(len_a, len_b)`, and the score of the optimal alignment.
+///
+/// The optimal alignment itself is available via [`NeedlemanWunsch::align`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, NeedlemanWunsch};
+/// let nw = NeedlemanWunsch::new(1., 1., 1.);
+/// assert_eq!(nw.str_distance("GATTACA", "GCATGCU"), 7.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NeedlemanWunsch {
+    /// The reward for aligning two equal elements.
+    match_score: f64,
+    /// The penalty for aligning two unequal elements.
+    mismatch_penalty: f64,
+    /// The penalty for every element aligned to a gap.
+    gap_penalty: f64,
+}
+
+impl NeedlemanWunsch {
+    /// Creates a new [`NeedlemanWunsch`] distance, that rewards equal
+    /// elements with `match_score` and penalizes unequal elements with
+    /// `mismatch_penalty` and every element aligned to a gap with
+    /// `gap_penalty`, in the order of [`SmithWaterman::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if any argument is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, NeedlemanWunsch};
+    /// // two gaps are cheaper than a mismatch
+    /// let nw = NeedlemanWunsch::new(1., 2., 0.5);
+    /// assert_eq!(nw.str_distance("abc", "axc"), 2.);
+    /// let (a, b) = nw.align("abc", "axc");
+    /// assert_eq!(a, vec![Some('a'), None, Some('b'), Some('c')]);
+    /// assert_eq!(b, vec![Some('a'), Some('x'), None, Some('c')]);
+    /// ```
+    pub fn new(match_score: f64, mismatch_penalty: f64, gap_penalty: f64) -> Self {
+        assert_valid(&[match_score, mismatch_penalty, gap_penalty]);
+        Self {
+            match_score,
+            mismatch_penalty,
+            gap_penalty,
+        }
+    }
+
+    /// Computes the optimal global alignment of `a` and `b`.
+    ///
+    /// Both returned sequences have the same length, a `None` denotes a gap
+    /// in the respective sequence. If several alignments have the optimal
+    /// score, aligning the last elements with each other is preferred over a
+    /// gap in `b`, which is preferred over a gap in `a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::NeedlemanWunsch;
+    /// let nw = NeedlemanWunsch::new(1., 1., 1.);
+    /// let (a, b) = nw.align("abcd", "acd");
+    /// assert_eq!(a, vec![Some('a'), Some('b'), Some('c'), Some('d')]);
+    /// assert_eq!(b, vec![Some('a'), None, Some('c'), Some('d')]);
+    /// ```
+    pub fn align(&self, a: &str, b: &str) -> (Vec<Option<char>>, Vec<Option<char>>) {
+        let a: Vec<_> = a.chars().collect();
+        let b: Vec<_> = b.chars().collect();
+        self.traceback(&a, &b)
+    }
+
+    /// The score of aligning `c1` with `c2`.
+    fn substitution_score<A, B>(&self, c1: &A, c2: &B) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        if c1 == c2 {
+            self.match_score
+        } else {
+            -self.mismatch_penalty
+        }
+    }
+
+    /// The score of the optimal global alignment of `a` and `b`.
+    fn score<A, B>(&self, a: &[A], b: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        let mut cache: Vec<_> = (0..=b.len())
+            .map(|j| -(j as f64) * self.gap_penalty)
+            .collect();
+        for (i, c1) in a.iter().enumerate() {
+            let mut diagonal = cache[0];
+            cache[0] = -((i + 1) as f64) * self.gap_penalty;
+            for (j, c2) in b.iter().enumerate() {
+                let above = cache[j + 1];
+                let substitution = diagonal + self.substitution_score(c1, c2);
+                let gap = above.max(cache[j]) - self.gap_penalty;
+                cache[j + 1] = substitution.max(gap);
+                diagonal = above;
+            }
+        }
+        cache[b.len()]
+    }
+
+    /// Evaluates the complete matrix and reconstructs the optimal alignment
+    /// from the last cell.
+    ///
+    /// The step into every cell is recorded while the matrix is evaluated, so
+    /// the traceback does not depend on rederiving the scores exactly.
+    fn traceback<A, B>(&self, a: &[A], b: &[B]) -> (Vec<Option<A>>, Vec<Option<B>>)
+    where
+        A: PartialEq<B> + Clone,
+        B: Clone,
+    {
+        // the first row and column can only be reached via gaps
+        let mut steps = vec![vec![Step::GapA; b.len() + 1]; a.len() + 1];
+        for row in steps.iter_mut() {
+            row[0] = Step::GapB;
+        }
+        let mut cache: Vec<_> = (0..=b.len())
+            .map(|j| -(j as f64) * self.gap_penalty)
+            .collect();
+        for (i, c1) in a.iter().enumerate() {
+            let mut diagonal = cache[0];
+            cache[0] = -((i + 1) as f64) * self.gap_penalty;
+            for (j, c2) in b.iter().enumerate() {
+                let above = cache[j + 1];
+                let substitution = diagonal + self.substitution_score(c1, c2);
+                let gap_b = above - self.gap_penalty;
+                let gap_a = cache[j] - self.gap_penalty;
+                let (score, step) = if substitution >= gap_b && substitution >= gap_a {
+                    (substitution, Step::Align)
+                } else if gap_b >= gap_a {
+                    (gap_b, Step::GapB)
+                } else {
+                    (gap_a, Step::GapA)
+                };
+                cache[j + 1] = score;
+                steps[i + 1][j + 1] = step;
+                diagonal = above;
+            }
+        }
+
+        let mut aligned_a = Vec::with_capacity(a.len() + b.len());
+        let mut aligned_b = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (a.len(), b.len());
+        while i > 0 || j > 0 {
+            match steps[i][j] {
+                Step::Align => {
+                    aligned_a.push(Some(a[i - 1].clone()));
+                    aligned_b.push(Some(b[j - 1].clone()));
+                    i -= 1;
+                    j -= 1;
+                }
+                Step::GapB => {
+                    aligned_a.push(Some(a[i - 1].clone()));
+                    aligned_b.push(None);
+                    i -= 1;
+                }
+                Step::GapA => {
+                    aligned_a.push(None);
+                    aligned_b.push(Some(b[j - 1].clone()));
+                    j -= 1;
+                }
+            }
+        }
+        aligned_a.reverse();
+        aligned_b.reverse();
+        (aligned_a, aligned_b)
+    }
+
+    /// The distance of the optimal alignment and an upper bound of the
+    /// distance for inputs of the same lengths.
+    fn distance_and_bound<A, B>(&self, a: &[A], b: &[B]) -> (f64, f64)
+    where
+        A: PartialEq<B>,
+    {
+        let min_len = cmp::min(a.len(), b.len());
+        let len_diff = a.len().abs_diff(b.len());
+        let best = self.match_score * min_len as f64;
+        // either all elements are gaps, or the shorter input is completely
+        // mismatched and the remaining elements are gaps
+        let bound = ((a.len() + b.len()) as f64 * self.gap_penalty + best).min(
+            (self.match_score + self.mismatch_penalty) * min_len as f64
+                + len_diff as f64 * self.gap_penalty,
+        );
+        (best - self.score(a, b), bound)
+    }
+}
+
+/// The step of the traceback into a cell of the matrix of
+/// [`NeedlemanWunsch`].
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    /// The elements are aligned with each other.
+    Align,
+    /// The element of `a` is aligned to a gap in `b`.
+    GapB,
+    /// The element of `b` is aligned to a gap in `a`.
+    GapA,
+}
+
+impl DistanceMetric for NeedlemanWunsch {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.distance_and_bound(&a, &b).0
+    }
+
+    /// The distance divided by the distance of the worst possible alignment
+    /// of inputs of the same lengths.
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        let (dist, bound) = self.distance_and_bound(&a, &b);
+        if bound <= 0. {
+            0.
+        } else {
+            (dist / bound).clamp(0., 1.)
+        }
+    }
+}
+
+/// Panics unless all scores and penalties are finite and non negative.
+fn assert_valid(params: &[f64]) {
+    assert!(
+        params.iter().all(|param| param.is_finite() && *param >= 0.),
+        "the scores and penalties must be finite and non negative"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gotoh_gap_open() {
+        // "ab" / "ba" either mismatches twice, or matches `b` with two gaps
+        let cheap_gaps = Gotoh::new(1., 1., 1., 0.5);
+        assert_eq!(cheap_gaps.str_distance("ab", "ba"), 3.);
+        let expensive_gaps = Gotoh::new(1., 1., 3., 0.5);
+        assert_eq!(expensive_gaps.str_distance("ab", "ba"), 4.);
+
+        // a single long gap is preferred over two mismatches and a gap
+        let gotoh = Gotoh::new(1., 1., 2., 0.5);
+        assert_eq!(gotoh.str_distance("abcdef", "abef"), 2.5);
+        assert_eq!(gotoh.str_distance("abcdef", "abcdef"), 0.);
+        assert_eq!(gotoh.str_distance("", "abc"), 3.);
+        assert_eq!(gotoh.str_distance("", ""), 0.);
+    }
+
+    #[test]
+    fn gotoh_affine_gaps() {
+        // "aXXXb" / "ab": one gap of three vs. mismatching `b`
+        let affine = Gotoh::new(1., 1., 2., 0.1);
+        assert_eq!(format!("{:.1}", affine.str_distance("aXXXb", "ab")), "2.2");
+        let linear = Gotoh::new(1., 1., 2., 2.);
+        assert_eq!(linear.str_distance("aXXXb", "ab"), 6.);
+    }
+
+    #[test]
+    fn gotoh_symmetric() {
+        let gotoh = Gotoh::new(2., 1., 3., 1.);
+        let words = ["", "a", "kitten", "sitting", "GATTACA", "GCATGCU", "ab☃c"];
+        for a in words.iter() {
+            for b in words.iter() {
+                assert_eq!(gotoh.str_distance(a, b), gotoh.str_distance(b, a));
+                let normalized = gotoh.str_normalized(a, b);
+                assert!((0. ..=1.).contains(&normalized));
+                assert_eq!(normalized, gotoh.str_normalized(b, a));
+            }
+        }
+        assert_eq!(gotoh.str_normalized("kitten", "kitten"), 0.);
+    }
+
+    #[test]
+    fn smith_waterman_contained() {
+        let sw = SmithWaterman::new(2., 1., 1.);
+        let text = "the quick brown fox jumps over the lazy dog";
+        assert_eq!(sw.str_distance("brown fox", text), 0.);
+        assert_eq!(sw.str_distance(text, "lazy"), 0.);
+        assert!(sw.str_distance("brown fux", text) < 0.3);
+        assert_eq!(sw.str_distance("xyz", "abc"), 1.);
+        assert_eq!(sw.str_distance("", ""), 0.);
+        assert_eq!(sw.str_distance("", "abc"), 1.);
+        // bridging the gap costs more than matching "def" gains
+        assert_eq!(sw.str_distance("abcdef", "abcxxxxxxxxdef"), 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn smith_waterman_nan() {
+        SmithWaterman::new(1., f64::NAN, 1.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gotoh_negative_penalty() {
+        Gotoh::new(1., 1., -1., 0.5);
+    }
+
+    #[test]
+    fn needleman_wunsch_gattaca() {
+        let nw = NeedlemanWunsch::new(1., 1., 1.);
+        // the optimal alignment scores 0
+        assert_eq!(nw.str_distance("GATTACA", "GCATGCU"), 7.);
+        assert_eq!(nw.str_distance("GCATGCU", "GATTACA"), 7.);
+
+        let (a, b) = nw.align("GATTACA", "GCATGCU");
+        let render = |aligned: &[Option<char>]| -> String {
+            aligned.iter().map(|c| c.unwrap_or('-')).collect()
+        };
+        assert_eq!(render(&a), "G-ATTACA");
+        assert_eq!(render(&b), "GCA-TGCU");
+
+        assert_eq!(nw.str_distance("", ""), 0.);
+        assert_eq!(nw.align("", ""), (vec![], vec![]));
+        assert_eq!(
+            nw.align("ab", ""),
+            (vec![Some('a'), Some('b')], vec![None, None])
+        );
+        assert_eq!(nw.str_normalized("GATTACA", "GATTACA"), 0.);
+    }
+
+    #[test]
+    fn needleman_wunsch_fractional_gap_penalty() {
+        // the multiples of 0.1 are not exactly the sums of 0.1
+        let nw = NeedlemanWunsch::new(1., 1., 0.1);
+        let (a, b) = nw.align("abcdefgh", "");
+        assert_eq!(a.len(), 8);
+        assert!(a.iter().all(Option::is_some));
+        assert!(b.iter().all(Option::is_none));
+        let (a, b) = nw.align("", "abcdefgh");
+        assert!(a.iter().all(Option::is_none));
+        assert_eq!(b.len(), 8);
+
+        let (a, b) = nw.align("abcdefghij", "axcdefghij");
+        assert_eq!(a.iter().flatten().collect::<String>(), "abcdefghij");
+        assert_eq!(b.iter().flatten().collect::<String>(), "axcdefghij");
+    }
+}
@@ -0,0 +1,290 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::utils::Map;
+use crate::DistanceMetric;
+
+/// The Bag distance compares the multisets ("bags") of elements of both
+/// inputs, regardless of their order.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     max(|bag(s1) - bag(s2)|, |bag(s2) - bag(s1)|)
+/// ```
+///
+/// Every element that is only present in one of the bags requires at least one
+/// edit, so the Bag distance is a lower bound of the [`crate::Levenshtein`]
+/// and [`crate::DamerauLevenshtein`] distance, that is evaluated in linear time
+/// for `str`s. This makes it suitable to filter candidates before evaluating
+/// the more expensive edit distance.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{Bag, DistanceMetric, Levenshtein};
+/// assert_eq!(Bag.str_distance("kitten", "sitting"), 3);
+/// assert_eq!(Bag.str_distance("abc", "cba"), 0);
+/// assert!(Bag.str_distance("kitten", "sitting") <= *Levenshtein::default().str_distance("kitten", "sitting"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bag;
+
+impl DistanceMetric for Bag {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let (len_a, len_b, num_matches) = count_matches(a, b);
+        cmp::max(len_a, len_b) - num_matches
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (only_a, only_b) = count_unmatched_chars(a.as_ref(), b.as_ref());
+        cmp::max(only_a, only_b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let max_len = cmp::max(a.clone().count(), b.clone().count());
+        if max_len == 0 {
+            0.
+        } else {
+            self.distance(a, b) as f64 / max_len as f64
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let max_len = cmp::max(a.chars().count(), b.chars().count());
+        if max_len == 0 {
+            0.
+        } else {
+            self.str_distance(a, b) as f64 / max_len as f64
+        }
+    }
+}
+
+/// The CharHistogram distance is the Manhattan distance between the
+/// histograms of the elements of both inputs, i.e. the sum of the absolute
+/// differences of how often each element appears in either input.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     |bag(s1) - bag(s2)| + |bag(s2) - bag(s1)|
+/// ```
+///
+/// Like [`Bag`] the order of the elements is ignored, so anagrams have a
+/// distance of `0`. For `str`s this equals [`crate::QGram`] with `q = 1`,
+/// but the distance is normalized by the total number of elements of both
+/// inputs, which is the maximum distance.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{CharHistogram, DistanceMetric};
+/// assert_eq!(CharHistogram.str_distance("listen", "silent"), 0);
+/// assert_eq!(CharHistogram.str_distance("kitten", "sitting"), 5);
+/// assert_eq!(CharHistogram.str_normalized("abc", "xyz"), 1.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CharHistogram;
+
+impl DistanceMetric for CharHistogram {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let (len_a, len_b, num_matches) = count_matches(a, b);
+        len_a + len_b - 2 * num_matches
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (only_a, only_b) = count_unmatched_chars(a.as_ref(), b.as_ref());
+        only_a + only_b
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let (len_a, len_b, num_matches) = count_matches(a, b);
+        if len_a + len_b == 0 {
+            0.
+        } else {
+            (len_a + len_b - 2 * num_matches) as f64 / (len_a + len_b) as f64
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let total = a.chars().count() + b.chars().count();
+        if total == 0 {
+            0.
+        } else {
+            self.str_distance(a, b) as f64 / total as f64
+        }
+    }
+}
+
+/// The number of elements of a and b and the number of elements of a that
+/// are matched with an equal element of b, each element of b at most once.
+fn count_matches<S, T>(a: S, b: T) -> (usize, usize, usize)
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+{
+    let a: Vec<_> = a.into_iter().collect();
+    let b: Vec<_> = b.into_iter().collect();
+
+    // match every element of a with an equal, not yet matched element of b
+    let mut matched = vec![false; b.len()];
+    let mut num_matches = 0;
+    for c1 in a.iter() {
+        if let Some(idx) = b
+            .iter()
+            .enumerate()
+            .position(|(idx, c2)| !matched[idx] && c1 == c2)
+        {
+            matched[idx] = true;
+            num_matches += 1;
+        }
+    }
+    (a.len(), b.len(), num_matches)
+}
+
+/// The number of chars of a that are not in b and the number of chars of b
+/// that are not in a, counting repeated chars.
+fn count_unmatched_chars(a: &str, b: &str) -> (usize, usize) {
+    // the number of times a char appears in a minus in b
+    let mut counts: Map<char, isize> = Map::new();
+    for c in a.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    for c in b.chars() {
+        *counts.entry(c).or_default() -= 1;
+    }
+    counts.values().fold((0, 0), |(only_a, only_b), n| {
+        if *n > 0 {
+            (only_a + n.unsigned_abs(), only_b)
+        } else {
+            (only_a, only_b + n.unsigned_abs())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DamerauLevenshtein, Levenshtein, QGram};
+
+    #[test]
+    fn bag_lower_bound() {
+        let words = [
+            "",
+            "a",
+            "kitten",
+            "sitting",
+            "saturday",
+            "sunday",
+            "abc",
+            "cba",
+            "aab",
+            "abb",
+            "a☃c",
+            "rosettacode",
+            "raisethysword",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                let bag = Bag.str_distance(a, b);
+                assert_eq!(bag, Bag.distance(a.chars(), b.chars()), "{} {}", a, b);
+                assert!(
+                    bag <= *Levenshtein::default().str_distance(a, b),
+                    "{} {}",
+                    a,
+                    b
+                );
+                assert!(
+                    bag <= *DamerauLevenshtein::default().str_distance(a, b),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bag_distance() {
+        assert_eq!(Bag.str_distance("aab", "abb"), 1);
+        assert_eq!(Bag.str_distance("", "abc"), 3);
+        assert_eq!(Bag.distance(&[1, 1, 2], &[1, 2, 2, 3]), 2);
+        assert_eq!(Bag.str_normalized("abcd", "dcba"), 0.);
+        assert_eq!(Bag.str_normalized("", ""), 0.);
+    }
+
+    #[test]
+    fn char_histogram() {
+        assert_eq!(CharHistogram.str_distance("listen", "silent"), 0);
+        assert_eq!(CharHistogram.str_normalized("listen", "silent"), 0.);
+        assert_eq!(CharHistogram.str_distance("aab", "abb"), 2);
+        assert_eq!(CharHistogram.str_normalized("aab", "abb"), 2. / 6.);
+        assert_eq!(CharHistogram.str_normalized("", ""), 0.);
+        assert_eq!(CharHistogram.str_normalized("", "abc"), 1.);
+        assert_eq!(CharHistogram.distance(&[1, 1, 2], &[1, 2, 2, 3]), 3);
+        assert_eq!(CharHistogram.normalized(&[1, 1, 2], &[1, 2, 2, 3]), 3. / 7.);
+        for (a, b) in [("kitten", "sitting"), ("a☃c", "abc"), ("", "ab")].iter() {
+            let dist = CharHistogram.str_distance(a, b);
+            assert_eq!(dist, CharHistogram.distance(a.chars(), b.chars()));
+            assert_eq!(dist, QGram::new(1).str_distance(a, b));
+        }
+    }
+}
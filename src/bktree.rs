@@ -0,0 +1,243 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::utils::{Entry, Map};
+use crate::DistanceMetric;
+
+/// A BK-tree, an index of words to find all words within a maximum distance
+/// of a query word without comparing the query with every word.
+///
+/// Every child of a node is stored under its distance to the node. Due to the
+/// triangle inequality, only the children whose distance to a node differs by
+/// at most `max_dist` from the distance of the query to the node can contain
+/// matches, so all other subtrees are skipped.
+///
+/// # Remark
+///
+/// This requires `D` to be a true metric, i.e. to satisfy the triangle
+/// inequality, e.g. [`crate::Levenshtein`] or [`crate::Indel`]. Otherwise
+/// queries may miss words within the maximum distance. Metrics with a
+/// maximum distance only satisfy the triangle inequality below their
+/// maximum, so they should not be used either.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::Levenshtein;
+/// # use str_distance::bktree::BkTree;
+/// let mut tree = BkTree::new(Levenshtein::default());
+/// for word in ["book", "books", "cake", "boo", "cape", "cart"].iter() {
+///     tree.insert(*word);
+/// }
+/// assert_eq!(
+///     tree.query("bool", 1),
+///     vec![("boo".to_string(), 1), ("book".to_string(), 1)]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct BkTree<D: DistanceMetric> {
+    /// The metric to compare the words with.
+    dist: D,
+    /// The first inserted word, `None` if the tree is empty.
+    root: Option<Node>,
+    /// The number of words in the tree.
+    len: usize,
+}
+
+/// A word of a [`BkTree`] and its children by their distance to the word.
+#[derive(Debug, Clone)]
+struct Node {
+    word: String,
+    children: Map<usize, Node>,
+}
+
+impl Node {
+    fn new(word: String) -> Self {
+        Self {
+            word,
+            children: Map::new(),
+        }
+    }
+}
+
+impl<D> BkTree<D>
+where
+    D: DistanceMetric,
+    D::Dist: Into<usize>,
+{
+    /// Creates a new, empty [`BkTree`] that compares the words with distance
+    /// `D`.
+    pub fn new(dist: D) -> Self {
+        Self {
+            dist,
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Inserts the `word` into the tree.
+    ///
+    /// Returns `false` if the tree already contains a word at a distance of
+    /// `0`, in which case the `word` is not inserted.
+    pub fn insert(&mut self, word: impl Into<String>) -> bool {
+        let word = word.into();
+        let mut node = match self.root {
+            Some(ref mut root) => root,
+            None => {
+                self.root = Some(Node::new(word));
+                self.len += 1;
+                return true;
+            }
+        };
+        loop {
+            let dist = self.dist.str_distance(&node.word, &word).into();
+            if dist == 0 {
+                return false;
+            }
+            node = match node.children.entry(dist) {
+                Entry::Occupied(child) => child.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(Node::new(word));
+                    self.len += 1;
+                    return true;
+                }
+            };
+        }
+    }
+
+    /// Finds all words whose distance to the `word` is at most `max_dist`.
+    ///
+    /// Returns the words together with their distance, ordered by the
+    /// distance and then by the words.
+    pub fn query(&self, word: &str, max_dist: usize) -> Vec<(String, usize)> {
+        let mut matches = Vec::new();
+        let mut stack: Vec<&Node> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            let dist = self.dist.str_distance(&node.word, word).into();
+            if dist <= max_dist {
+                matches.push((node.word.to_string(), dist));
+            }
+            let (min, max) = (dist.saturating_sub(max_dist), dist + max_dist);
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_dist, _)| min <= **child_dist && **child_dist <= max)
+                    .map(|(_, child)| child),
+            );
+        }
+        matches.sort_unstable_by(|(word_a, dist_a), (word_b, dist_b)| {
+            dist_a.cmp(dist_b).then_with(|| word_a.cmp(word_b))
+        });
+        matches
+    }
+
+    /// The number of words in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the tree contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The metric the words are compared with.
+    pub fn metric(&self) -> &D {
+        &self.dist
+    }
+
+    /// All words of the tree, in no particular order.
+    pub fn words(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut stack: Vec<&Node> = self.root.iter().collect();
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.values());
+            Some(node.word.as_str())
+        })
+    }
+}
+
+impl<D, S> Extend<S> for BkTree<D>
+where
+    D: DistanceMetric,
+    D::Dist: Into<usize>,
+    S: Into<String>,
+{
+    fn extend<I: IntoIterator<Item = S>>(&mut self, words: I) {
+        for word in words {
+            self.insert(word);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Indel, Levenshtein};
+
+    const WORDS: [&str; 12] = [
+        "book", "books", "boo", "boon", "cook", "cake", "cape", "cart", "hook", "look", "brook", "",
+    ];
+
+    /// Asserts that the tree finds the same words as a scan of all words.
+    fn assert_queries<D>(dist: D)
+    where
+        D: DistanceMetric + Clone,
+        D::Dist: Into<usize>,
+    {
+        let mut tree = BkTree::new(dist.clone());
+        tree.extend(WORDS.iter().copied());
+        assert_eq!(tree.len(), WORDS.len());
+        for query in ["book", "bool", "cap", "", "xyz", "brooks"].iter() {
+            for max_dist in 0..4 {
+                let mut expected: Vec<_> = WORDS
+                    .iter()
+                    .map(|word| (word.to_string(), dist.str_distance(word, query).into()))
+                    .filter(|(_, d)| *d <= max_dist)
+                    .collect();
+                expected.sort_unstable_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+                assert_eq!(
+                    tree.query(query, max_dist),
+                    expected,
+                    "{} {}",
+                    query,
+                    max_dist
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bktree_query() {
+        let mut tree = BkTree::new(Levenshtein::default());
+        assert!(tree.is_empty());
+        assert!(tree.query("book", 1).is_empty());
+        tree.extend(WORDS.iter().copied());
+        assert!(!tree.insert("book"));
+        assert_eq!(tree.len(), WORDS.len());
+
+        let words: Vec<_> = tree
+            .query("book", 1)
+            .into_iter()
+            .map(|(word, _)| word)
+            .collect();
+        assert_eq!(
+            words,
+            vec!["book", "boo", "books", "boon", "brook", "cook", "hook", "look"]
+        );
+        assert_eq!(tree.query("cap", 1), vec![("cape".to_string(), 1)]);
+        assert_eq!(tree.query("xyz", 2), Vec::new());
+
+        let mut words: Vec<_> = tree.words().collect();
+        words.sort_unstable();
+        let mut expected = WORDS.to_vec();
+        expected.sort_unstable();
+        assert_eq!(words, expected);
+    }
+
+    #[test]
+    fn bktree_matches_scan() {
+        assert_queries(Levenshtein::default());
+        assert_queries(Indel);
+    }
+}
@@ -0,0 +1,370 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::utils::Map;
+use crate::DistanceMetric;
+
+/// Groups near duplicates in `items` by approximating the connected components
+/// of the graph, in which two items are connected if their normalized distance
+/// is below or equal to the `threshold`.
+///
+/// Returns the indices of the items of each component in ascending order. The
+/// components themselves are ordered by their first index, items without any
+/// near duplicate are returned as a component of their own.
+///
+/// # Remark
+///
+/// Components are the transitive closure of the "within threshold" relation:
+/// if `A ~ B` and `B ~ C` then `A`, `B` and `C` end up in the same component,
+/// even if the distance between `A` and `C` exceeds the `threshold`.
+///
+/// To avoid evaluating the distance of all pairs, only pairs that share at
+/// least one character bigram are compared, items with less than two
+/// characters are compared to all others. Pairs whose items already belong to
+/// the same component are also skipped.
+///
+/// The components are therefore approximate: pairs that have no bigram in
+/// common are never connected, even if their distance is within the
+/// `threshold`, e.g. `"abcd"` and `"badc"` under [`crate::Jaro`] with a
+/// `threshold` of `0.2`. Use [`cluster`] to compare all pairs.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{dedup_components, Levenshtein};
+/// let items = ["color", "colour", "flavor", "flavour"];
+/// assert_eq!(
+///     dedup_components(&items, 0.2, &Levenshtein::default()),
+///     vec![vec![0, 1], vec![2, 3]]
+/// );
+/// ```
+pub fn dedup_components<D: DistanceMetric>(
+    items: &[&str],
+    threshold: f64,
+    dist: &D,
+) -> Vec<Vec<usize>> {
+    let chars: Vec<Vec<char>> = items.iter().map(|s| s.chars().collect()).collect();
+
+    // inverted index of all items that contain a bigram
+    let mut index: Map<&[char], Vec<usize>> = Map::new();
+    // items that are too short to contain a bigram
+    let mut short = Vec::new();
+    for (idx, item) in chars.iter().enumerate() {
+        if item.len() < 2 {
+            short.push(idx);
+            continue;
+        }
+        for bigram in item.windows(2) {
+            let ids = index.entry(bigram).or_default();
+            if ids.last() != Some(&idx) {
+                ids.push(idx);
+            }
+        }
+    }
+
+    let mut components = UnionFind::new(items.len());
+    let mut is_candidate = vec![false; items.len()];
+
+    for (idx, item) in chars.iter().enumerate() {
+        let mut candidates = Vec::new();
+        let mut add = |other: usize| {
+            if other > idx && !is_candidate[other] {
+                is_candidate[other] = true;
+                candidates.push(other);
+            }
+        };
+        if item.len() < 2 {
+            (idx + 1..items.len()).for_each(&mut add);
+        } else {
+            short.iter().copied().for_each(&mut add);
+            for bigram in item.windows(2) {
+                index[bigram].iter().copied().for_each(&mut add);
+            }
+        }
+
+        for other in candidates {
+            is_candidate[other] = false;
+            if components.find(idx) == components.find(other) {
+                continue;
+            }
+            if dist.str_normalized(items[idx], items[other]) <= threshold {
+                components.union(idx, other);
+            }
+        }
+    }
+
+    components.into_components()
+}
+
+/// Clusters near duplicates in `items` like [`dedup_components`], but
+/// compares all pairs of items whose normalized distance may be below or equal
+/// to the `threshold`.
+///
+/// Two items are connected if their normalized distance is below or equal to
+/// the `threshold`, i.e. `dist <= threshold`, so a `threshold` of `0.` still
+/// clusters items at a distance of `0.`.
+///
+/// Returns the indices of the items of each cluster in ascending order. The
+/// clusters themselves are ordered by their first index, items without any
+/// near duplicate are returned as a cluster of their own. Like the components
+/// of [`dedup_components`], clusters are transitive.
+///
+/// Unlike [`dedup_components`], pairs without a common character bigram are
+/// compared as well, so no pair within the `threshold` is missed. Pairs are
+/// compared via [`DistanceMetric::str_distance_within`], which lets metrics
+/// reject pairs early: [`crate::Levenshtein`] for example skips pairs whose
+/// difference in length alone exceeds the `threshold`. Pairs whose items
+/// already belong to the same cluster are skipped. To reject pairs with a
+/// cheaper metric first, see [`cluster_with_prefilter`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{cluster, Levenshtein};
+/// let items = ["color", "colour", "flavor", "flavour"];
+/// assert_eq!(
+///     cluster(&items, 0.2, &Levenshtein::default()),
+///     vec![vec![0, 1], vec![2, 3]]
+/// );
+/// ```
+pub fn cluster<D: DistanceMetric>(items: &[&str], threshold: f64, dist: &D) -> Vec<Vec<usize>> {
+    cluster_by(items, threshold, dist, |_, _| true)
+}
+
+/// Clusters near duplicates in `items` like [`cluster`], but skips all pairs
+/// whose normalized distance according to the cheaper `prefilter` exceeds the
+/// `threshold`.
+///
+/// The result equals the one of [`cluster`] as long as the normalized
+/// distance of the `prefilter` is a lower bound of the normalized distance of
+/// `dist` for all pairs, e.g.
+///  - [`crate::LengthRatio`] or [`crate::Bag`] for [`crate::Levenshtein`] and
+///    [`crate::DamerauLevenshtein`], since every edit changes the length and
+///    the bag distance by at most 1, and both are normalized by the length of
+///    the longer str.
+///  - [`crate::CharHistogram`] for [`crate::Indel`], since every char of the
+///    longest common subsequence is also a match of the char histograms, and
+///    both are normalized by the total number of chars.
+///
+/// Any other `prefilter` is a heuristic that may miss pairs within the
+/// `threshold`.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{cluster, cluster_with_prefilter, Bag, Levenshtein};
+/// let items = ["color", "colour", "flavor", "flavour"];
+/// assert_eq!(
+///     cluster_with_prefilter(&items, 0.2, &Levenshtein::default(), &Bag),
+///     cluster(&items, 0.2, &Levenshtein::default())
+/// );
+/// ```
+pub fn cluster_with_prefilter<D, P>(
+    items: &[&str],
+    threshold: f64,
+    dist: &D,
+    prefilter: &P,
+) -> Vec<Vec<usize>>
+where
+    D: DistanceMetric,
+    P: DistanceMetric,
+{
+    cluster_by(items, threshold, dist, |a, b| {
+        prefilter.str_normalized(a, b) <= threshold
+    })
+}
+
+/// Clusters the items, only comparing the pairs that `could_match`.
+fn cluster_by<D, F>(items: &[&str], threshold: f64, dist: &D, could_match: F) -> Vec<Vec<usize>>
+where
+    D: DistanceMetric,
+    F: Fn(&str, &str) -> bool,
+{
+    let mut clusters = UnionFind::new(items.len());
+    for (idx, item) in items.iter().enumerate() {
+        for (other, other_item) in items.iter().enumerate().skip(idx + 1) {
+            if clusters.find(idx) == clusters.find(other) || !could_match(item, other_item) {
+                continue;
+            }
+            if dist
+                .str_distance_within(item, other_item, threshold)
+                .is_some()
+            {
+                clusters.union(idx, other);
+            }
+        }
+    }
+    clusters.into_components()
+}
+
+/// A disjoint-set forest with path compression and union by rank.
+#[derive(Debug, Clone)]
+pub(crate) struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Creates `len` singleton sets.
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+        }
+    }
+
+    /// Returns the representative of the set that contains `idx`.
+    pub(crate) fn find(&mut self, idx: usize) -> usize {
+        let mut root = idx;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut idx = idx;
+        while self.parent[idx] != root {
+            let next = self.parent[idx];
+            self.parent[idx] = root;
+            idx = next;
+        }
+        root
+    }
+
+    /// Merges the sets that contain `a` and `b`.
+    pub(crate) fn union(&mut self, a: usize, b: usize) {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return;
+        }
+        if self.rank[a] < self.rank[b] {
+            self.parent[a] = b;
+        } else {
+            self.parent[b] = a;
+            if self.rank[a] == self.rank[b] {
+                self.rank[a] += 1;
+            }
+        }
+    }
+
+    /// Returns all sets with their members in ascending order, ordered by
+    /// their smallest member.
+    pub(crate) fn into_components(mut self) -> Vec<Vec<usize>> {
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut component_of_root: Vec<Option<usize>> = vec![None; self.parent.len()];
+        for idx in 0..self.parent.len() {
+            let root = self.find(idx);
+            match component_of_root[root] {
+                Some(component) => components[component].push(idx),
+                None => {
+                    component_of_root[root] = Some(components.len());
+                    components.push(vec![idx]);
+                }
+            }
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bag, CharHistogram, Indel, Jaro, LengthRatio, Levenshtein};
+
+    #[test]
+    fn dedup_levenshtein() {
+        let items = ["color", "colour", "colr", "flavor", "flavour", "xyz"];
+        assert_eq!(
+            dedup_components(&items, 0.2, &Levenshtein::default()),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+        assert_eq!(
+            dedup_components(&items, 0., &Levenshtein::default()),
+            vec![vec![0], vec![1], vec![2], vec![3], vec![4], vec![5]]
+        );
+        assert!(dedup_components(&[], 0.2, &Levenshtein::default()).is_empty());
+    }
+
+    #[test]
+    fn dedup_short_items() {
+        let items = ["a", "ab", "", "b", "a"];
+        assert_eq!(
+            dedup_components(&items, 0., &Jaro),
+            vec![vec![0, 4], vec![1], vec![2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn cluster_levenshtein() {
+        let items = ["color", "colour", "flavor", "flavour"];
+        assert_eq!(
+            cluster(&items, 0.2, &Levenshtein::default()),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        let items = ["color", "colour", "colr", "flavor", "flavour", "xyz"];
+        assert_eq!(
+            cluster(&items, 0.2, &Levenshtein::default()),
+            dedup_components(&items, 0.2, &Levenshtein::default())
+        );
+        assert!(cluster(&[], 0.2, &Levenshtein::default()).is_empty());
+    }
+
+    #[test]
+    fn cluster_without_common_bigram() {
+        // "abcd" and "badc" share no bigram, but are within the threshold
+        let items = ["abcd", "badc", "xyz"];
+        assert!(Jaro.str_normalized("abcd", "badc") <= 0.2);
+        assert_eq!(
+            dedup_components(&items, 0.2, &Jaro),
+            vec![vec![0], vec![1], vec![2]]
+        );
+        assert_eq!(cluster(&items, 0.2, &Jaro), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn cluster_is_transitive() {
+        // "abcd" ~ "abce" ~ "abfe", but "abcd" and "abfe" differ by 2 chars
+        let items = ["abfe", "xyz", "abcd", "abce"];
+        assert_eq!(
+            cluster(&items, 0.25, &Levenshtein::default()),
+            vec![vec![0, 2, 3], vec![1]]
+        );
+    }
+
+    #[test]
+    fn cluster_prefilter() {
+        let items = [
+            "color", "colour", "colr", "flavor", "flavour", "xyz", "", "a", "abcd", "badc",
+            "kitten", "sitting", "mitten",
+        ];
+        for threshold in [0., 0.2, 0.3, 0.5, 0.8].iter() {
+            let lev = cluster(&items, *threshold, &Levenshtein::default());
+            assert_eq!(
+                cluster_with_prefilter(&items, *threshold, &Levenshtein::default(), &Bag),
+                lev
+            );
+            assert_eq!(
+                cluster_with_prefilter(&items, *threshold, &Levenshtein::default(), &LengthRatio),
+                lev
+            );
+            assert_eq!(
+                cluster_with_prefilter(&items, *threshold, &Indel, &CharHistogram),
+                cluster(&items, *threshold, &Indel)
+            );
+        }
+        // the distance equals the threshold
+        assert_eq!(
+            cluster_with_prefilter(&["ab", "abc"], 1. / 3., &Levenshtein::default(), &Bag),
+            vec![vec![0, 1]]
+        );
+    }
+
+    #[test]
+    fn union_find() {
+        let mut uf = UnionFind::new(5);
+        uf.union(3, 1);
+        uf.union(4, 0);
+        uf.union(1, 4);
+        assert_eq!(uf.find(0), uf.find(3));
+        assert_ne!(uf.find(0), uf.find(2));
+        assert_eq!(uf.into_components(), vec![vec![0, 1, 3, 4], vec![2]]);
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A single operation of a line based diff.
+///
+/// Indices refer to the position of a line in the first (`a`) or second (`b`)
+/// sequence of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineOp {
+    /// The line `a` of the first sequence is kept as line `b` of the second
+    /// sequence.
+    Keep { a: usize, b: usize },
+    /// The line of the first sequence was deleted.
+    Delete(usize),
+    /// The line of the second sequence was inserted.
+    Insert(usize),
+    /// The line `from` of the first sequence was moved to line `to` of the
+    /// second sequence.
+    Move { from: usize, to: usize },
+}
+
+/// Computes the line based diff between `a` and `b`, detecting lines that were
+/// moved to a different position.
+///
+/// The lines are first aligned via their longest common subsequence, which
+/// only yields [`LineOp::Keep`], [`LineOp::Delete`] and [`LineOp::Insert`].
+/// Afterwards every inserted line is matched with the first deleted line that
+/// is equal and not yet matched. Each matched pair is reported as a single
+/// [`LineOp::Move`] at the position of the insertion, the deletion is removed
+/// from the result.
+///
+/// Since the alignment keeps the longest common subsequence, the lines
+/// reported as moved are always the ones outside of it: swapping two blocks
+/// reports the smaller block as moved.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::diff::{line_diff_with_moves, LineOp};
+/// let a = ["a", "b", "c"];
+/// let b = ["b", "c", "a"];
+/// assert_eq!(
+///     line_diff_with_moves(&a, &b),
+///     vec![
+///         LineOp::Keep { a: 1, b: 0 },
+///         LineOp::Keep { a: 2, b: 1 },
+///         LineOp::Move { from: 0, to: 2 }
+///     ]
+/// );
+/// ```
+pub fn line_diff_with_moves(a: &[&str], b: &[&str]) -> Vec<LineOp> {
+    let mut ops = lcs_line_diff(a, b);
+
+    let mut matched_delete = vec![false; ops.len()];
+    for idx in 0..ops.len() {
+        if let LineOp::Insert(to) = ops[idx] {
+            let delete = ops.iter().enumerate().position(|(op_idx, op)| match op {
+                LineOp::Delete(from) => !matched_delete[op_idx] && a[*from] == b[to],
+                _ => false,
+            });
+            if let Some(op_idx) = delete {
+                matched_delete[op_idx] = true;
+                if let LineOp::Delete(from) = ops[op_idx] {
+                    ops[idx] = LineOp::Move { from, to };
+                }
+            }
+        }
+    }
+
+    ops.into_iter()
+        .zip(matched_delete)
+        .filter(|(_, matched)| !matched)
+        .map(|(op, _)| op)
+        .collect()
+}
+
+/// Aligns the lines via their longest common subsequence.
+fn lcs_line_diff(a: &[&str], b: &[&str]) -> Vec<LineOp> {
+    // lcs[i][j] is the length of the lcs of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                core::cmp::max(lcs[i + 1][j], lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ops.push(LineOp::Keep { a: i, b: j });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(LineOp::Delete(i));
+            i += 1;
+        } else {
+            ops.push(LineOp::Insert(j));
+            j += 1;
+        }
+    }
+    ops.extend((i..a.len()).map(LineOp::Delete));
+    ops.extend((j..b.len()).map(LineOp::Insert));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocated_block() {
+        let a = ["fn a() {", "}", "", "fn b() {", "}", "fn c() {", "}"];
+        let b = ["fn b() {", "}", "fn a() {", "}", "", "fn c() {", "}"];
+        assert_eq!(
+            line_diff_with_moves(&a, &b),
+            vec![
+                LineOp::Move { from: 3, to: 0 },
+                LineOp::Move { from: 4, to: 1 },
+                LineOp::Keep { a: 0, b: 2 },
+                LineOp::Keep { a: 1, b: 3 },
+                LineOp::Keep { a: 2, b: 4 },
+                LineOp::Keep { a: 5, b: 5 },
+                LineOp::Keep { a: 6, b: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_delete() {
+        let a = ["a", "b", "c"];
+        let b = ["a", "x", "c", "d"];
+        assert_eq!(
+            line_diff_with_moves(&a, &b),
+            vec![
+                LineOp::Keep { a: 0, b: 0 },
+                LineOp::Delete(1),
+                LineOp::Insert(1),
+                LineOp::Keep { a: 2, b: 2 },
+                LineOp::Insert(3),
+            ]
+        );
+        assert_eq!(line_diff_with_moves(&[], &["a"]), vec![LineOp::Insert(0)]);
+        assert_eq!(line_diff_with_moves(&["a"], &[]), vec![LineOp::Delete(0)]);
+        assert!(line_diff_with_moves(&[], &[]).is_empty());
+    }
+}
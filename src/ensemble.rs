@@ -0,0 +1,322 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{DistanceMetric, StrMetric};
+
+/// The Ensemble distance is the weighted average of the normalized distances
+/// of several metrics, that are chosen at runtime via [`StrMetric`].
+///
+/// The weights are normalized to sum up to 1, so only their ratios matter.
+///
+/// For other types than strings the metrics can't be evaluated, so the
+/// distance is `0.` if both inputs are equal and `1.` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Ensemble, Jaro, Levenshtein, StrMetric};
+/// let ensemble = Ensemble::new(vec![
+///     (Box::new(Levenshtein::default()) as Box<dyn StrMetric>, 1.),
+///     (Box::new(Jaro), 3.),
+/// ]);
+/// let expected = 0.25 * Levenshtein::default().str_normalized("kitten", "sitting")
+///     + 0.75 * Jaro.str_normalized("kitten", "sitting");
+/// assert!((ensemble.str_distance("kitten", "sitting") - expected).abs() < 1e-12);
+/// ```
+pub struct Ensemble {
+    /// The metrics together with their normalized weights.
+    metrics: Vec<(Box<dyn StrMetric>, f64)>,
+}
+
+impl Ensemble {
+    /// Creates a new [`Ensemble`] of the `metrics` and their weights.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a weight is negative or if the weights don't sum up to a
+    /// positive number, e.g. if there are no metrics.
+    pub fn new(metrics: Vec<(Box<dyn StrMetric>, f64)>) -> Self {
+        assert!(metrics.iter().all(|(_, weight)| *weight >= 0.));
+        let total: f64 = metrics.iter().map(|(_, weight)| weight).sum();
+        assert!(total > 0. && total.is_finite());
+        let metrics = metrics
+            .into_iter()
+            .map(|(metric, weight)| (metric, weight / total))
+            .collect();
+        Self { metrics }
+    }
+
+    /// The normalized weights of the metrics, in the order of the metrics.
+    pub fn weights(&self) -> impl Iterator<Item = f64> + '_ {
+        self.metrics.iter().map(|(_, weight)| *weight)
+    }
+}
+
+impl DistanceMetric for Ensemble {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        if a.into_iter().eq(b) {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        self.metrics
+            .iter()
+            .map(|(metric, weight)| weight * metric.str_normalized_f64(a, b))
+            .sum()
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The MinMetric distance is the minimum normalized distance of several
+/// metrics, i.e. the most optimistic one, see [`MaxMetric`] for the most
+/// pessimistic one.
+///
+/// For other types than strings the metrics can't be evaluated, so the
+/// distance is `0.` if both inputs are equal and `1.` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Jaro, Levenshtein, MinMetric};
+/// let min = MinMetric::new(vec![Box::new(Levenshtein::default()), Box::new(Jaro)]);
+/// assert_eq!(min.str_distance("kitten", "sitting"), Jaro.str_distance("kitten", "sitting"));
+/// ```
+pub struct MinMetric {
+    /// The metrics to take the minimum distance of.
+    metrics: Vec<Box<dyn StrMetric>>,
+}
+
+impl MinMetric {
+    /// Creates a new [`MinMetric`] of the `metrics`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no metrics.
+    pub fn new(metrics: Vec<Box<dyn StrMetric>>) -> Self {
+        assert!(!metrics.is_empty());
+        Self { metrics }
+    }
+}
+
+/// The MaxMetric distance is the maximum normalized distance of several
+/// metrics, i.e. the most pessimistic one, see [`MinMetric`] for the most
+/// optimistic one.
+///
+/// For other types than strings the metrics can't be evaluated, so the
+/// distance is `0.` if both inputs are equal and `1.` otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Jaro, Levenshtein, MaxMetric};
+/// let max = MaxMetric::new(vec![Box::new(Levenshtein::default()), Box::new(Jaro)]);
+/// assert_eq!(max.str_distance("kitten", "sitting"), Levenshtein::default().str_normalized("kitten", "sitting"));
+/// ```
+pub struct MaxMetric {
+    /// The metrics to take the maximum distance of.
+    metrics: Vec<Box<dyn StrMetric>>,
+}
+
+impl MaxMetric {
+    /// Creates a new [`MaxMetric`] of the `metrics`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no metrics.
+    pub fn new(metrics: Vec<Box<dyn StrMetric>>) -> Self {
+        assert!(!metrics.is_empty());
+        Self { metrics }
+    }
+}
+
+/// Implements [`DistanceMetric`] for combinators of boxed metrics, that reduce
+/// the normalized distances of all `metrics` with `f64::min` or `f64::max`.
+macro_rules! impl_extremum_metric {
+    ($($metric:ty => $reduce:path),*) => {
+        $(
+            impl DistanceMetric for $metric {
+                type Dist = f64;
+
+                fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+                where
+                    S: IntoIterator,
+                    T: IntoIterator,
+                    <S as IntoIterator>::IntoIter: Clone,
+                    <T as IntoIterator>::IntoIter: Clone,
+                    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+                    <T as IntoIterator>::Item: PartialEq,
+                {
+                    if a.into_iter().eq(b) {
+                        0.
+                    } else {
+                        1.
+                    }
+                }
+
+                fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+                where
+                    S: AsRef<str>,
+                    T: AsRef<str>,
+                {
+                    let (a, b) = (a.as_ref(), b.as_ref());
+                    self.metrics
+                        .iter()
+                        .map(|metric| metric.str_normalized_f64(a, b))
+                        .reduce($reduce)
+                        .expect("at least one metric")
+                }
+
+                fn normalized<S, T>(&self, a: S, b: T) -> f64
+                where
+                    S: IntoIterator,
+                    T: IntoIterator,
+                    <S as IntoIterator>::IntoIter: Clone,
+                    <T as IntoIterator>::IntoIter: Clone,
+                    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+                    <T as IntoIterator>::Item: PartialEq,
+                {
+                    self.distance(a, b)
+                }
+
+                fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+                where
+                    S: AsRef<str>,
+                    T: AsRef<str>,
+                {
+                    self.str_distance(a, b)
+                }
+            }
+        )*
+    };
+}
+
+impl_extremum_metric!(MinMetric => f64::min, MaxMetric => f64::max);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Jaro, Levenshtein};
+
+    fn lev_jaro(weight_lev: f64, weight_jaro: f64) -> Ensemble {
+        Ensemble::new(vec![
+            (Box::new(Levenshtein::default()), weight_lev),
+            (Box::new(Jaro), weight_jaro),
+        ])
+    }
+
+    #[test]
+    fn ensemble() {
+        let ensemble = lev_jaro(0.5, 0.5);
+        for (a, b) in [("kitten", "sitting"), ("martha", "marhta"), ("abc", "xyz")].iter() {
+            let lev = Levenshtein::default().str_normalized(a, b);
+            let jaro = Jaro.str_normalized(a, b);
+            let dist = ensemble.str_distance(a, b);
+            assert!((dist - (lev + jaro) / 2.).abs() < 1e-12);
+            assert!(
+                lev.min(jaro) <= dist && dist <= lev.max(jaro),
+                "{} {}",
+                a,
+                b
+            );
+        }
+        assert_eq!(ensemble.str_distance("", ""), 0.);
+        assert_eq!(ensemble.str_distance("kitten", "kitten"), 0.);
+        assert_eq!(ensemble.distance(&[1, 2], &[1, 2]), 0.);
+        assert_eq!(ensemble.distance(&[1, 2], &[2, 1]), 1.);
+
+        // only the ratio of the weights matters
+        let scaled = lev_jaro(4., 4.);
+        assert_eq!(scaled.weights().collect::<Vec<_>>(), vec![0.5, 0.5]);
+        assert_eq!(
+            scaled.str_distance("kitten", "sitting"),
+            ensemble.str_distance("kitten", "sitting")
+        );
+        let only_jaro = lev_jaro(0., 2.);
+        assert_eq!(
+            only_jaro.str_distance("kitten", "sitting"),
+            Jaro.str_distance("kitten", "sitting")
+        );
+    }
+
+    #[test]
+    fn min_max_metric() {
+        let min = MinMetric::new(vec![Box::new(Levenshtein::default()), Box::new(Jaro)]);
+        let max = MaxMetric::new(vec![Box::new(Levenshtein::default()), Box::new(Jaro)]);
+        let words = [
+            "", "a", "kitten", "sitting", "martha", "marhta", "abc", "xyz",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                let lev = Levenshtein::default().str_normalized(a, b);
+                let jaro = Jaro.str_normalized(a, b);
+                let (min, max) = (min.str_distance(a, b), max.str_distance(a, b));
+                assert!(min <= lev && min <= jaro, "{} {}", a, b);
+                assert!(max >= lev && max >= jaro, "{} {}", a, b);
+                assert_eq!(min, lev.min(jaro));
+                assert_eq!(max, lev.max(jaro));
+            }
+        }
+        let single = MinMetric::new(vec![Box::new(Jaro)]);
+        assert_eq!(
+            single.str_distance("martha", "marhta"),
+            Jaro.str_distance("martha", "marhta")
+        );
+        assert_eq!(max.distance(&[1, 2], &[1, 2]), 0.);
+        assert_eq!(min.distance(&[1, 2], &[2, 1]), 1.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn min_metric_empty() {
+        MinMetric::new(Vec::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ensemble_negative_weight() {
+        lev_jaro(-1., 2.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ensemble_zero_weights() {
+        lev_jaro(0., 0.);
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::string::String;
+use core::cmp;
+
+use crate::utils;
+use crate::{DistanceMetric, Partial, RatcliffObershelp, TokenSet, TokenSort};
+
+/// The similarity of `a` and `b` as an integer percentage between 0 and 100,
+/// like fuzzywuzzy's `fuzz.ratio`.
+///
+/// This is the [`DistanceMetric::score_100`] of [`RatcliffObershelp`]. Unlike
+/// fuzzywuzzy, which returns 0 if a str is empty, two empty str are equal and
+/// score 100, only an empty and a non-empty str score 0.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::fuzzywuzzy::ratio;
+/// assert_eq!(ratio("this is a test", "this is a test!"), 97);
+/// assert_eq!(ratio("", ""), 100);
+/// ```
+pub fn ratio<S, T>(a: S, b: T) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    RatcliffObershelp.score_100(a, b)
+}
+
+/// The similarity of the shorter str and its best matching substring of the
+/// longer str as an integer percentage between 0 and 100, like fuzzywuzzy's
+/// `fuzz.partial_ratio`.
+///
+/// This is the [`DistanceMetric::score_100`] of [`Partial`] with
+/// [`RatcliffObershelp`]. Unlike fuzzywuzzy, which only compares the
+/// substrings that start at the matching blocks of both str, all substrings
+/// of the length of the shorter str are compared, so the score may be higher.
+/// Like for [`ratio`], two empty str score 100 instead of 0.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::fuzzywuzzy::partial_ratio;
+/// assert_eq!(partial_ratio("this is a test", "this is a test!"), 100);
+/// ```
+pub fn partial_ratio<S, T>(a: S, b: T) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    Partial::new(RatcliffObershelp).score_100(a, b)
+}
+
+/// The [`ratio`] of `a` and `b` after sorting their words alphabetically, like
+/// fuzzywuzzy's `fuzz.token_sort_ratio`.
+///
+/// This is the [`DistanceMetric::score_100`] of [`TokenSort`] with
+/// [`RatcliffObershelp`]. Unlike fuzzywuzzy, the str are not lowercased and
+/// non-alphanumeric chars are kept.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::fuzzywuzzy::token_sort_ratio;
+/// assert_eq!(token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"), 100);
+/// ```
+pub fn token_sort_ratio<S, T>(a: S, b: T) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    TokenSort::new(RatcliffObershelp).score_100(a, b)
+}
+
+/// The best [`ratio`] of the common words of `a` and `b` and the words of each
+/// str, like fuzzywuzzy's `fuzz.token_set_ratio`.
+///
+/// This is the score of [`TokenSet`] with [`RatcliffObershelp`]. Unlike
+/// fuzzywuzzy, the str are not lowercased and non-alphanumeric chars are kept.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::fuzzywuzzy::token_set_ratio;
+/// assert_eq!(token_set_ratio("fuzzy was a bear", "fuzzy fuzzy was a bear"), 100);
+/// ```
+pub fn token_set_ratio<S, T>(a: S, b: T) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    // the distance of `TokenSet` with `RatcliffObershelp` is already normalized
+    score(TokenSet::new(RatcliffObershelp).str_distance(a, b))
+}
+
+/// The best of the scores of [`ratio`], [`partial_ratio`], [`token_sort_ratio`]
+/// and [`token_set_ratio`] weighted by the length ratio of `a` and `b`, like
+/// fuzzywuzzy's `fuzz.WRatio`.
+///
+/// Both str are lowercased and all chars that are not alphanumeric are
+/// replaced with whitespace first. Then
+///
+///  - if the longer str has less than 1.5 times the chars of the shorter str,
+///    the result is the maximum of the [`ratio`] and `0.95` times the
+///    [`token_sort_ratio`] and [`token_set_ratio`].
+///  - otherwise the partial scores are used, the result is the maximum of the
+///    [`ratio`], `0.9` times the [`partial_ratio`], and `0.95 * 0.9` times the
+///    partial token sort and token set ratios. If the longer str has more than
+///    8 times the chars of the shorter str, the partial scores are scaled with
+///    `0.6` instead of `0.9`.
+///
+/// Returns 0 if a str is empty after processing.
+///
+/// Since the token ratios are scaled, str that only match after reordering
+/// their words score at most 95, even if their [`token_set_ratio`] is 100.
+/// This is the score of fuzzywuzzy's `fuzz.WRatio` as well.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::fuzzywuzzy::{token_set_ratio, wratio};
+/// assert_eq!(wratio("this is a test", "this is a test!"), 100);
+/// assert_eq!(token_set_ratio("Real Madrid vs FC Barcelona", "Barcelona vs Real Madrid"), 100);
+/// assert_eq!(wratio("Real Madrid vs FC Barcelona", "Barcelona vs Real Madrid"), 95);
+/// assert_eq!(wratio("YANKEES", "New York Yankees"), 90);
+/// ```
+pub fn wratio<S, T>(a: S, b: T) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    const UNBASE_SCALE: f64 = 0.95;
+
+    let a = full_process(a.as_ref());
+    let b = full_process(b.as_ref());
+    let len_a = a.chars().count();
+    let len_b = b.chars().count();
+    if len_a == 0 || len_b == 0 {
+        return 0;
+    }
+
+    let base = f64::from(ratio(&a, &b));
+    let len_ratio = cmp::max(len_a, len_b) as f64 / cmp::min(len_a, len_b) as f64;
+
+    let best = if len_ratio < 1.5 {
+        let token_sort = f64::from(token_sort_ratio(&a, &b)) * UNBASE_SCALE;
+        let token_set = f64::from(token_set_ratio(&a, &b)) * UNBASE_SCALE;
+        base.max(token_sort).max(token_set)
+    } else {
+        let partial_scale = if len_ratio > 8. { 0.6 } else { 0.9 };
+        let partial = f64::from(partial_ratio(&a, &b)) * partial_scale;
+        let partial_token_sort =
+            f64::from(TokenSort::new(Partial::new(RatcliffObershelp)).score_100(&a, &b))
+                * UNBASE_SCALE
+                * partial_scale;
+        let partial_token_set = f64::from(score(
+            TokenSet::new(Partial::new(RatcliffObershelp)).str_distance(&a, &b),
+        )) * UNBASE_SCALE
+            * partial_scale;
+        base.max(partial)
+            .max(partial_token_sort)
+            .max(partial_token_set)
+    };
+    utils::round(best) as u8
+}
+
+/// Lowercases `s` and replaces all chars that are not alphanumeric with
+/// whitespace, like fuzzywuzzy's `full_process`.
+fn full_process(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| {
+            let c = if c.is_alphanumeric() { c } else { ' ' };
+            c.to_lowercase()
+        })
+        .collect::<String>()
+        .trim()
+        .into()
+}
+
+/// `(1 - dist) * 100`, rounded half-up to the nearest integer like
+/// [`DistanceMetric::score_100`].
+fn score(dist: f64) -> u8 {
+    utils::round((1. - dist) * 100.).clamp(0., 100.) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzywuzzy_ratios() {
+        // the documented outputs of fuzzywuzzy
+        assert_eq!(ratio("this is a test", "this is a test!"), 97);
+        assert_eq!(
+            ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            91
+        );
+        assert_eq!(ratio("YANKEES", "NEW YORK YANKEES"), 61);
+        assert_eq!(partial_ratio("this is a test", "this is a test!"), 100);
+        assert_eq!(partial_ratio("YANKEES", "NEW YORK YANKEES"), 100);
+        assert_eq!(
+            token_sort_ratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            100
+        );
+        assert_eq!(
+            token_sort_ratio("fuzzy was a bear", "fuzzy fuzzy was a bear"),
+            84
+        );
+        assert_eq!(
+            token_set_ratio("fuzzy was a bear", "fuzzy fuzzy was a bear"),
+            100
+        );
+
+        let mets = "New York Mets vs Atlanta Braves";
+        let braves = "Atlanta Braves vs New York Mets";
+        assert!(ratio(mets, braves) < 50);
+        assert!(partial_ratio(mets, braves) < 50);
+        assert_eq!(token_sort_ratio(mets, braves), 100);
+        assert_eq!(token_set_ratio(mets, braves), 100);
+    }
+
+    #[test]
+    fn fuzzywuzzy_wratio() {
+        let s1 = "Real Madrid vs FC Barcelona";
+        let s2 = "Barcelona vs Real Madrid";
+        assert_eq!(token_set_ratio(s1, s2), 100);
+        // the token set ratio is scaled with 0.95 since the lengths are similar
+        assert_eq!(wratio(s1, s2), 95);
+        assert_eq!(
+            wratio("Real Madrid vs FC Barcelona", "real-madrid vs fc-barcelona"),
+            100
+        );
+
+        assert_eq!(wratio("this is a test", "this is a test!"), 100);
+        assert_eq!(
+            wratio("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            95
+        );
+        // the partial ratio is scaled with 0.9 for a length ratio of at least 1.5
+        assert_eq!(wratio("YANKEES", "New York Yankees"), 90);
+        // and with 0.6 for a length ratio above 8
+        assert_eq!(wratio("ab", "ab cdefghijklmnopq"), 60);
+        assert_eq!(wratio("", "abc"), 0);
+        assert_eq!(wratio("!!", "abc"), 0);
+    }
+
+    #[test]
+    fn fuzzywuzzy_edge_cases() {
+        // fuzzywuzzy returns 0 for empty str
+        assert_eq!(ratio("", ""), 100);
+        assert_eq!(partial_ratio("", ""), 100);
+        assert_eq!(partial_ratio("", "abc"), 0);
+        assert_eq!(ratio("abc", ""), 0);
+        assert_eq!(ratio("abc", "xyz"), 0);
+        assert_eq!(token_set_ratio("abc", "xyz"), 0);
+        assert_eq!(partial_ratio("abc", "abc"), 100);
+    }
+}
@@ -0,0 +1,154 @@
+use core::cmp;
+
+use crate::DistanceMetric;
+
+/// The Hamming distance is the number of positions at which the elements of
+/// both inputs differ.
+///
+/// The Hamming distance is only defined for inputs of equal length, see
+/// [`Hamming::checked_distance`]. To keep [`DistanceMetric`] total, every
+/// element of the longer input beyond the length of the shorter one counts as
+/// a mismatch, so the distance is normalized by the length of the longer
+/// input. For inputs of equal length this is the length of both.
+///
+/// Unlike [`crate::Levenshtein`] nothing is inserted or deleted, so a single
+/// missing element shifts all following ones and can make every position a
+/// mismatch.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Hamming};
+/// assert_eq!(Hamming.str_distance("karolin", "kathrin"), 3);
+/// assert_eq!(Hamming.str_normalized("karolin", "kathrin"), 3. / 7.);
+/// // "bc" is shifted by the missing "a"
+/// assert_eq!(Hamming.str_distance("abc", "bc"), 3);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hamming;
+
+impl Hamming {
+    /// Evaluates the Hamming distance of two inputs of equal length.
+    ///
+    /// Returns `None` if the inputs have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::Hamming;
+    /// assert_eq!(Hamming.checked_distance("1011101".chars(), "1001001".chars()), Some(2));
+    /// assert_eq!(Hamming.checked_distance("abc".chars(), "ab".chars()), None);
+    /// ```
+    pub fn checked_distance<S, T>(&self, a: S, b: T) -> Option<usize>
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+    {
+        let (len_a, len_b, dist) = count_mismatches(a, b);
+        (len_a == len_b).then_some(dist)
+    }
+}
+
+impl DistanceMetric for Hamming {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        count_mismatches(a, b).2
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let (len_a, len_b, dist) = count_mismatches(a, b);
+        let max_len = cmp::max(len_a, len_b);
+        if max_len == 0 {
+            0.
+        } else {
+            dist as f64 / max_len as f64
+        }
+    }
+}
+
+/// The number of elements of a and b and the number of positions at which
+/// they differ, including the surplus elements of the longer input.
+fn count_mismatches<S, T>(a: S, b: T) -> (usize, usize, usize)
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+{
+    let mut a = a.into_iter();
+    let mut b = b.into_iter();
+    let (mut len_a, mut len_b, mut dist) = (0, 0, 0);
+    loop {
+        match (a.next(), b.next()) {
+            (Some(x), Some(y)) => {
+                len_a += 1;
+                len_b += 1;
+                if x != y {
+                    dist += 1;
+                }
+            }
+            (Some(_), None) => {
+                len_a += 1;
+                dist += 1;
+            }
+            (None, Some(_)) => {
+                len_b += 1;
+                dist += 1;
+            }
+            (None, None) => return (len_a, len_b, dist),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hamming() {
+        assert_eq!(Hamming.str_distance("", ""), 0);
+        assert_eq!(Hamming.str_normalized("", ""), 0.);
+        assert_eq!(Hamming.str_distance("karolin", "kerstin"), 3);
+        assert_eq!(Hamming.str_distance("2173896", "2233796"), 3);
+        assert_eq!(Hamming.str_distance("öঙ香", "öঙx"), 1);
+        assert_eq!(Hamming.str_normalized("abcd", "wxyz"), 1.);
+        assert_eq!(Hamming.distance(&[1, 2, 3], &[1, 5, 3]), 1);
+
+        // the surplus elements of the longer input are mismatches
+        assert_eq!(Hamming.str_distance("", "abc"), 3);
+        assert_eq!(Hamming.str_distance("abcdef", "abc"), 3);
+        assert_eq!(Hamming.str_normalized("abcdef", "abc"), 0.5);
+        assert_eq!(Hamming.str_normalized("abc", "abcdef"), 0.5);
+
+        assert_eq!(
+            Hamming.checked_distance("karolin".chars(), "kathrin".chars()),
+            Some(3)
+        );
+        assert_eq!(Hamming.checked_distance("".chars(), "".chars()), Some(0));
+        assert_eq!(
+            Hamming.checked_distance("abc".chars(), "abcd".chars()),
+            None
+        );
+        assert_eq!(
+            Hamming.checked_distance("abcd".chars(), "abc".chars()),
+            None
+        );
+    }
+}
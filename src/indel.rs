@@ -0,0 +1,208 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::utils::eq_by;
+use crate::DistanceMetric;
+
+/// The Indel distance is the edit distance that only allows insertions and
+/// deletions, a substitution requires a deletion and an insertion.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     |s1| + |s2| - 2 * |LCS(s1, s2)|
+/// ```
+///
+/// where `LCS(s1, s2)` denotes the longest common subsequence of both inputs.
+/// The normalized distance divides by `|s1| + |s2|`, which is the
+/// complement of the normalized similarity ratio of e.g. `rapidfuzz`. If both
+/// inputs are empty a value of `0.` is returned.
+///
+/// The metric is also exported as [`crate::Lcs`], and the length of the
+/// longest common subsequence itself is available via [`Indel::lcs_len`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Indel};
+/// // "ittn" is the longest common subsequence
+/// assert_eq!(Indel.str_distance("kitten", "sitting"), 5);
+/// assert_eq!(Indel.str_normalized("kitten", "sitting"), 5. / 13.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Indel;
+
+impl Indel {
+    /// The length of the longest common subsequence of `a` and `b`.
+    ///
+    /// Unlike a common substring, the elements of a common subsequence don't
+    /// need to be adjacent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Lcs};
+    /// // e.g. "BCBA"
+    /// assert_eq!(Lcs.lcs_len("ABCBDAB".chars(), "BDCABA".chars()), 4);
+    /// assert_eq!(Lcs.str_distance("ABCBDAB", "BDCABA"), 7 + 6 - 2 * 4);
+    /// ```
+    pub fn lcs_len<S, T>(&self, a: S, b: T) -> usize
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        lcs_len(&a, &b)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but compares the elements with the
+    /// predicate `eq` instead of `PartialEq`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::Indel;
+    /// let eq = |a: &char, b: &char| a.eq_ignore_ascii_case(b);
+    /// assert_eq!(Indel.distance_by("Kitten".chars(), "kITTEN".chars(), eq), 0);
+    /// ```
+    pub fn distance_by<S, T, F>(&self, a: S, b: T, eq: F) -> usize
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        F: Fn(&<S as IntoIterator>::Item, &<T as IntoIterator>::Item) -> bool,
+    {
+        let a: Vec<_> = eq_by(a, &eq).collect();
+        let b: Vec<_> = eq_by(b, &eq).collect();
+        a.len() + b.len() - 2 * lcs_len(&a, &b)
+    }
+
+    /// The length of the longest common subsequence of the chars of `a` and
+    /// `b`.
+    pub fn str_lcs_len<S, T>(&self, a: S, b: T) -> usize
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.lcs_len(a.as_ref().chars(), b.as_ref().chars())
+    }
+}
+
+impl DistanceMetric for Indel {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        a.len() + b.len() - 2 * lcs_len(&a, &b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        let total_len = a.len() + b.len();
+        if total_len == 0 {
+            0.
+        } else {
+            (total_len - 2 * lcs_len(&a, &b)) as f64 / total_len as f64
+        }
+    }
+}
+
+/// The length of the longest common subsequence of `a` and `b`.
+fn lcs_len<A, B>(a: &[A], b: &[B]) -> usize
+where
+    A: PartialEq<B>,
+{
+    // row[j] is the length of the LCS of the current prefix of a and b[..j]
+    let mut row = vec![0usize; b.len() + 1];
+    for item_a in a {
+        let mut diag = 0;
+        for (j, item_b) in b.iter().enumerate() {
+            let lcs = if item_a == item_b {
+                diag + 1
+            } else {
+                cmp::max(row[j], row[j + 1])
+            };
+            diag = row[j + 1];
+            row[j + 1] = lcs;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Levenshtein;
+
+    #[test]
+    fn indel_distance() {
+        assert_eq!(
+            lcs_len(
+                &['k', 'i', 't', 't', 'e', 'n'],
+                &"sitting".chars().collect::<Vec<_>>()
+            ),
+            4
+        );
+        assert_eq!(Indel.str_distance("kitten", "sitting"), 6 + 7 - 2 * 4);
+        assert_eq!(Indel.str_distance("", ""), 0);
+        assert_eq!(Indel.str_distance("", "abc"), 3);
+        assert_eq!(Indel.str_distance("abc", "abc"), 0);
+        // a substitution is a deletion and an insertion
+        assert_eq!(Indel.str_distance("abc", "axc"), 2);
+        assert_eq!(Indel.str_distance("ab", "ba"), 2);
+        assert_eq!(Indel.distance(&[1, 2, 3, 4], &[2, 4, 5]), 3);
+
+        assert_eq!(Indel.str_normalized("", ""), 0.);
+        assert_eq!(Indel.str_normalized("abc", "xyz"), 1.);
+        assert_eq!(Indel.str_normalized("kitten", "sitting"), 5. / 13.);
+    }
+
+    #[test]
+    fn lcs() {
+        assert_eq!(Indel.str_lcs_len("ABCBDAB", "BDCABA"), 4);
+        assert_eq!(Indel.str_lcs_len("BDCABA", "ABCBDAB"), 4);
+        assert_eq!(Indel.str_distance("ABCBDAB", "BDCABA"), 5);
+        assert_eq!(Indel.str_normalized("ABCBDAB", "BDCABA"), 5. / 13.);
+        assert_eq!(Indel.lcs_len(&[1, 2, 3, 4], &[2, 4, 5]), 2);
+        assert_eq!(Indel.str_lcs_len("", "abc"), 0);
+        assert_eq!(Indel.str_lcs_len("a☃c", "☃c"), 2);
+    }
+
+    #[test]
+    fn indel_bounds() {
+        let words = [
+            "", "a", "kitten", "sitting", "saturday", "sunday", "a☃c", "cba",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                let indel = Indel.str_distance(a, b);
+                let lev = *Levenshtein::default().str_distance(a, b);
+                assert!(lev <= indel && indel <= 2 * lev, "{} {}", a, b);
+                assert_eq!(indel, Indel.str_distance(b, a));
+            }
+        }
+    }
+}
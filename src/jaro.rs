@@ -1,11 +1,259 @@
-use std::cmp;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp;
 
 use crate::modifiers::Winkler;
-use crate::utils::order_by_len_asc;
+use crate::utils::{floor, order_by_len_asc};
 use crate::DistanceMetric;
 
+/// The Jaro distance, based on the number of matching chars and transpositions
+/// between both inputs.
+///
+/// Two elements match, if they are equal and their positions differ by at most
+/// `max(len_a, len_b) / 2 - 1`, each element matches at most once.
 pub struct Jaro;
 
+impl Jaro {
+    /// Evaluates the distance between the slices `s1` and `s2`.
+    ///
+    /// Same as [`DistanceMetric::distance`], but operates on the borrowed
+    /// slices directly instead of collecting the inputs first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro};
+    /// let a = ["Paul", "Adam", "Smith"];
+    /// let b = ["Adam", "Paul", "Smith"];
+    /// assert_eq!(Jaro.distance_slice(&a, &b), Jaro.distance(&a, &b));
+    /// ```
+    pub fn distance_slice<A, B>(&self, s1: &[A], s2: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        jaro_distance(s1, s2, 0.5, 0.5)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], together with the intermediate counts it
+    /// is derived from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro};
+    /// let details = Jaro::details("martha".chars(), "marhta".chars());
+    /// assert_eq!(details.matches, 6);
+    /// assert_eq!(details.transpositions, 1.);
+    /// assert_eq!(details.distance, Jaro.str_distance("martha", "marhta"));
+    /// ```
+    pub fn details<S, T>(a: S, b: T) -> JaroDetails
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+    {
+        let s1: Vec<_> = a.into_iter().collect();
+        let s2: Vec<_> = b.into_iter().collect();
+        jaro_details(&s1, &s2, 0.5, 0.5)
+    }
+
+    /// Create a [`CustomJaro`] distance, that weighs every pair of matching
+    /// elements that are out of order with `transposition_weight` instead of
+    /// `0.5`, and whose matching window is
+    /// `max(len_a, len_b) * window_factor - 1` instead of
+    /// `max(len_a, len_b) / 2 - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `transposition_weight` is not between zero and one, or if
+    /// `window_factor` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro};
+    /// assert_eq!(Jaro::with_params(0.5, 0.5).str_distance("martha", "marhta"), Jaro.str_distance("martha", "marhta"));
+    /// assert_eq!(Jaro::with_params(0., 0.5).str_distance("martha", "marhta"), 0.);
+    /// ```
+    pub fn with_params(transposition_weight: f64, window_factor: f64) -> CustomJaro {
+        assert!((0. ..=1.).contains(&transposition_weight));
+        assert!(window_factor >= 0. && window_factor.is_finite());
+        CustomJaro {
+            transposition_weight,
+            window_factor,
+            trim: false,
+        }
+    }
+}
+
+/// The intermediate counts of a [`Jaro`] distance, see [`Jaro::details`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JaroDetails {
+    /// The number of matching elements.
+    pub matches: usize,
+    /// Half the number of matching elements that are out of order.
+    pub transpositions: f64,
+    /// Elements match if their positions differ by at most this many.
+    pub window: usize,
+    /// The resulting distance.
+    pub distance: f64,
+}
+
+/// The Jaro distance between `s1` and `s2`, where every element that matches
+/// out of order counts as `transposition_weight` transpositions and the
+/// matching window is `max(len_a, len_b) * window_factor - 1`.
+fn jaro_distance<A, B>(s1: &[A], s2: &[B], transposition_weight: f64, window_factor: f64) -> f64
+where
+    A: PartialEq<B>,
+{
+    jaro_details(s1, s2, transposition_weight, window_factor).distance
+}
+
+/// Like [`jaro_distance`], but returns the intermediate counts as well.
+fn jaro_details<A, B>(
+    s1: &[A],
+    s2: &[B],
+    transposition_weight: f64,
+    window_factor: f64,
+) -> JaroDetails
+where
+    A: PartialEq<B>,
+{
+    let s1_len = s1.len();
+    let s2_len = s2.len();
+
+    // the matching window, elements match if their positions differ by at
+    // most `max_dist`. For inputs with at most 3 elements the default window
+    // is 0, so only elements at the same position match. A window beyond the
+    // longer input matches the same elements as the longer input itself.
+    let max_len = cmp::max(s1_len, s2_len);
+    let max_dist = cmp::min(
+        (floor(max_len as f64 * window_factor) as usize).saturating_sub(1),
+        max_len,
+    );
+    let mut details = JaroDetails {
+        matches: 0,
+        transpositions: 0.,
+        window: max_dist,
+        distance: 1.,
+    };
+
+    // edge case where an input is empty
+    if s1_len + s2_len == 0 {
+        details.distance = 0.;
+        return details;
+    } else if cmp::min(s1_len, s2_len) == 0 {
+        return details;
+    }
+
+    // short inputs keep the flags of the matched positions in a bitset on
+    // the stack instead of allocating them
+    let (matches, transpositions) = if cmp::max(s1_len, s2_len) <= u128::BITS as usize {
+        match_elements(s1, s2, max_dist, transposition_weight, 0u128, 0u128)
+    } else {
+        match_elements(
+            s1,
+            s2,
+            max_dist,
+            transposition_weight,
+            vec![false; s1_len],
+            vec![false; s2_len],
+        )
+    };
+    if matches == 0 {
+        return details;
+    }
+    let m = matches as f64;
+    details.matches = matches;
+    details.transpositions = transpositions;
+    details.distance =
+        1. - (m / s1_len as f64 + m / s2_len as f64 + (m - transpositions) / m) / 3.0;
+    details
+}
+
+/// The flags of the positions of an input that are matched.
+trait MatchFlags {
+    fn is_set(&self, idx: usize) -> bool;
+
+    fn set(&mut self, idx: usize);
+}
+
+impl MatchFlags for u128 {
+    #[inline]
+    fn is_set(&self, idx: usize) -> bool {
+        self & (1 << idx) != 0
+    }
+
+    #[inline]
+    fn set(&mut self, idx: usize) {
+        *self |= 1 << idx;
+    }
+}
+
+impl MatchFlags for Vec<bool> {
+    #[inline]
+    fn is_set(&self, idx: usize) -> bool {
+        self[idx]
+    }
+
+    #[inline]
+    fn set(&mut self, idx: usize) {
+        self[idx] = true;
+    }
+}
+
+/// Matches the elements of `s1` with the elements of `s2` within the window
+/// `max_dist` and returns the number of matches and transpositions.
+///
+/// The flags need to hold all positions of the respective input, so a `u128`
+/// is only sufficient for inputs of at most 128 elements.
+fn match_elements<A, B, M>(
+    s1: &[A],
+    s2: &[B],
+    max_dist: usize,
+    transposition_weight: f64,
+    mut s1_matches: M,
+    mut s2_matches: M,
+) -> (usize, f64)
+where
+    A: PartialEq<B>,
+    M: MatchFlags,
+{
+    let s2_len = s2.len();
+    let mut matches = 0usize;
+
+    for (i, c1) in s1.iter().enumerate() {
+        let start = i.saturating_sub(max_dist);
+        let end = cmp::min(i.saturating_add(max_dist).saturating_add(1), s2_len);
+        for (j, c2) in s2.iter().enumerate().take(end).skip(start) {
+            if !s2_matches.is_set(j) && c1 == c2 {
+                s1_matches.set(i);
+                s2_matches.set(j);
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return (0, 0.);
+    }
+    let mut transpositions = 0.0;
+    let mut k = 0;
+    for (i, c1) in s1.iter().enumerate() {
+        if s1_matches.is_set(i) {
+            while !s2_matches.is_set(k) {
+                k += 1;
+            }
+            if *c1 != s2[k] {
+                transpositions += transposition_weight;
+            }
+            k += 1;
+        }
+    }
+    (matches, transpositions)
+}
+
 impl DistanceMetric for Jaro {
     type Dist = f64;
 
@@ -20,54 +268,105 @@ impl DistanceMetric for Jaro {
     {
         let s1: Vec<_> = a.into_iter().collect();
         let s2: Vec<_> = b.into_iter().collect();
+        self.distance_slice(&s1, &s2)
+    }
 
-        let s1_len = s1.len();
-        let s2_len = s2.len();
-
-        // edge cases
-        if s1_len + s2_len == 0 {
-            return 0.0;
-        } else if cmp::min(s1_len, s2_len) == 0 {
-            return 1.0;
-        } else if s1_len + s2_len == 2 {
-            return if s1[0] == s2[0] { 0. } else { 1. };
+    fn str_distance<S, T>(&self, s1: S, s2: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (s1, s2) = order_by_len_asc(s1.as_ref(), s2.as_ref());
+        if s1.is_ascii() && s2.is_ascii() {
+            return self.distance_slice(s1.as_bytes(), s2.as_bytes());
         }
+        self.distance(s1.chars(), s2.chars())
+    }
 
-        let max_dist = cmp::max(s1_len, s2_len) / 2 - 1;
-        let mut s1_matches = vec![false; s1_len];
-        let mut s2_matches = vec![false; s2_len];
-        let mut matches = 0usize;
-
-        for i in 0..s1_len {
-            let start = cmp::max(0, i as isize - max_dist as isize) as usize;
-            let end = cmp::min(i + max_dist + 1, s2_len);
-            for j in start..end {
-                if !s2_matches[j] && s1[i] == s2[j] {
-                    s1_matches[i] = true;
-                    s2_matches[j] = true;
-                    matches += 1;
-                    break;
-                }
-            }
-        }
-        if matches == 0 {
-            return 1.;
-        }
-        let mut transpositions = 0.0;
-        let mut k = 0;
-        for i in 0..s1_len {
-            if s1_matches[i] {
-                while !s2_matches[k] {
-                    k += 1;
-                }
-                if s1[i] != s2[k] {
-                    transpositions += 0.5;
-                }
-                k += 1;
-            }
-        }
-        let m = matches as f64;
-        1. - (m / s1_len as f64 + m / s2_len as f64 + (m - transpositions) / m) / 3.0
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+}
+
+/// A [`Jaro`] distance with a custom transposition weight and matching
+/// window, see [`Jaro::with_params`].
+#[derive(Debug, Clone)]
+pub struct CustomJaro {
+    /// The number of transpositions every element that matches out of order
+    /// counts as.
+    transposition_weight: f64,
+    /// The factor of the length of the longer input that determines the
+    /// matching window.
+    window_factor: f64,
+    /// Whether leading and trailing whitespace of str is ignored.
+    trim: bool,
+}
+
+impl CustomJaro {
+    /// Evaluates the distance between the slices `s1` and `s2`, see
+    /// [`Jaro::distance_slice`].
+    pub fn distance_slice<A, B>(&self, s1: &[A], s2: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        jaro_distance(s1, s2, self.transposition_weight, self.window_factor)
+    }
+
+    /// The number of transpositions every element that matches out of order
+    /// counts as.
+    pub fn transposition_weight(&self) -> f64 {
+        self.transposition_weight
+    }
+
+    /// The factor of the length of the longer input that determines the
+    /// matching window.
+    pub fn window_factor(&self) -> f64 {
+        self.window_factor
+    }
+
+    /// Whether to ignore leading and trailing whitespace of str, e.g. the
+    /// trailing space of messy input. Disabled by default.
+    ///
+    /// This only affects the str methods, e.g. [`DistanceMetric::str_distance`]
+    /// and [`DistanceMetric::str_normalized`], the str are trimmed without
+    /// allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro};
+    /// assert_eq!(Jaro::with_params(0.5, 0.5).trim(true).str_distance("foo", " foo "), 0.);
+    /// assert!(Jaro::with_params(0.5, 0.5).str_distance("foo", "foo ") > 0.);
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+}
+
+impl DistanceMetric for CustomJaro {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let s1: Vec<_> = a.into_iter().collect();
+        let s2: Vec<_> = b.into_iter().collect();
+        self.distance_slice(&s1, &s2)
     }
 
     fn str_distance<S, T>(&self, s1: S, s2: T) -> Self::Dist
@@ -75,10 +374,26 @@ impl DistanceMetric for Jaro {
         S: AsRef<str>,
         T: AsRef<str>,
     {
-        let (s1, s2) = order_by_len_asc(s1.as_ref(), s2.as_ref());
+        let (s1, s2) = (s1.as_ref(), s2.as_ref());
+        let (s1, s2) = if self.trim {
+            order_by_len_asc(s1.trim(), s2.trim())
+        } else {
+            order_by_len_asc(s1, s2)
+        };
+        if s1.is_ascii() && s2.is_ascii() {
+            return self.distance_slice(s1.as_bytes(), s2.as_bytes());
+        }
         self.distance(s1.chars(), s2.chars())
     }
 
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+
     fn normalized<S, T>(&self, a: S, b: T) -> f64
     where
         S: IntoIterator,
@@ -98,6 +413,40 @@ pub type JaroWinkler = Winkler<Jaro>;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::WinklerConfig;
+
+    #[test]
+    fn bitset_matches_vec() {
+        // inputs around the 128 elements that fit into the bitset
+        let input = |len: usize, step: usize| -> Vec<u8> {
+            (0..len).map(|i| b'a' + (i * step % 7) as u8).collect()
+        };
+        for len1 in [0, 1, 5, 64, 120, 127, 128, 129, 140].iter() {
+            for len2 in [0, 3, 64, 126, 128, 129, 150].iter() {
+                let (s1, s2) = (input(*len1, 3), input(*len2, 5));
+                let max_dist = cmp::max(len1, len2) / 2;
+                let expected = match_elements(
+                    &s1,
+                    &s2,
+                    max_dist.saturating_sub(1),
+                    0.5,
+                    vec![false; *len1],
+                    vec![false; *len2],
+                );
+                if cmp::max(len1, len2) <= &128 {
+                    assert_eq!(
+                        match_elements(&s1, &s2, max_dist.saturating_sub(1), 0.5, 0u128, 0u128),
+                        expected
+                    );
+                }
+                let details = Jaro::details(&s1, &s2);
+                if *len1 > 0 && *len2 > 0 {
+                    assert_eq!((details.matches, details.transpositions), expected);
+                }
+                assert_eq!(Jaro.distance_slice(&s1, &s2), details.distance);
+            }
+        }
+    }
 
     #[test]
     fn jaro() {
@@ -120,6 +469,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn jaro_details() {
+        let details = Jaro::details("martha".chars(), "marhta".chars());
+        assert_eq!(
+            details,
+            JaroDetails {
+                matches: 6,
+                transpositions: 1.,
+                window: 2,
+                distance: Jaro.str_distance("martha", "marhta"),
+            }
+        );
+        let details = Jaro::details("dixon".chars(), "dicksonx".chars());
+        assert_eq!((details.matches, details.transpositions), (4, 0.));
+        assert_eq!(details.window, 3);
+        assert_eq!(details.distance, Jaro.str_distance("dixon", "dicksonx"));
+        // the window of 0 only matches the chars at the same position
+        let details = Jaro::details("abc".chars(), "bca".chars());
+        assert_eq!((details.matches, details.window), (0, 0));
+        assert_eq!(details.distance, 1.);
+        assert_eq!(Jaro::details("".chars(), "".chars()).distance, 0.);
+        assert_eq!(Jaro::details("".chars(), "a".chars()).distance, 1.);
+        assert_eq!(Jaro::details("a".chars(), "a".chars()).matches, 1);
+    }
+
     #[test]
     fn winkler() {
         assert_eq!(
@@ -130,4 +504,187 @@ mod tests {
             "0.038889"
         );
     }
+
+    #[test]
+    fn winkler_max_boost() {
+        let winkler = Winkler::with_config(Jaro, WinklerConfig::with_max_boost(0.5, 0.7, 10));
+        let reference = "abcdefghijkl";
+        let jaro = Jaro.str_distance(reference, "abcdefghijkX");
+        assert_eq!(jaro, Jaro.str_distance(reference, "abXdefghijkl"));
+
+        // the prefix of 11 chars is capped at 10, the full `max_boost`
+        let long_prefix = winkler.str_distance(reference, "abcdefghijkX");
+        assert!((long_prefix - 0.5 * jaro).abs() < 1e-12);
+        let short_prefix = winkler.str_distance(reference, "abXdefghijkl");
+        assert!((short_prefix - 0.9 * jaro).abs() < 1e-12);
+
+        assert_eq!(winkler.str_distance(reference, reference), 0.);
+        assert_eq!(winkler.str_distance(reference, "zyx"), 1.);
+
+        let full = Winkler::with_config(Jaro, WinklerConfig::with_max_boost(1., 0.7, 10));
+        assert!(full.str_distance("SKU-123456-A", "SKU-123456-B").abs() < 1e-12);
+        let none = Winkler::with_config(Jaro, WinklerConfig::with_max_boost(0.5, 0.7, 0));
+        assert_eq!(none.str_distance(reference, "abcdefghijkX"), jaro);
+    }
+
+    #[test]
+    fn jaro_params() {
+        let default = Jaro.str_distance("martha", "marhta");
+        assert_eq!(
+            Jaro::with_params(0.5, 0.5).str_distance("martha", "marhta"),
+            default
+        );
+        // 6 matches, of which 2 are out of order
+        assert_eq!(
+            Jaro::with_params(0., 0.5).str_distance("martha", "marhta"),
+            0.
+        );
+        let heavy = Jaro::with_params(1., 0.5).str_distance("martha", "marhta");
+        assert!((heavy - (1. - (2. + 4. / 6.) / 3.)).abs() < 1e-12);
+        assert!(heavy > default);
+
+        // no chars match within the default window of 0
+        assert_eq!(Jaro.str_distance("abc", "bca"), 1.);
+        // all 3 chars match within a window of 2, but out of order
+        let wide = Jaro::with_params(0.5, 1.).str_distance("abc", "bca");
+        assert!((wide - (1. - (1. + 1. + 0.5) / 3.)).abs() < 1e-12);
+        assert_eq!(Jaro::with_params(0., 1.).str_distance("abc", "bca"), 0.);
+
+        for (a, b) in [("kitten", "sitting"), ("dixon", "dicksonx"), ("", "a")].iter() {
+            assert_eq!(
+                Jaro::with_params(0.5, 0.5).str_distance(a, b),
+                Jaro.str_distance(a, b)
+            );
+        }
+
+        // a window beyond the longer input matches like the longer input
+        let full = Jaro::with_params(0.5, 1.).str_distance("abcd", "dcba");
+        assert_eq!(
+            Jaro::with_params(0.5, 1e20).str_distance("abcd", "dcba"),
+            full
+        );
+        assert_eq!(format!("{:.4}", full), "0.1667");
+    }
+
+    #[test]
+    #[should_panic]
+    fn jaro_infinite_window() {
+        Jaro::with_params(0.5, f64::INFINITY);
+    }
+
+    #[test]
+    fn jaro_trim() {
+        let trimmed = Jaro::with_params(0.5, 0.5).trim(true);
+        assert_eq!(trimmed.str_distance("foo", "foo "), 0.);
+        assert_eq!(trimmed.str_distance("\tfoo", "foo\n"), 0.);
+        assert_eq!(
+            trimmed.str_distance(" martha ", "marhta"),
+            Jaro.str_distance("martha", "marhta")
+        );
+        assert_eq!(trimmed.str_distance(" ", ""), 0.);
+        // inner whitespace is kept
+        assert_eq!(
+            trimmed.str_distance("foo bar", "foobar"),
+            Jaro.str_distance("foo bar", "foobar")
+        );
+        // unchanged without trimming
+        assert_eq!(
+            Jaro::with_params(0.5, 0.5).str_distance("foo", "foo "),
+            Jaro.str_distance("foo", "foo ")
+        );
+        assert_eq!(
+            format!("{:.6}", Jaro.str_distance("foo", "foo ")),
+            "0.083333"
+        );
+
+        let winkler = Winkler::with_config(Jaro, WinklerConfig::default().trim(true));
+        assert_eq!(winkler.str_distance("foo", "foo "), 0.);
+        // the common prefix is counted after trimming
+        assert_eq!(
+            winkler.str_distance("  martha", "marhta "),
+            JaroWinkler::default().str_distance("martha", "marhta")
+        );
+        assert!(JaroWinkler::default().str_distance("foo", "foo ") > 0.);
+
+        // the normalized str methods trim the same way
+        for (a, b) in [("foo", " foo "), ("\tmartha", "martha\n"), (" ", "")].iter() {
+            assert_eq!(trimmed.str_normalized(a, b), trimmed.str_distance(a, b));
+            assert_eq!(winkler.str_normalized(a, b), winkler.str_distance(a, b));
+            assert_eq!(trimmed.score_100(a, b), 100);
+            assert_eq!(winkler.score_100(a, b), 100);
+        }
+        assert_eq!(
+            winkler.str_normalized("  martha", "marhta "),
+            winkler.str_distance("  martha", "marhta ")
+        );
+    }
+
+    /// All strings over `alphabet` with 1 to `max_len` chars.
+    fn all_strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+        let mut strings: Vec<String> = vec![String::new()];
+        let mut all = Vec::new();
+        for _ in 0..max_len {
+            strings = strings
+                .iter()
+                .flat_map(|s| {
+                    alphabet.iter().map(move |c| {
+                        let mut s = s.clone();
+                        s.push(*c);
+                        s
+                    })
+                })
+                .collect();
+            all.extend(strings.iter().cloned());
+        }
+        all
+    }
+
+    #[test]
+    fn jaro_short_windows() {
+        let strings = all_strings(&['a', 'b', 'c'], 3);
+        for a in strings.iter() {
+            for b in strings.iter() {
+                let dist = Jaro.str_distance(a, b);
+                let expected = 1. - strsim::jaro(a, b);
+                assert!(
+                    (dist - expected).abs() < 1e-12,
+                    "{} {}: {} {}",
+                    a,
+                    b,
+                    dist,
+                    expected
+                );
+                assert!((dist - Jaro.str_distance(b, a)).abs() < 1e-12);
+            }
+        }
+        // the window of inputs with at most 3 chars is 0
+        assert_eq!(format!("{:.6}", Jaro.str_distance("a", "ab")), "0.166667");
+        assert_eq!(Jaro.str_distance("ab", "ba"), 1.);
+        assert_eq!(
+            format!("{:.6}", Jaro.str_distance("abc", "bac")),
+            "0.444444"
+        );
+        // the window of inputs with 4 or 5 chars is 1, "ab" is transposed
+        assert_eq!(
+            format!("{:.6}", Jaro.str_distance("abcd", "bacd")),
+            "0.083333"
+        );
+    }
+
+    #[test]
+    fn jaro_slice() {
+        let strings = all_strings(&['a', 'b', 'c'], 4);
+        for a in strings.iter().step_by(7) {
+            let a: Vec<_> = a.chars().collect();
+            for b in strings.iter().step_by(5) {
+                let b: Vec<_> = b.chars().collect();
+                assert_eq!(Jaro.distance_slice(&a, &b), Jaro.distance(&a, &b));
+            }
+        }
+        assert_eq!(Jaro.distance_slice::<u8, u8>(&[], &[]), 0.);
+        assert_eq!(
+            Jaro.distance_slice(&["martha"], &["marhta"]),
+            Jaro.distance(&["martha"], &["marhta"])
+        );
+    }
 }
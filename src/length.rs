@@ -0,0 +1,95 @@
+use core::cmp;
+
+use crate::DistanceMetric;
+
+/// The LengthRatio distance compares only the lengths of both inputs.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - min(|s1|, |s2|) / max(|s1|, |s2|)
+/// ```
+///
+/// which is `0.` for inputs of equal length and approaches `1.` as the lengths
+/// diverge. If both inputs are empty a value of `0.` is returned.
+///
+/// Inputs whose lengths differ a lot are unlikely to be similar by any other
+/// metric, so this is a cheap filter to reject candidates, e.g. with
+/// [`DistanceMetric::str_distance_within`], before evaluating a more
+/// expensive metric.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, LengthRatio};
+/// assert_eq!(LengthRatio.str_distance("kitten", "sitting"), 1. - 6. / 7.);
+/// assert_eq!(LengthRatio.str_distance("abc", "xyz"), 0.);
+/// assert_eq!(LengthRatio.str_distance_within("a", "abcdefgh", 0.5), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LengthRatio;
+
+impl DistanceMetric for LengthRatio {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let len_a = a.into_iter().count();
+        let len_b = b.into_iter().count();
+        let max_len = cmp::max(len_a, len_b);
+        if max_len == 0 {
+            0.
+        } else {
+            1. - cmp::min(len_a, len_b) as f64 / max_len as f64
+        }
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_ratio() {
+        assert_eq!(LengthRatio.str_distance("", ""), 0.);
+        assert_eq!(LengthRatio.str_distance("", "a"), 1.);
+        assert_eq!(LengthRatio.str_distance("abc", ""), 1.);
+        assert_eq!(LengthRatio.str_distance("abcd", "wxyz"), 0.);
+        assert_eq!(LengthRatio.str_distance("ab", "abcd"), 0.5);
+        assert_eq!(LengthRatio.str_distance("abcd", "ab"), 0.5);
+        assert_eq!(LengthRatio.str_distance("a", "abcdefghij"), 0.9);
+        // chars, not bytes
+        assert_eq!(LengthRatio.str_distance("a☃", "ab"), 0.);
+        assert_eq!(LengthRatio.distance(&[1, 2, 3], &[1]), 1. - 1. / 3.);
+        assert_eq!(LengthRatio.str_normalized("ab", "abcd"), 0.5);
+    }
+
+    #[test]
+    fn length_ratio_within() {
+        assert_eq!(
+            LengthRatio.str_distance_within("abc", "abcd", 0.3),
+            Some(0.25)
+        );
+        assert_eq!(LengthRatio.str_distance_within("abc", "abcdefg", 0.3), None);
+        assert_eq!(LengthRatio.str_distance_within("", "", 0.), Some(0.));
+    }
+}
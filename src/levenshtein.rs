@@ -1,6 +1,8 @@
-use std::cmp::min;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::min;
 
-use crate::utils::{order_by_len_asc, DelimDistinct};
+use crate::utils::{ceil, eq_by, order_by_len_asc, DelimDistinct, Flipped, Map};
 use crate::{DistanceMetric, DistanceValue};
 
 #[derive(Debug, Clone, Default)]
@@ -18,12 +20,150 @@ impl Levenshtein {
             max_distance: Some(max_distance),
         }
     }
-}
 
-impl DistanceMetric for Levenshtein {
-    type Dist = DistanceValue;
+    /// Creates a [`CostFnLevenshtein`], a Levenshtein distance where
+    /// substituting two unequal chars costs `cost(c1, c2)` instead of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Levenshtein};
+    /// // substituting adjacent keys costs 1, all other substitutions 2
+    /// let typos = Levenshtein::with_cost_fn(|c1, c2| match (c1, c2) {
+    ///     ('a', 's') | ('s', 'a') => 1,
+    ///     _ => 2,
+    /// });
+    /// assert_eq!(typos.str_distance("sat", "aat"), 1);
+    /// assert_eq!(typos.str_distance("sat", "pat"), 2);
+    /// ```
+    pub fn with_cost_fn<F>(cost: F) -> CostFnLevenshtein<F>
+    where
+        F: Fn(char, char) -> usize,
+    {
+        CostFnLevenshtein { cost }
+    }
 
-    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    /// Wraps the exact `dist` in a [`DistanceValue`] according to the
+    /// configured maximum distance.
+    #[inline]
+    pub(crate) fn bounded(&self, dist: usize) -> DistanceValue {
+        match self.max_distance {
+            Some(max_dist) if dist > max_dist => DistanceValue::Exceeded(max_dist),
+            _ => DistanceValue::Exact(dist),
+        }
+    }
+
+    /// Whether the distance to a pattern of `pattern_len` chars is evaluated
+    /// with the bit-parallel [`MyersPattern`].
+    ///
+    /// A single block is always faster than the DP. With a maximum distance,
+    /// longer patterns are evaluated within the band of the maximum distance
+    /// instead, which only depends on the lengths and not on the number of
+    /// blocks.
+    #[inline]
+    pub(crate) fn uses_myers(&self, pattern_len: usize) -> bool {
+        pattern_len <= MyersPattern::BLOCK_LEN || self.max_distance.is_none()
+    }
+
+    /// Returns the edit operations of a minimal alignment, that transform `a`
+    /// into `b`.
+    ///
+    /// The number of operations other than [`EditOp::Keep`] equals the
+    /// Levenshtein distance. The maximum distance is ignored. Unlike the
+    /// distance, which only keeps a single row, this evaluates the full matrix
+    /// and requires `O(len_a * len_b)` memory. If multiple alignments are minimal, keeping or substituting an
+    /// element is preferred over a deletion, and a deletion over an insertion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::Levenshtein;
+    /// # use str_distance::levenshtein::EditOp;
+    /// assert_eq!(
+    ///     Levenshtein::default().edit_ops("ab".chars(), "xb".chars()),
+    ///     vec![
+    ///         EditOp::Substitute { a: 0, b: 0, from: 'a', to: 'x' },
+    ///         EditOp::Keep { a: 1, b: 1, item: 'b' },
+    ///     ]
+    /// );
+    /// ```
+    pub fn edit_ops<S, T>(&self, a: S, b: T) -> Vec<EditOp<S::Item, T::Item>>
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item> + Clone,
+        <T as IntoIterator>::Item: Clone,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        weighted_edit_ops(&a, &b, |_, _| 1)
+    }
+
+    /// Returns the edit operations of a minimal alignment, that transform `a`
+    /// into `b`, like [`Levenshtein::edit_ops`] but in linear memory.
+    ///
+    /// Uses Hirschberg's algorithm, that splits `a` in half, finds the
+    /// position in `b` where a minimal alignment crosses the split from the
+    /// last rows of the matrices of both halves and recurses on both parts.
+    /// Only two rows of `len_b + 1` cells are kept at a time and the recursion
+    /// is `O(log len_a)` deep, so besides the inputs and the script this
+    /// requires `O(len_b + log len_a)` memory, at the cost of evaluating about
+    /// twice as many cells as [`Levenshtein::edit_ops`]. The rows span `b`, so
+    /// the memory is smallest if `b` is the shorter input.
+    ///
+    /// The number of operations other than [`EditOp::Keep`] equals the
+    /// Levenshtein distance, but if multiple alignments are minimal the
+    /// returned script may differ from the one of [`Levenshtein::edit_ops`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::Levenshtein;
+    /// # use str_distance::levenshtein::EditOp;
+    /// assert_eq!(
+    ///     Levenshtein::default().edit_ops_linear("ab".chars(), "xb".chars()),
+    ///     Levenshtein::default().edit_ops("ab".chars(), "xb".chars())
+    /// );
+    /// ```
+    pub fn edit_ops_linear<S, T>(&self, a: S, b: T) -> Vec<EditOp<S::Item, T::Item>>
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item> + Clone,
+        <T as IntoIterator>::Item: Clone,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+
+        let mut ops = Vec::with_capacity(core::cmp::max(a.len(), b.len()));
+        hirschberg(&a, &b, 0, 0, &mut ops);
+        ops
+    }
+}
+
+impl Levenshtein {
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], and returns how much of the matrix was
+    /// evaluated, e.g. to tune the maximum distance.
+    ///
+    /// This always evaluates the matrix, also for str that
+    /// [`DistanceMetric::str_distance`] would evaluate with the bit-parallel
+    /// algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceValue, Levenshtein};
+    /// let (dist, stats) = Levenshtein::default().distance_verbose("kitten".chars(), "sitting".chars());
+    /// assert_eq!(dist, DistanceValue::Exact(3));
+    /// assert_eq!(stats.cells_evaluated, 6 * 7);
+    /// assert!(!stats.short_circuited);
+    ///
+    /// let (dist, stats) = Levenshtein::with_max_distance(1).distance_verbose("kitten".chars(), "sitting".chars());
+    /// assert_eq!(dist, DistanceValue::Exceeded(1));
+    /// assert!(stats.short_circuited);
+    /// ```
+    pub fn distance_verbose<S, T>(&self, a: S, b: T) -> (DistanceValue, Stats)
     where
         S: IntoIterator,
         T: IntoIterator,
@@ -31,23 +171,72 @@ impl DistanceMetric for Levenshtein {
         <T as IntoIterator>::IntoIter: Clone,
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
+    {
+        let mut stats = Stats::default();
+        let dist = self.distance_with_stats(a, b, &mut stats);
+        stats.short_circuited = matches!(dist, DistanceValue::Exceeded(_));
+        (dist, stats)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but compares the elements with the
+    /// predicate `eq` instead of `PartialEq`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceValue, Levenshtein};
+    /// // treat all digits as interchangeable
+    /// let eq = |a: &char, b: &char| a == b || (a.is_ascii_digit() && b.is_ascii_digit());
+    /// let dist = Levenshtein::default().distance_by("SKU-123".chars(), "SKU-987".chars(), eq);
+    /// assert_eq!(dist, DistanceValue::Exact(0));
+    /// ```
+    pub fn distance_by<S, T, F>(&self, a: S, b: T, eq: F) -> DistanceValue
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        F: Fn(&<S as IntoIterator>::Item, &<T as IntoIterator>::Item) -> bool,
+    {
+        self.distance_with_stats(eq_by(a, &eq), eq_by(b, &eq), &mut Stats::default())
+    }
+
+    /// Evaluates the distance via the matrix and records the evaluated cells
+    /// in `stats`.
+    fn distance_with_stats<S, T>(&self, a: S, b: T, stats: &mut Stats) -> DistanceValue
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
     {
         // exclude matching prefix and suffix
         let delim = DelimDistinct::new_skip_take(a.into_iter(), b.into_iter());
 
         if delim.remaining_s1() == 0 {
             // the longer str starts or ends completely with the shorter str
-            return DistanceValue::Exact(delim.remaining_s2());
+            return self.bounded(delim.remaining_s2());
         }
 
         if let Some(max_dist) = self.max_distance {
-            if delim.remaining_s2() - delim.remaining_s1() > max_dist {
+            if delim.remaining_s2().abs_diff(delim.remaining_s1()) > max_dist {
                 return DistanceValue::Exceeded(max_dist);
             }
+            // the distance never exceeds the length of the longer input
+            let max_dist = min(
+                max_dist,
+                core::cmp::max(delim.remaining_s1(), delim.remaining_s2()),
+            );
+            return self.bounded(banded_distance(
+                delim.distinct_s1,
+                delim.distinct_s2,
+                max_dist,
+                stats,
+            ));
         }
 
-        let max_dist = self.max_distance.unwrap_or_else(|| delim.remaining_s2());
-
         let mut cache: Vec<usize> = (1..=delim.remaining_s2()).collect();
 
         let mut result = 0;
@@ -55,32 +244,534 @@ impl DistanceMetric for Levenshtein {
         for (c1_idx, c1) in delim.distinct_s1.enumerate() {
             result = c1_idx + 1;
             let mut dist_c2 = c1_idx;
-            let mut min_dist = if c1_idx == 0 { 0 } else { c1_idx - 1 };
 
             for (c2_idx, c2) in delim.distinct_s2.clone().enumerate() {
                 let cost = if c1 == c2 { 0usize } else { 1usize };
                 let dist_c1 = dist_c2 + cost;
                 dist_c2 = cache[c2_idx];
                 result = min(result + 1, min(dist_c1, dist_c2 + 1));
-                min_dist = min(min_dist, dist_c2);
                 cache[c2_idx] = result;
             }
-            if min_dist > max_dist {
-                return DistanceValue::Exceeded(max_dist);
+            stats.cells_evaluated += cache.len();
+        }
+
+        self.bounded(result)
+    }
+}
+
+/// How much of the matrix of a [`Levenshtein`] distance was evaluated, see
+/// [`Levenshtein::distance_verbose`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Stats {
+    /// The number of cells of the matrix that were evaluated, after stripping
+    /// the common prefix and suffix.
+    pub cells_evaluated: usize,
+    /// Whether the distance exceeded the maximum distance, so the evaluation
+    /// stopped as soon as that was guaranteed. This is also set if that was
+    /// only known after the last cell, or already from the lengths of the
+    /// inputs without their common prefix and suffix.
+    pub short_circuited: bool,
+}
+
+#[cfg(feature = "std")]
+impl Levenshtein {
+    /// Evaluates the distance between the UTF-8 encoded texts of the readers
+    /// `a` and `b` without reading `b` into memory.
+    ///
+    /// The chars of `a` are buffered, since every char of `b` is compared with
+    /// all of them, while `b` is decoded and processed char by char. Pass the
+    /// shorter text as `a` to keep the memory small. With a maximum distance
+    /// the exact distance is evaluated first and bounded afterwards.
+    ///
+    /// Returns an error of kind [`std::io::ErrorKind::InvalidData`] if a text
+    /// is not valid UTF-8, or the first error of a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use str_distance::{DistanceValue, Levenshtein};
+    /// let dist = Levenshtein::default()
+    ///     .distance_reader(Cursor::new("kitten"), Cursor::new("sitting"))
+    ///     .unwrap();
+    /// assert_eq!(dist, DistanceValue::Exact(3));
+    /// ```
+    pub fn distance_reader<R, S>(&self, mut a: R, b: S) -> std::io::Result<DistanceValue>
+    where
+        R: std::io::Read,
+        S: std::io::Read,
+    {
+        let mut text_a = String::new();
+        a.read_to_string(&mut text_a)?;
+        let chars_a: Vec<_> = text_a.chars().collect();
+
+        let mut error = None;
+        let chars_b = Utf8Chars::new(b).map_while(|c| c.map_err(|err| error = Some(err)).ok());
+        let dist = MyersPattern::new(&chars_a).distance_chars(chars_b);
+        match error {
+            Some(err) => Err(err),
+            None => Ok(self.bounded(dist)),
+        }
+    }
+}
+
+/// Decodes the chars of a UTF-8 encoded reader one by one.
+#[cfg(feature = "std")]
+struct Utf8Chars<R: std::io::Read> {
+    bytes: std::io::Bytes<std::io::BufReader<R>>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Utf8Chars<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            bytes: std::io::Read::bytes(std::io::BufReader::new(reader)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Iterator for Utf8Chars<R> {
+    type Item = std::io::Result<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.bytes.next()? {
+            Ok(byte) => byte,
+            Err(err) => return Some(Err(err)),
+        };
+        // the number of bytes of the char, according to its first byte
+        let len = match first.leading_ones() {
+            0 => 1,
+            2 => 2,
+            3 => 3,
+            4 => 4,
+            _ => return Some(Err(invalid_utf8())),
+        };
+        let mut buf = [first, 0, 0, 0];
+        for byte in buf[1..len].iter_mut() {
+            *byte = match self.bytes.next() {
+                Some(Ok(byte)) => byte,
+                Some(Err(err)) => return Some(Err(err)),
+                None => return Some(Err(invalid_utf8())),
+            };
+        }
+        Some(
+            core::str::from_utf8(&buf[..len])
+                .map(|s| s.chars().next().expect("one char"))
+                .map_err(|_| invalid_utf8()),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+fn invalid_utf8() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// A single operation of the edit script that transforms the first input
+/// (`a`) into the second input (`b`), see [`Levenshtein::edit_ops`].
+///
+/// Indices refer to the position of an element in `a` or `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditOp<A, B = A> {
+    /// The element `a` of the first input is equal to and kept as element `b`
+    /// of the second input.
+    Keep { a: usize, b: usize, item: A },
+    /// The element `a` of the first input is substituted with the element `b`
+    /// of the second input.
+    Substitute { a: usize, b: usize, from: A, to: B },
+    /// The element `b` of the second input is inserted.
+    Insert { b: usize, item: B },
+    /// The element `a` of the first input is deleted.
+    Delete { a: usize, item: A },
+}
+
+/// A single operation of the edit script that transforms the first input
+/// (`a`) into the second input (`b`), see [`DamerauLevenshtein::edit_ops`].
+///
+/// Indices refer to the position of an element in `a` or `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamerauEditOp<A, B = A> {
+    /// The element `a` of the first input is equal to and kept as element `b`
+    /// of the second input.
+    Keep { a: usize, b: usize, item: A },
+    /// The element `a` of the first input is substituted with the element `b`
+    /// of the second input.
+    Substitute { a: usize, b: usize, from: A, to: B },
+    /// The element `b` of the second input is inserted.
+    Insert { b: usize, item: B },
+    /// The element `a` of the first input is deleted.
+    Delete { a: usize, item: A },
+    /// The adjacent elements `a` and `a + 1` of the first input, `first` and
+    /// `second`, are swapped and become the elements `b` and `b + 1` of the
+    /// second input.
+    Transpose {
+        a: usize,
+        b: usize,
+        first: A,
+        second: A,
+    },
+}
+
+/// The edit operations of a minimal optimal string alignment of `a` and `b`,
+/// see [`DamerauLevenshtein::edit_ops`], where transposing two adjacent
+/// elements costs `transposition_cost` and all other edits cost 1.
+fn damerau_edit_ops<A, B>(a: &[A], b: &[B], transposition_cost: usize) -> Vec<DamerauEditOp<A, B>>
+where
+    A: PartialEq<B> + Clone,
+    B: Clone,
+{
+    // whether a[i - 2..i] is transposed to b[j - 2..j]
+    let transposed =
+        |i: usize, j: usize| i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1];
+
+    // dist[i][j] is the distance between a[..i] and b[..j]
+    let mut dist = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dist[i][j] = min(
+                dist[i - 1][j - 1] + cost,
+                min(dist[i - 1][j], dist[i][j - 1]) + 1,
+            );
+            if transposed(i, j) {
+                dist[i][j] = min(dist[i][j], dist[i - 2][j - 2] + transposition_cost);
+            }
+        }
+    }
+
+    // trace the alignment back from the end
+    let mut ops = Vec::with_capacity(core::cmp::max(a.len(), b.len()));
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let keep = a[i - 1] == b[j - 1];
+            if dist[i][j] == dist[i - 1][j - 1] + if keep { 0 } else { 1 } {
+                i -= 1;
+                j -= 1;
+                ops.push(if keep {
+                    DamerauEditOp::Keep {
+                        a: i,
+                        b: j,
+                        item: a[i].clone(),
+                    }
+                } else {
+                    DamerauEditOp::Substitute {
+                        a: i,
+                        b: j,
+                        from: a[i].clone(),
+                        to: b[j].clone(),
+                    }
+                });
+                continue;
+            }
+            if transposed(i, j) && dist[i][j] == dist[i - 2][j - 2] + transposition_cost {
+                i -= 2;
+                j -= 2;
+                ops.push(DamerauEditOp::Transpose {
+                    a: i,
+                    b: j,
+                    first: a[i].clone(),
+                    second: a[i + 1].clone(),
+                });
+                continue;
+            }
+        }
+        if i > 0 && dist[i][j] == dist[i - 1][j] + 1 {
+            i -= 1;
+            ops.push(DamerauEditOp::Delete {
+                a: i,
+                item: a[i].clone(),
+            });
+        } else {
+            j -= 1;
+            ops.push(DamerauEditOp::Insert {
+                b: j,
+                item: b[j].clone(),
+            });
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// The edit operations of a minimal alignment of `a` and `b`, see
+/// [`Levenshtein::edit_ops`], where substituting unequal elements costs
+/// `substitution_cost` and insertions and deletions cost 1.
+fn weighted_edit_ops<A, B, F>(a: &[A], b: &[B], substitution_cost: F) -> Vec<EditOp<A, B>>
+where
+    A: PartialEq<B> + Clone,
+    B: Clone,
+    F: Fn(&A, &B) -> usize,
+{
+    let cost = |x: &A, y: &B| if x == y { 0 } else { substitution_cost(x, y) };
+
+    // dist[i][j] is the distance between a[..i] and b[..j]
+    let mut dist = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            dist[i][j] = min(
+                dist[i - 1][j - 1] + cost(&a[i - 1], &b[j - 1]),
+                min(dist[i - 1][j], dist[i][j - 1]) + 1,
+            );
+        }
+    }
+
+    // trace the alignment back from the end
+    let mut ops = Vec::with_capacity(core::cmp::max(a.len(), b.len()));
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let keep = a[i - 1] == b[j - 1];
+            if dist[i][j] == dist[i - 1][j - 1] + cost(&a[i - 1], &b[j - 1]) {
+                i -= 1;
+                j -= 1;
+                ops.push(if keep {
+                    EditOp::Keep {
+                        a: i,
+                        b: j,
+                        item: a[i].clone(),
+                    }
+                } else {
+                    EditOp::Substitute {
+                        a: i,
+                        b: j,
+                        from: a[i].clone(),
+                        to: b[j].clone(),
+                    }
+                });
+                continue;
+            }
+        }
+        if i > 0 && dist[i][j] == dist[i - 1][j] + 1 {
+            i -= 1;
+            ops.push(EditOp::Delete {
+                a: i,
+                item: a[i].clone(),
+            });
+        } else {
+            j -= 1;
+            ops.push(EditOp::Insert {
+                b: j,
+                item: b[j].clone(),
+            });
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+/// The Levenshtein distance between `a` and `b`, or `max_dist + 1` if it
+/// exceeds `max_dist`.
+///
+/// The distance of the cell `(i, j)` of the matrix is at least `|i - j|`, so
+/// only the diagonal band of width `2 * max_dist + 1` around the main diagonal
+/// is evaluated (Ukkonen), which takes `O(max_dist * len_a)` time. All
+/// distances are capped at `max_dist + 1`, which also stands in for the cells
+/// outside of the band.
+///
+/// The evaluated cells are recorded in `stats`.
+fn banded_distance<S, T>(a: S, b: T, max_dist: usize, stats: &mut Stats) -> usize
+where
+    S: Iterator,
+    T: Iterator,
+    <S as Iterator>::Item: PartialEq<<T as Iterator>::Item>,
+{
+    let b: Vec<_> = b.collect();
+    let exceeded = max_dist + 1;
+    if b.is_empty() {
+        return min(a.count(), exceeded);
+    }
+
+    // the previous row of the matrix, only the cells within the band are valid
+    let mut row: Vec<usize> = (0..=b.len()).map(|j| min(j, exceeded)).collect();
+
+    for (i, item_a) in (1usize..).zip(a) {
+        // the band of row i
+        let lo = core::cmp::max(1, i.saturating_sub(max_dist));
+        let hi = min(b.len(), i + max_dist);
+        if lo > hi {
+            // the band lies to the right of the last column
+            return exceeded;
+        }
+
+        let mut diag = row[lo - 1];
+        let mut left = if lo == 1 {
+            row[0] = min(i, exceeded);
+            row[0]
+        } else {
+            exceeded
+        };
+        let mut min_dist = left;
+
+        for (j, item_b) in (lo..=hi).zip(&b[lo - 1..hi]) {
+            let cost = if item_a == *item_b { 0 } else { 1 };
+            let dist = min(exceeded, min(diag + cost, min(row[j], left) + 1));
+            diag = row[j];
+            row[j] = dist;
+            left = dist;
+            min_dist = min(min_dist, dist);
+        }
+        stats.cells_evaluated += hi + 1 - lo;
+        if min_dist > max_dist {
+            // all alignments pass through this row
+            return exceeded;
+        }
+    }
+    row[b.len()]
+}
+
+/// Appends the edit operations of a minimal alignment of `a` and `b` to `ops`,
+/// where `a` and `b` start at `offset_a` and `offset_b` of the inputs.
+fn hirschberg<A, B>(a: &[A], b: &[B], offset_a: usize, offset_b: usize, ops: &mut Vec<EditOp<A, B>>)
+where
+    A: PartialEq<B> + Clone,
+    B: Clone,
+{
+    let insert_all = |b: &[B], offset_b: usize, ops: &mut Vec<EditOp<A, B>>| {
+        ops.extend(b.iter().enumerate().map(|(j, item)| EditOp::Insert {
+            b: offset_b + j,
+            item: item.clone(),
+        }))
+    };
+
+    if a.is_empty() {
+        insert_all(b, offset_b, ops);
+    } else if b.is_empty() {
+        ops.extend(a.iter().enumerate().map(|(i, item)| EditOp::Delete {
+            a: offset_a + i,
+            item: item.clone(),
+        }));
+    } else if a.len() == 1 {
+        // keep the element at its first occurrence in b, otherwise substitute
+        // the first element of b, all other elements of b are inserted
+        let j = b.iter().position(|item| a[0] == *item).unwrap_or(0);
+        insert_all(&b[..j], offset_b, ops);
+        ops.push(if a[0] == b[j] {
+            EditOp::Keep {
+                a: offset_a,
+                b: offset_b + j,
+                item: a[0].clone(),
+            }
+        } else {
+            EditOp::Substitute {
+                a: offset_a,
+                b: offset_b + j,
+                from: a[0].clone(),
+                to: b[j].clone(),
             }
+        });
+        insert_all(&b[j + 1..], offset_b + j + 1, ops);
+    } else {
+        let mid = a.len() / 2;
+        // the distances of the first half of a to all prefixes of b and of the
+        // second half of a to all suffixes of b, both rows are dropped before
+        // recursing so at most two rows are alive at any time
+        let split = {
+            let prefix = last_row(a[..mid].iter(), b.iter());
+            let suffix = last_row(a[mid..].iter().rev(), b.iter().rev());
+            (0..=b.len())
+                .min_by_key(|j| prefix[*j] + suffix[b.len() - j])
+                .unwrap_or_default()
+        };
+
+        hirschberg(&a[..mid], &b[..split], offset_a, offset_b, ops);
+        hirschberg(
+            &a[mid..],
+            &b[split..],
+            offset_a + mid,
+            offset_b + split,
+            ops,
+        );
+    }
+}
+
+/// The last row of the Levenshtein matrix of `a` and `b`, the distances
+/// between `a` and all prefixes of `b`.
+fn last_row<'a, 'b, A, B>(
+    a: impl Iterator<Item = &'a A>,
+    b: impl ExactSizeIterator<Item = &'b B> + Clone,
+) -> Vec<usize>
+where
+    A: PartialEq<B> + 'a,
+    B: 'b,
+{
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, item_a) in a.enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, item_b) in b.clone().enumerate() {
+            let cost = if item_a == item_b { 0 } else { 1 };
+            let dist = min(diag + cost, min(row[j], row[j + 1]) + 1);
+            diag = row[j + 1];
+            row[j + 1] = dist;
         }
+    }
+    row
+}
+
+impl DistanceMetric for Levenshtein {
+    type Dist = DistanceValue;
 
-        DistanceValue::Exact(result)
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance_with_stats(a, b, &mut Stats::default())
     }
 
+    /// Uses the bit-parallel algorithm of Myers, that processes the shorter
+    /// str in blocks of 64 chars. With a maximum distance, only a shorter str
+    /// of at most 64 chars is processed this way.
+    ///
+    /// If both strs are ASCII, their bytes are compared directly without
+    /// decoding and collecting the chars.
     fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
     where
         S: AsRef<str>,
         T: AsRef<str>,
     {
-        // make sure we use the shortest str for the outer loop
-        let (a, b) = order_by_len_asc(a.as_ref(), b.as_ref());
-        self.distance(a.chars(), b.chars())
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            let (a, b) = (a.as_bytes(), b.as_bytes());
+            let (a, b) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+            return if self.uses_myers(a.len()) {
+                self.bounded(AsciiMyersPattern::new(a).distance(b))
+            } else {
+                self.distance(a, b)
+            };
+        }
+
+        let a: Vec<_> = a.chars().collect();
+        let b: Vec<_> = b.chars().collect();
+        // make sure we use the shortest str as pattern or for the outer loop
+        let (a, b) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+
+        if self.uses_myers(a.len()) {
+            self.bounded(MyersPattern::new(&a).distance(&b))
+        } else {
+            self.distance(a, b)
+        }
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -96,12 +787,42 @@ impl DistanceMetric for Levenshtein {
     }
 
     fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        normalize(
+            self.str_distance(a, b),
+            a.chars().count(),
+            b.chars().count(),
+        )
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let dist = self.str_distance(a, b);
+        let normalized = normalize(dist.clone(), a.chars().count(), b.chars().count());
+        (dist, normalized)
+    }
+
+    fn str_distance_within<S, T>(&self, a: S, b: T, threshold: f64) -> Option<f64>
     where
         S: AsRef<str>,
         T: AsRef<str>,
     {
         let (a, b) = order_by_len_asc(a.as_ref(), b.as_ref());
-        normalized_levenshtein(self, a.chars(), b.chars())
+        normalized_levenshtein_within(
+            self.max_distance,
+            a,
+            b,
+            threshold,
+            Levenshtein::with_max_distance,
+        )
     }
 }
 
@@ -122,37 +843,127 @@ impl DistanceMetric for Levenshtein {
 /// of 2 by a complete application of Damerau-Levenshtein, but a distance of 3
 /// by this method that uses the optimal string alignment algorithm. See
 /// wikipedia article for more detail on this distinction.
-#[derive(Debug, Clone, Default)]
+///
+/// A transposition of two adjacent elements costs 1 by default, like any other
+/// edit. The cost is configurable via
+/// [`DamerauLevenshtein::with_transposition_cost`] or
+/// [`DamerauLevenshtein::transposition_cost`], a cost of 0 makes
+/// transpositions free and a cost of 2 or more effectively disables them,
+/// since a transposition can always be replaced by two substitutions.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DamerauLevenshtein, DistanceMetric};
+/// assert_eq!(*DamerauLevenshtein::default().str_distance("ab", "ba"), 1);
+/// assert_eq!(*DamerauLevenshtein::with_transposition_cost(0).str_distance("ab", "ba"), 0);
+/// assert_eq!(*DamerauLevenshtein::with_transposition_cost(2).str_distance("ab", "ba"), 2);
+/// ```
+#[derive(Debug, Clone)]
 pub struct DamerauLevenshtein {
     /// The maximum edit distance of interest.
     ///
     /// Used to short circuit the exact evaluation of the distance, if the exact
     /// value is guaranteed to exceed the configured maximum.
     max_distance: Option<usize>,
+    /// The cost of transposing two adjacent elements.
+    transposition_cost: usize,
+}
+
+impl Default for DamerauLevenshtein {
+    fn default() -> Self {
+        Self {
+            max_distance: None,
+            transposition_cost: 1,
+        }
+    }
 }
 
 impl DamerauLevenshtein {
+    /// Creates a new [`DamerauLevenshtein`] that stops evaluating the distance
+    /// once it exceeds `max_distance`, with the default transposition cost of
+    /// 1.
+    ///
+    /// The transposition cost can be changed with
+    /// [`DamerauLevenshtein::transposition_cost`].
     pub fn with_max_distance(max_distance: usize) -> Self {
         Self {
             max_distance: Some(max_distance),
+            ..Default::default()
         }
     }
-}
 
-impl DistanceMetric for DamerauLevenshtein {
-    type Dist = DistanceValue;
+    /// Creates a new [`DamerauLevenshtein`] where transposing two adjacent
+    /// elements costs `transposition_cost`, without a maximum distance.
+    ///
+    /// Use [`DamerauLevenshtein::transposition_cost`] to combine the cost with
+    /// [`DamerauLevenshtein::with_max_distance`].
+    pub fn with_transposition_cost(transposition_cost: usize) -> Self {
+        Self::default().transposition_cost(transposition_cost)
+    }
 
-    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    /// Sets the cost of transposing two adjacent elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DamerauLevenshtein, DistanceMetric};
+    /// let metric = DamerauLevenshtein::with_max_distance(1).transposition_cost(0);
+    /// assert_eq!(*metric.str_distance("abcd", "badc"), 0);
+    /// assert!(metric.str_distance("abcd", "xyz").is_exceeded());
+    /// ```
+    pub fn transposition_cost(mut self, transposition_cost: usize) -> Self {
+        self.transposition_cost = transposition_cost;
+        self
+    }
+
+    /// Returns the edit operations of a minimal optimal string alignment,
+    /// that transform `a` into `b`.
+    ///
+    /// The operations other than [`DamerauEditOp::Keep`] add up to the
+    /// distance, where a [`DamerauEditOp::Transpose`] costs the
+    /// transposition cost and every other edit 1. The maximum distance is
+    /// ignored. This evaluates the full matrix and requires
+    /// `O(len_a * len_b)` memory. If multiple alignments are minimal, keeping
+    /// or substituting an element is preferred over a transposition, a
+    /// transposition over a deletion, and a deletion over an insertion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::DamerauLevenshtein;
+    /// # use str_distance::levenshtein::DamerauEditOp;
+    /// assert_eq!(
+    ///     DamerauLevenshtein::default().edit_ops("abc".chars(), "bac".chars()),
+    ///     vec![
+    ///         DamerauEditOp::Transpose { a: 0, b: 0, first: 'a', second: 'b' },
+    ///         DamerauEditOp::Keep { a: 2, b: 2, item: 'c' },
+    ///     ]
+    /// );
+    /// ```
+    pub fn edit_ops<S, T>(&self, a: S, b: T) -> Vec<DamerauEditOp<S::Item, T::Item>>
     where
         S: IntoIterator,
         T: IntoIterator,
-        <S as IntoIterator>::IntoIter: Clone,
-        <T as IntoIterator>::IntoIter: Clone,
-        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
-        <T as IntoIterator>::Item: PartialEq,
+        <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item> + Clone,
+        <T as IntoIterator>::Item: Clone,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        damerau_edit_ops(&a, &b, self.transposition_cost)
+    }
+
+    /// The distance between `a` and `b`, where `a` has at most as many
+    /// elements as `b`.
+    fn ordered_distance<S, T>(&self, a: S, b: T) -> DistanceValue
+    where
+        S: Iterator + Clone,
+        T: Iterator + Clone,
+        <S as Iterator>::Item: PartialEq + PartialEq<<T as Iterator>::Item>,
+        <T as Iterator>::Item: PartialEq,
     {
         // exclude matching prefix prefix and suffix
-        let delim = DelimDistinct::new_skip_take(a.into_iter(), b.into_iter());
+        let delim = DelimDistinct::new_skip_take(a, b);
 
         if delim.remaining_s1() == 0 {
             // the longer str starts or ends completely with the shorter str
@@ -171,7 +982,7 @@ impl DistanceMetric for DamerauLevenshtein {
 
         let mut v0 = Vec::with_capacity(delim.remaining_s2());
         v0.extend(1..=max_dist);
-        for _i in max_dist + 1..delim.remaining_s2() {
+        for _i in max_dist..delim.remaining_s2() {
             v0.push(max_dist + 1);
         }
 
@@ -185,8 +996,10 @@ impl DistanceMetric for DamerauLevenshtein {
         let mut s2_start = 0;
         let mut s2_end = max_dist;
         let mut current = 0;
-        // whether a check for exceeding a max dist is necessary
-        let have_max = max_dist < remaining_s2;
+        // whether a check for exceeding a max dist is necessary, free
+        // transpositions can decrease the distance along the diagonal, so the
+        // intermediate distance is no lower bound of the final distance
+        let have_max = max_dist < remaining_s2 && self.transposition_cost > 0;
 
         for (s1_idx, c1) in delim.distinct_s1.enumerate() {
             let left_c1 = s1_tmp;
@@ -228,7 +1041,7 @@ impl DistanceMetric for DamerauLevenshtein {
                     current += 1;
                     if (s1_idx != 0) && (s2_idx != 0) && (s1_tmp == left_c2) && (left_c1 == s2_tmp)
                     {
-                        this_trans_cost += 1;
+                        this_trans_cost += self.transposition_cost;
                         if this_trans_cost < current {
                             // transposition
                             current = this_trans_cost
@@ -249,6 +1062,28 @@ impl DistanceMetric for DamerauLevenshtein {
             DistanceValue::Exceeded(max_dist)
         }
     }
+}
+
+impl DistanceMetric for DamerauLevenshtein {
+    type Dist = DistanceValue;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let (a, b) = (a.into_iter(), b.into_iter());
+        // the shorter input is required first
+        if a.clone().count() > b.clone().count() {
+            self.ordered_distance(b.map(Flipped), a.map(Flipped))
+        } else {
+            self.ordered_distance(a, b)
+        }
+    }
 
     fn str_distance<S, T>(&self, s1: S, s2: T) -> Self::Dist
     where
@@ -280,12 +1115,494 @@ impl DistanceMetric for DamerauLevenshtein {
         let (a, b) = order_by_len_asc(a.as_ref(), b.as_ref());
         normalized_levenshtein(self, a.chars(), b.chars())
     }
-}
 
-fn normalized_levenshtein<D, S, T>(dist: &D, a: S, b: T) -> f64
-where
-    D: DistanceMetric<Dist = DistanceValue>,
-    S: IntoIterator,
+    fn str_distance_within<S, T>(&self, a: S, b: T, threshold: f64) -> Option<f64>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = order_by_len_asc(a.as_ref(), b.as_ref());
+        normalized_levenshtein_within(self.max_distance, a, b, threshold, |max_distance| {
+            DamerauLevenshtein {
+                max_distance: Some(max_distance),
+                transposition_cost: self.transposition_cost,
+            }
+        })
+    }
+}
+
+/// A [`Levenshtein`] variant that charges a configurable
+/// `case_mismatch_cost` instead of a full substitution for two characters that
+/// only differ by case, e.g. `'A'` and `'a'`.
+///
+/// A `case_mismatch_cost` of `0.` is equivalent to a case insensitive
+/// Levenshtein distance, whereas a cost of `1.` is equivalent to the case
+/// sensitive [`Levenshtein`] distance.
+///
+/// Case is only considered when comparing `str`s. For other data types all
+/// unequal elements are charged a full substitution.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{CaseAwareLevenshtein, DistanceMetric};
+/// assert_eq!(CaseAwareLevenshtein::new(0.25).str_distance("Hello", "hello"), 0.25);
+/// assert_eq!(CaseAwareLevenshtein::new(0.25).str_distance("Hello", "jello"), 1.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CaseAwareLevenshtein {
+    /// The cost of substituting a character with a character that only
+    /// differs by case.
+    case_mismatch_cost: f64,
+}
+
+impl CaseAwareLevenshtein {
+    /// # Panics
+    ///
+    /// Panics if the `case_mismatch_cost` is not within `[0, 1]`.
+    pub fn new(case_mismatch_cost: f64) -> Self {
+        assert!((0. ..=1.).contains(&case_mismatch_cost));
+        Self { case_mismatch_cost }
+    }
+
+    /// The cost of substituting `c1` with `c2`.
+    fn substitution_cost(&self, c1: char, c2: char) -> f64 {
+        if c1 == c2 {
+            0.
+        } else if c1.to_lowercase().eq(c2.to_lowercase()) {
+            self.case_mismatch_cost
+        } else {
+            1.
+        }
+    }
+}
+
+impl DistanceMetric for CaseAwareLevenshtein {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        weighted_levenshtein(&a, &b, 1., |c1, c2| if c1 == c2 { 0. } else { 1. })
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().chars().collect();
+        let b: Vec<_> = b.as_ref().chars().collect();
+        weighted_levenshtein(&a, &b, 1., |c1, c2| self.substitution_cost(*c1, *c2))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let max_len = core::cmp::max(a.clone().count(), b.clone().count());
+        if max_len == 0 {
+            0.
+        } else {
+            self.distance(a, b) / max_len as f64
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let max_len = core::cmp::max(a.chars().count(), b.chars().count());
+        if max_len == 0 {
+            0.
+        } else {
+            self.str_distance(a, b) / max_len as f64
+        }
+    }
+}
+
+/// A [`Levenshtein`] variant where the cost of substituting two unequal chars
+/// is determined by a function, e.g. to make substitutions of adjacent keys
+/// of a keyboard cheaper. Created by [`Levenshtein::with_cost_fn`].
+///
+/// Keeping a char is free and insertions and deletions cost 1. A substitution
+/// that costs more than 2 is never part of a minimal alignment, since the char
+/// can be deleted and the other inserted instead.
+///
+/// The normalized distance divides by the number of chars of the longer str
+/// like [`Levenshtein`], but never exceeds `1.`.
+///
+/// The cost function is only considered when comparing `str`s. For other
+/// data types all unequal elements are charged a substitution cost of 1.
+#[derive(Clone)]
+pub struct CostFnLevenshtein<F> {
+    /// The cost of substituting the first with the second char.
+    cost: F,
+}
+
+impl<F> CostFnLevenshtein<F>
+where
+    F: Fn(char, char) -> usize,
+{
+    /// The cost of substituting `c1` with `c2`.
+    fn substitution_cost(&self, c1: char, c2: char) -> usize {
+        if c1 == c2 {
+            0
+        } else {
+            (self.cost)(c1, c2)
+        }
+    }
+
+    /// Returns the edit operations of a minimal alignment, that transform `a`
+    /// into `b`, like [`Levenshtein::edit_ops`] but with the substitution
+    /// costs of the cost function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::Levenshtein;
+    /// # use str_distance::levenshtein::EditOp;
+    /// // other substitutions are more expensive than a deletion and an insertion
+    /// let typos = Levenshtein::with_cost_fn(|_, _| 3);
+    /// assert_eq!(
+    ///     typos.edit_ops("a".chars(), "b".chars()),
+    ///     vec![EditOp::Insert { b: 0, item: 'b' }, EditOp::Delete { a: 0, item: 'a' }]
+    /// );
+    /// ```
+    pub fn edit_ops<S, T>(&self, a: S, b: T) -> Vec<EditOp<char>>
+    where
+        S: IntoIterator<Item = char>,
+        T: IntoIterator<Item = char>,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        weighted_edit_ops(&a, &b, |c1, c2| self.substitution_cost(*c1, *c2))
+    }
+}
+
+impl<F> DistanceMetric for CostFnLevenshtein<F>
+where
+    F: Fn(char, char) -> usize,
+{
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        *Levenshtein::default().distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().chars().collect();
+        let b: Vec<_> = b.as_ref().chars().collect();
+        // the costs are integers, so the distance is exact
+        weighted_levenshtein(&a, &b, 1., |c1, c2| self.substitution_cost(*c1, *c2) as f64) as usize
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        Levenshtein::default().normalized(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let max_len = core::cmp::max(a.chars().count(), b.chars().count());
+        if max_len == 0 {
+            0.
+        } else {
+            (self.str_distance(a, b) as f64 / max_len as f64).min(1.)
+        }
+    }
+}
+
+/// The default log probability of an insertion or deletion: `ln(0.01)`.
+const DEFAULT_INDEL_LOG_PROB: f64 = -4.605_170_185_988_091;
+
+/// The default log probability of a substitution that is not part of the
+/// substitution matrix: `ln(0.0001)`.
+const DEFAULT_UNSEEN_LOG_PROB: f64 = -9.210_340_371_976_182;
+
+/// A weighted Levenshtein distance that evaluates the minimum cost
+/// (maximum likelihood) alignment of two strings, based on empirical
+/// probabilities that a character `x` was written as character `y`.
+///
+/// The cost of every edit operation is its negative log probability
+/// `-ln(p)`, so that the distance corresponds to the negative log likelihood
+/// of the most probable alignment.
+///
+/// Substituting a character with itself is always free. Substitutions that
+/// are not part of the matrix are considered unseen and cost `-ln(0.0001)` by
+/// default, which is the same as the default cost of deleting the character
+/// and inserting the other (`2 * -ln(0.01)`).
+///
+/// The matrix is directional: `(x, y)` is the log probability that `x` of the
+/// first str was written as `y` in the second str.
+///
+/// The substitution matrix is only considered when comparing `str`s, for other
+/// data types all substitutions are considered unseen.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, ProbabilisticLevenshtein};
+/// let dist = ProbabilisticLevenshtein::new(vec![(('u', 'v'), 0.5f64.ln())]);
+/// assert_eq!(dist.str_distance("uale", "vale"), -0.5f64.ln());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProbabilisticLevenshtein {
+    /// The cost `-ln(p)` of substituting the first with the second char.
+    substitution_costs: Map<(char, char), f64>,
+    /// The cost `-ln(p)` of a single insertion or deletion.
+    indel_cost: f64,
+    /// The cost `-ln(p)` of a substitution not part of `substitution_costs`.
+    unseen_cost: f64,
+}
+
+impl ProbabilisticLevenshtein {
+    /// Creates a new metric from the log probabilities `ln(p)` that the first
+    /// char was written as the second char, using the default log
+    /// probabilities for insertions/deletions and unseen substitutions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a log probability is greater than 0.
+    pub fn new<I>(log_probs: I) -> Self
+    where
+        I: IntoIterator<Item = ((char, char), f64)>,
+    {
+        Self::with_log_probs(log_probs, DEFAULT_INDEL_LOG_PROB, DEFAULT_UNSEEN_LOG_PROB)
+    }
+
+    /// Creates a new metric from the log probabilities `ln(p)` of the
+    /// substitution matrix, an insertion or deletion and an unseen
+    /// substitution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a log probability is greater than 0.
+    pub fn with_log_probs<I>(log_probs: I, indel_log_prob: f64, unseen_log_prob: f64) -> Self
+    where
+        I: IntoIterator<Item = ((char, char), f64)>,
+    {
+        assert!(indel_log_prob <= 0.);
+        assert!(unseen_log_prob <= 0.);
+        let substitution_costs = log_probs
+            .into_iter()
+            .map(|(pair, log_prob)| {
+                assert!(log_prob <= 0.);
+                (pair, -log_prob)
+            })
+            .collect();
+        Self {
+            substitution_costs,
+            indel_cost: -indel_log_prob,
+            unseen_cost: -unseen_log_prob,
+        }
+    }
+
+    /// The cost of substituting `c1` with `c2`.
+    fn substitution_cost(&self, c1: char, c2: char) -> f64 {
+        if c1 == c2 {
+            0.
+        } else {
+            *self
+                .substitution_costs
+                .get(&(c1, c2))
+                .unwrap_or(&self.unseen_cost)
+        }
+    }
+
+    /// Normalizes the `dist` by the cost of deleting all `len_a` and inserting
+    /// all `len_b` elements, which is the upper bound of the distance.
+    fn normalize(&self, dist: f64, len_a: usize, len_b: usize) -> f64 {
+        if len_a + len_b == 0 || self.indel_cost == 0. {
+            0.
+        } else {
+            dist / ((len_a + len_b) as f64 * self.indel_cost)
+        }
+    }
+}
+
+impl DistanceMetric for ProbabilisticLevenshtein {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        weighted_levenshtein(&a, &b, self.indel_cost, |c1, c2| {
+            if c1 == c2 {
+                0.
+            } else {
+                self.unseen_cost
+            }
+        })
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().chars().collect();
+        let b: Vec<_> = b.as_ref().chars().collect();
+        weighted_levenshtein(&a, &b, self.indel_cost, |c1, c2| {
+            self.substitution_cost(*c1, *c2)
+        })
+    }
+
+    /// The distance divided by the cost of deleting all elements of `a` and
+    /// inserting all elements of `b`.
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let (len_a, len_b) = (a.clone().count(), b.clone().count());
+        self.normalize(self.distance(a, b), len_a, len_b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        self.normalize(
+            self.str_distance(a, b),
+            a.chars().count(),
+            b.chars().count(),
+        )
+    }
+}
+
+/// Evaluates the Levenshtein distance in `f64`, where the cost of a
+/// substitution is determined by `substitution_cost` and insertions and
+/// deletions cost `indel_cost`.
+fn weighted_levenshtein<A, B, F>(a: &[A], b: &[B], indel_cost: f64, substitution_cost: F) -> f64
+where
+    F: Fn(&A, &B) -> f64,
+{
+    let mut cache: Vec<f64> = (0..=b.len()).map(|i| i as f64 * indel_cost).collect();
+
+    for (a_idx, c1) in a.iter().enumerate() {
+        let mut diagonal = cache[0];
+        cache[0] = (a_idx + 1) as f64 * indel_cost;
+        for (b_idx, c2) in b.iter().enumerate() {
+            let above = cache[b_idx + 1];
+            let substitution = diagonal + substitution_cost(c1, c2);
+            let insertion = cache[b_idx] + indel_cost;
+            let deletion = above + indel_cost;
+            cache[b_idx + 1] = substitution.min(insertion).min(deletion);
+            diagonal = above;
+        }
+    }
+    cache[b.len()]
+}
+
+/// Evaluates the normalized distance of `a` and `b`, if it does not exceed
+/// the `threshold`.
+///
+/// The metric is evaluated with a maximum distance derived from the
+/// `threshold`, so that the evaluation is short circuited as soon as the
+/// distance is guaranteed to exceed the `threshold`. The bound is rounded up
+/// and the normalized distance is compared with the `threshold` afterwards,
+/// so rounding `threshold * max_len` never rejects a distance that the
+/// default [`DistanceMetric::str_distance_within`] accepts.
+fn normalized_levenshtein_within<D, F>(
+    max_distance: Option<usize>,
+    a: &str,
+    b: &str,
+    threshold: f64,
+    with_max_distance: F,
+) -> Option<f64>
+where
+    D: DistanceMetric<Dist = DistanceValue>,
+    F: Fn(usize) -> D,
+{
+    if threshold < 0. {
+        return None;
+    }
+    let max_len = core::cmp::max(a.chars().count(), b.chars().count());
+    if max_len == 0 {
+        return Some(0.);
+    }
+    if threshold >= 1. {
+        return Some(normalized_levenshtein(
+            &with_max_distance(max_distance.unwrap_or(max_len)),
+            a.chars(),
+            b.chars(),
+        ));
+    }
+
+    let mut bound = ceil(threshold * max_len as f64) as usize;
+    if let Some(max_distance) = max_distance {
+        bound = min(bound, max_distance);
+    }
+
+    match with_max_distance(bound).distance(a.chars(), b.chars()) {
+        DistanceValue::Exact(val) if val <= bound => {
+            Some(val as f64 / max_len as f64).filter(|dist| *dist <= threshold)
+        }
+        _ => None,
+    }
+}
+
+fn normalized_levenshtein<D, S, T>(dist: &D, a: S, b: T) -> f64
+where
+    D: DistanceMetric<Dist = DistanceValue>,
+    S: IntoIterator,
     T: IntoIterator,
     <S as IntoIterator>::IntoIter: Clone,
     <T as IntoIterator>::IntoIter: Clone,
@@ -294,19 +1611,178 @@ where
 {
     let a = a.into_iter();
     let b = b.into_iter();
-    if let DistanceValue::Exact(val) = dist.distance(a.clone(), b.clone()) {
-        let len_a = a.count();
-        let len_b = b.count();
+    let dist = dist.distance(a.clone(), b.clone());
+    normalize(dist, a.count(), b.count())
+}
+
+/// Normalizes the distance by the length of the longer input, an exceeded
+/// distance is normalized to `1.`.
+fn normalize(dist: DistanceValue, len_a: usize, len_b: usize) -> f64 {
+    if let DistanceValue::Exact(val) = dist {
         if len_a + len_b == 0 {
             0.
         } else {
-            (val as f64) / std::cmp::max(len_a, len_b) as f64
+            (val as f64) / core::cmp::max(len_a, len_b) as f64
         }
     } else {
         1.
     }
 }
 
+/// The bit-parallel pattern of Myers' algorithm, that evaluates the
+/// Levenshtein distance to a text in `O(n * m / 64)` word operations.
+///
+/// The pattern is split into blocks of 64 chars. Every column of the DP matrix
+/// is evaluated block by block, where the horizontal delta at the bottom of a
+/// block is carried into the top of the next block.
+///
+/// See Heikki Hyyrö, "Explaining and Extending the Bit-parallel Approximate
+/// String Matching Algorithm of Myers" and "A Bit-Vector Algorithm for
+/// Computing Levenshtein and Damerau Edit Distances"
+#[derive(Debug, Clone)]
+pub(crate) struct MyersPattern {
+    /// For each char of the pattern, the bits of the positions it occurs at,
+    /// one word per block.
+    peq: Map<char, Vec<u64>>,
+    /// The number of chars of the pattern.
+    len: usize,
+}
+
+impl MyersPattern {
+    /// The number of chars of a block.
+    pub(crate) const BLOCK_LEN: usize = 64;
+
+    pub(crate) fn new(pattern: &[char]) -> Self {
+        let blocks = pattern.len().div_ceil(Self::BLOCK_LEN);
+        let mut peq = Map::new();
+        for (idx, c) in pattern.iter().enumerate() {
+            peq.entry(*c).or_insert_with(|| vec![0u64; blocks])[idx / Self::BLOCK_LEN] |=
+                1 << (idx % Self::BLOCK_LEN);
+        }
+        Self {
+            peq,
+            len: pattern.len(),
+        }
+    }
+
+    /// The Levenshtein distance between the pattern and the `text`.
+    pub(crate) fn distance(&self, text: &[char]) -> usize {
+        self.distance_chars(text.iter().copied())
+    }
+
+    /// The Levenshtein distance between the pattern and the `text`, whose
+    /// chars are only visited once and in order.
+    pub(crate) fn distance_chars(&self, text: impl IntoIterator<Item = char>) -> usize {
+        myers_distance(
+            self.len,
+            text.into_iter().map(|c| self.peq.get(&c)),
+            |peq, block| peq.map_or(0, |peq| peq[block]),
+        )
+    }
+}
+
+/// The bit-parallel pattern of Myers' algorithm for ASCII strs, see
+/// [`MyersPattern`].
+///
+/// Instead of a map from the chars to their positions, every block holds a
+/// table that is indexed by the bytes directly.
+#[derive(Debug, Clone)]
+pub(crate) struct AsciiMyersPattern {
+    /// For each block, the bits of the positions of every byte in the block.
+    peq: Vec<[u64; 128]>,
+    /// The number of bytes of the pattern.
+    len: usize,
+}
+
+impl AsciiMyersPattern {
+    /// Creates the pattern of the bytes of an ASCII str.
+    pub(crate) fn new(pattern: &[u8]) -> Self {
+        let mut peq = vec![[0u64; 128]; pattern.len().div_ceil(MyersPattern::BLOCK_LEN)];
+        for (idx, c) in pattern.iter().enumerate() {
+            peq[idx / MyersPattern::BLOCK_LEN][usize::from(c & 0x7f)] |=
+                1 << (idx % MyersPattern::BLOCK_LEN);
+        }
+        Self {
+            peq,
+            len: pattern.len(),
+        }
+    }
+
+    /// The Levenshtein distance between the pattern and the bytes of an ASCII
+    /// `text`.
+    pub(crate) fn distance(&self, text: &[u8]) -> usize {
+        myers_distance(self.len, text.iter(), |c, block| {
+            self.peq[block][usize::from(*c & 0x7f)]
+        })
+    }
+}
+
+/// The Levenshtein distance between a pattern of `len` elements and a text,
+/// where `eq(c, block)` returns the positions of the text's element `c` in the
+/// `block` of the pattern.
+#[inline]
+fn myers_distance<C, F>(len: usize, text: impl IntoIterator<Item = C>, eq: F) -> usize
+where
+    F: Fn(&C, usize) -> u64,
+{
+    if len == 0 {
+        return text.into_iter().count();
+    }
+    let blocks = len.div_ceil(MyersPattern::BLOCK_LEN);
+    // the bit of the last row of the pattern in the last block
+    let last = 1u64 << ((len - 1) % MyersPattern::BLOCK_LEN);
+    let mut pv = vec![!0u64; blocks];
+    let mut mv = vec![0u64; blocks];
+    let mut score = len;
+
+    for c in text {
+        // the first row of the matrix increases by one each column
+        let mut carry = 1i8;
+        for (block, (pv, mv)) in pv.iter_mut().zip(mv.iter_mut()).enumerate() {
+            let high = if block + 1 == blocks { last } else { 1 << 63 };
+            carry = advance_block(pv, mv, eq(&c, block), carry, high);
+        }
+        match carry {
+            1 => score += 1,
+            -1 => score -= 1,
+            _ => {}
+        }
+    }
+    score
+}
+
+/// Advances the vertical deltas `pv` and `mv` of a block by one column, where
+/// `eq` are the positions of the column's char in the block and `carry` is the
+/// horizontal delta at the top of the block.
+///
+/// Returns the horizontal delta of the row `high`.
+#[inline]
+fn advance_block(pv: &mut u64, mv: &mut u64, eq: u64, carry: i8, high: u64) -> i8 {
+    let xv = eq | *mv;
+    // a negative delta at the top acts like a match in the first row
+    let eq = if carry < 0 { eq | 1 } else { eq };
+    let xh = ((eq & *pv).wrapping_add(*pv) ^ *pv) | eq;
+    let mut ph = *mv | !(xh | *pv);
+    let mut mh = *pv & xh;
+    let carry_out = if ph & high != 0 {
+        1
+    } else if mh & high != 0 {
+        -1
+    } else {
+        0
+    };
+    ph <<= 1;
+    mh <<= 1;
+    match carry {
+        1 => ph |= 1,
+        -1 => mh |= 1,
+        _ => {}
+    }
+    *pv = mh | !(xv | ph);
+    *mv = ph & xv;
+    carry_out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +1802,418 @@ mod tests {
         assert_eq!(*Levenshtein::with_max_distance(10).str_distance(s1, s2), 10);
     }
 
+    /// Pseudo random strings over a small alphabet to provoke many matches.
+    fn random_strings(seed: u64, count: usize, max_len: usize) -> Vec<String> {
+        let alphabet: Vec<char> = "abcdeä☃".chars().collect();
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        (0..count)
+            .map(|_| {
+                let len = next() % (max_len + 1);
+                (0..len)
+                    .map(|_| alphabet[next() % alphabet.len()])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn myers_matches_dp() {
+        let strings = random_strings(0x2545_f491_4f6c_dd1d, 60, 70);
+        for a in strings.iter() {
+            for b in strings.iter() {
+                let dp = Levenshtein::default().distance(a.chars(), b.chars());
+                assert_eq!(Levenshtein::default().str_distance(a, b), dp);
+                for max_dist in [0, 1, 5, 20].iter() {
+                    let lev = Levenshtein::with_max_distance(*max_dist);
+                    assert_eq!(lev.str_distance(a, b), lev.distance(a.chars(), b.chars()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blocked_myers_matches_dp() {
+        // lengths that cross the block boundary at 64 and 128 chars
+        let strings: Vec<String> = random_strings(0x9e37_79b9_7f4a_7c15, 80, 140)
+            .into_iter()
+            .filter(|s| s.chars().count() >= 60)
+            .collect();
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        for (idx, a) in strings.iter().enumerate() {
+            pairs.push((a.clone(), strings[(idx + 1) % strings.len()].clone()));
+            // few edits to get small distances as well
+            let mut chars: Vec<char> = a.chars().collect();
+            chars.insert(idx % chars.len(), 'x');
+            chars.remove((idx * 7) % chars.len());
+            let len = chars.len();
+            chars[(idx * 13) % len] = 'y';
+            pairs.push((a.clone(), chars.into_iter().collect()));
+        }
+        for (a, b) in pairs.iter() {
+            let a: Vec<char> = a.chars().collect();
+            let b: Vec<char> = b.chars().collect();
+            let dp = *Levenshtein::default().distance(a.iter(), b.iter());
+            assert_eq!(MyersPattern::new(&a).distance(&b), dp);
+            assert_eq!(MyersPattern::new(&b).distance(&a), dp);
+
+            // the same pair with ASCII chars
+            let ascii = |s: &[char]| -> Vec<u8> {
+                s.iter()
+                    .map(|c| if c.is_ascii() { *c as u8 } else { b'z' })
+                    .collect()
+            };
+            let (a, b) = (ascii(&a), ascii(&b));
+            let dp = *Levenshtein::default().distance(a.iter(), b.iter());
+            assert_eq!(AsciiMyersPattern::new(&a).distance(&b), dp);
+            assert_eq!(AsciiMyersPattern::new(&b).distance(&a), dp);
+        }
+
+        // patterns of exactly one and two blocks
+        for len in [63, 64, 65, 127, 128, 129].iter() {
+            let a = "ab".repeat(70)[..*len].to_string();
+            let b = "ba".repeat(70);
+            let dp = Levenshtein::default().distance(a.chars(), b.chars());
+            assert_eq!(Levenshtein::default().str_distance(&a, &b), dp);
+        }
+    }
+
+    #[test]
+    fn edit_ops_kitten_sitting() {
+        let ops = Levenshtein::default().edit_ops("kitten".chars(), "sitting".chars());
+        assert_eq!(
+            ops,
+            vec![
+                EditOp::Substitute {
+                    a: 0,
+                    b: 0,
+                    from: 'k',
+                    to: 's'
+                },
+                EditOp::Keep {
+                    a: 1,
+                    b: 1,
+                    item: 'i'
+                },
+                EditOp::Keep {
+                    a: 2,
+                    b: 2,
+                    item: 't'
+                },
+                EditOp::Keep {
+                    a: 3,
+                    b: 3,
+                    item: 't'
+                },
+                EditOp::Substitute {
+                    a: 4,
+                    b: 4,
+                    from: 'e',
+                    to: 'i'
+                },
+                EditOp::Keep {
+                    a: 5,
+                    b: 5,
+                    item: 'n'
+                },
+                EditOp::Insert { b: 6, item: 'g' },
+            ]
+        );
+    }
+
+    /// Asserts that `ops` is a minimal edit script that transforms `a` into
+    /// `b`.
+    fn assert_edit_script(a: &str, b: &str, ops: Vec<EditOp<char>>) {
+        let edits = ops
+            .iter()
+            .filter(|op| !matches!(op, EditOp::Keep { .. }))
+            .count();
+        assert_eq!(
+            DistanceValue::Exact(edits),
+            Levenshtein::default().str_distance(a, b)
+        );
+
+        // applying the ops to `a` yields `b`
+        let mut next_a = 0;
+        let mut transformed = String::new();
+        for op in ops {
+            match op {
+                EditOp::Keep { a: idx, item, .. } => {
+                    assert_eq!(idx, next_a);
+                    next_a += 1;
+                    transformed.push(item);
+                }
+                EditOp::Substitute { a: idx, to, .. } => {
+                    assert_eq!(idx, next_a);
+                    next_a += 1;
+                    transformed.push(to);
+                }
+                EditOp::Delete { a: idx, .. } => {
+                    assert_eq!(idx, next_a);
+                    next_a += 1;
+                }
+                EditOp::Insert { b: idx, item } => {
+                    assert_eq!(b.chars().nth(idx), Some(item));
+                    transformed.push(item);
+                }
+            }
+        }
+        assert_eq!(next_a, a.chars().count());
+        assert_eq!(&transformed, b);
+    }
+
+    #[test]
+    fn edit_ops_transform() {
+        let strings = random_strings(0x9e37_79b9_7f4a_7c15, 30, 12);
+        for a in strings.iter() {
+            for b in strings.iter() {
+                assert_edit_script(a, b, Levenshtein::default().edit_ops(a.chars(), b.chars()));
+            }
+        }
+        assert!(Levenshtein::default()
+            .edit_ops(&[0u8; 0], &[0u8; 0])
+            .is_empty());
+        assert_eq!(
+            Levenshtein::default().edit_ops(&[1, 2], &[2]),
+            vec![
+                EditOp::Delete { a: 0, item: &1 },
+                EditOp::Keep {
+                    a: 1,
+                    b: 0,
+                    item: &2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_ops_linear() {
+        let strings = random_strings(0x853c_49e6_748f_ea9b, 30, 12);
+        for a in strings.iter() {
+            for b in strings.iter() {
+                assert_edit_script(
+                    a,
+                    b,
+                    Levenshtein::default().edit_ops_linear(a.chars(), b.chars()),
+                );
+            }
+        }
+        let long = random_strings(0xda94_2042_e4dd_58b5, 8, 400);
+        for a in long.iter() {
+            for b in long.iter() {
+                assert_edit_script(
+                    a,
+                    b,
+                    Levenshtein::default().edit_ops_linear(a.chars(), b.chars()),
+                );
+            }
+        }
+        assert!(Levenshtein::default()
+            .edit_ops_linear(&[0u8; 0], &[0u8; 0])
+            .is_empty());
+        assert_eq!(
+            Levenshtein::default().edit_ops_linear("kitten".chars(), "sitting".chars()),
+            Levenshtein::default().edit_ops("kitten".chars(), "sitting".chars())
+        );
+    }
+
+    #[test]
+    fn damerau_edit_ops() {
+        assert_eq!(
+            DamerauLevenshtein::default().edit_ops("ab".chars(), "ba".chars()),
+            vec![DamerauEditOp::Transpose {
+                a: 0,
+                b: 0,
+                first: 'a',
+                second: 'b'
+            }]
+        );
+        // the transposition is no cheaper than two substitutions
+        assert_eq!(
+            DamerauLevenshtein::with_transposition_cost(2).edit_ops("ab".chars(), "ba".chars()),
+            vec![
+                DamerauEditOp::Substitute {
+                    a: 0,
+                    b: 0,
+                    from: 'a',
+                    to: 'b'
+                },
+                DamerauEditOp::Substitute {
+                    a: 1,
+                    b: 1,
+                    from: 'b',
+                    to: 'a'
+                },
+            ]
+        );
+
+        let strings = random_strings(0x2545_f491_4f6c_dd1d, 30, 10);
+        for transposition_cost in 0..3 {
+            let dist = DamerauLevenshtein::with_transposition_cost(transposition_cost);
+            for a in strings.iter() {
+                for b in strings.iter() {
+                    let ops = dist.edit_ops(a.chars(), b.chars());
+                    let mut cost = 0;
+                    // applying the ops to `a` yields `b`
+                    let mut next_a = 0;
+                    let mut transformed = String::new();
+                    for op in ops {
+                        match op {
+                            DamerauEditOp::Keep { a: idx, item, .. } => {
+                                assert_eq!(idx, next_a);
+                                next_a += 1;
+                                transformed.push(item);
+                            }
+                            DamerauEditOp::Substitute { a: idx, to, .. } => {
+                                assert_eq!(idx, next_a);
+                                next_a += 1;
+                                cost += 1;
+                                transformed.push(to);
+                            }
+                            DamerauEditOp::Delete { a: idx, .. } => {
+                                assert_eq!(idx, next_a);
+                                next_a += 1;
+                                cost += 1;
+                            }
+                            DamerauEditOp::Insert { b: idx, item } => {
+                                assert_eq!(b.chars().nth(idx), Some(item));
+                                cost += 1;
+                                transformed.push(item);
+                            }
+                            DamerauEditOp::Transpose {
+                                a: idx,
+                                first,
+                                second,
+                                ..
+                            } => {
+                                assert_eq!(idx, next_a);
+                                assert_ne!(first, second);
+                                next_a += 2;
+                                cost += transposition_cost;
+                                transformed.push(second);
+                                transformed.push(first);
+                            }
+                        }
+                    }
+                    assert_eq!(next_a, a.chars().count());
+                    assert_eq!(&transformed, b);
+                    assert_eq!(DistanceValue::Exact(cost), dist.str_distance(a, b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn distance_reader() {
+        use std::io::Cursor;
+
+        let words = random_strings(7, 40, 150);
+        let lev = Levenshtein::default();
+        for a in words.iter().take(8) {
+            for b in words.iter() {
+                assert_eq!(
+                    lev.distance_reader(Cursor::new(a), Cursor::new(b)).unwrap(),
+                    lev.str_distance(a, b),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+
+        let (a, b) = ("Ünïcödé ☃ text\nwith lines", "Unicode ☂ text\nwith lines");
+        assert_eq!(
+            lev.distance_reader(Cursor::new(a), Cursor::new(b)).unwrap(),
+            lev.str_distance(a, b)
+        );
+        assert_eq!(
+            Levenshtein::with_max_distance(2)
+                .distance_reader(Cursor::new(a), Cursor::new(b))
+                .unwrap(),
+            DistanceValue::Exceeded(2)
+        );
+        assert_eq!(
+            lev.distance_reader(Cursor::new(""), Cursor::new("abc"))
+                .unwrap(),
+            DistanceValue::Exact(3)
+        );
+
+        // invalid UTF-8 in either input
+        for invalid in [&[b'a', 0xff][..], &[0xe2, 0x98][..], &[0x80][..]].iter() {
+            let err = lev
+                .distance_reader(Cursor::new("a"), Cursor::new(invalid))
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+            let err = lev
+                .distance_reader(Cursor::new(invalid), Cursor::new("a"))
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn max_distance_exceeded() {
+        let lev = Levenshtein::with_max_distance(2);
+        assert_eq!(lev.str_distance("", "abcdef"), DistanceValue::Exceeded(2));
+        assert_eq!(
+            lev.distance("abcdef".chars(), "".chars()),
+            DistanceValue::Exceeded(2)
+        );
+        assert_eq!(
+            lev.distance("abcdef".chars(), "a".chars()),
+            DistanceValue::Exceeded(2)
+        );
+        assert_eq!(
+            Levenshtein::with_max_distance(0).distance("abc".chars(), "abd".chars()),
+            DistanceValue::Exceeded(0)
+        );
+        assert_eq!(lev.str_distance("abc", "abd"), DistanceValue::Exact(1));
+        assert_eq!(
+            Levenshtein::default().distance("abcdef".chars(), "xa".chars()),
+            DistanceValue::Exact(6)
+        );
+    }
+
+    #[test]
+    fn banded_matches_full() {
+        let strings = random_strings(0x6a09_e667_f3bc_c908, 40, 20);
+        let long = random_strings(0xbb67_ae85_84ca_a73b, 6, 300);
+        for (a, b) in strings
+            .iter()
+            .flat_map(|a| strings.iter().map(move |b| (a, b)))
+            .chain(long.iter().flat_map(|a| long.iter().map(move |b| (a, b))))
+        {
+            let full = *Levenshtein::default().distance(a.chars(), b.chars());
+            for max_dist in [0, 1, 2, 3, 5, 8, 300].iter() {
+                let lev = Levenshtein::with_max_distance(*max_dist);
+                assert_eq!(
+                    lev.distance(a.chars(), b.chars()),
+                    lev.bounded(full),
+                    "{} {} {}",
+                    a,
+                    b,
+                    max_dist
+                );
+            }
+        }
+        // a long common middle part with edits at both ends
+        let a = format!("x{}y", "abc".repeat(200));
+        let b = format!("{}z", "abc".repeat(200));
+        let lev = Levenshtein::with_max_distance(2);
+        assert_eq!(lev.distance(a.chars(), b.chars()), DistanceValue::Exact(2));
+        let lev = Levenshtein::with_max_distance(1);
+        assert_eq!(
+            lev.distance(a.chars(), b.chars()),
+            DistanceValue::Exceeded(1)
+        );
+    }
+
     #[test]
     fn levenshtein_normalized() {
         assert_eq!(
@@ -344,6 +2232,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn levenshtein_within() {
+        let s1 = "The quick brown fox jumped over the angry dog.";
+        let s2 = "Lorem ipsum dolor sit amet, dicta latine an eam.";
+        // the bound derived from the threshold short circuits the evaluation
+        assert_eq!(
+            Levenshtein::with_max_distance((0.1 * s2.len() as f64) as usize).str_distance(s1, s2),
+            DistanceValue::Exceeded(4)
+        );
+        assert_eq!(
+            Levenshtein::default().str_distance_within(s1, s2, 0.1),
+            None
+        );
+
+        let pairs = [
+            ("kitten", "sitting"),
+            ("", ""),
+            ("", "abc"),
+            ("sunday", "saturday"),
+            ("abc", "abc"),
+            (s1, s2),
+        ];
+        for (a, b) in pairs.iter() {
+            for threshold in [0., 0.1, 0.3, 0.5, 0.8, 1.].iter() {
+                let expected = Some(Levenshtein::default().str_normalized(a, b))
+                    .filter(|dist| dist <= threshold);
+                assert_eq!(
+                    Levenshtein::default().str_distance_within(a, b, *threshold),
+                    expected
+                );
+                let expected = Some(DamerauLevenshtein::default().str_normalized(a, b))
+                    .filter(|dist| dist <= threshold);
+                assert_eq!(
+                    DamerauLevenshtein::default().str_distance_within(a, b, *threshold),
+                    expected
+                );
+            }
+        }
+        assert_eq!(
+            Levenshtein::with_max_distance(1).str_distance_within("kitten", "sitting", 0.5),
+            None
+        );
+
+        // 0.29 * 100 is slightly less than 29
+        let a = "a".repeat(100);
+        let b = "a".repeat(71) + &"b".repeat(29);
+        assert_eq!(Levenshtein::default().str_normalized(&a, &b), 0.29);
+        assert_eq!(
+            Levenshtein::default().str_distance_within(&a, &b, 0.29),
+            Some(0.29)
+        );
+        assert_eq!(
+            DamerauLevenshtein::default().str_distance_within(&a, &b, 0.29),
+            Some(0.29)
+        );
+        assert_eq!(
+            Levenshtein::default().str_distance_within(&a, &b, 0.28),
+            None
+        );
+    }
+
+    #[test]
+    fn levenshtein_within_short_circuits() {
+        let a = "abcdefghij".repeat(10);
+        let b = "klmnopqrst".repeat(10);
+        let bounds = core::cell::RefCell::new(Vec::new());
+        let within = normalized_levenshtein_within(None, &a, &b, 0.05, |bound| {
+            bounds.borrow_mut().push(bound);
+            Levenshtein::with_max_distance(bound)
+        });
+        assert_eq!(within, None);
+        // the metric is only evaluated once, with the bound of the threshold
+        assert_eq!(*bounds.borrow(), vec![5]);
+
+        let (dist, bounded) =
+            Levenshtein::with_max_distance(5).distance_verbose(a.chars(), b.chars());
+        let (_, full) = Levenshtein::default().distance_verbose(a.chars(), b.chars());
+        assert_eq!(dist, DistanceValue::Exceeded(5));
+        assert!(bounded.short_circuited);
+        assert!(bounded.cells_evaluated * 100 < full.cells_evaluated);
+    }
+
     #[test]
     fn damerau_levenshtein_dist() {
         assert_eq!(*DamerauLevenshtein::default().str_distance("", ""), 0);
@@ -373,6 +2343,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn damerau_levenshtein_small_max_distance() {
+        // the row of a bounded evaluation is as long as the remaining input
+        // for every maximum distance below the length of the input
+        for (a, b) in [
+            ("kitten", "sitting"),
+            ("ab", "ba"),
+            ("abcd", "xbcy"),
+            ("a", "b"),
+        ]
+        .iter()
+        {
+            let dist = *DamerauLevenshtein::default().str_distance(a, b);
+            for max_dist in 0..=dist + 1 {
+                let expected = if dist > max_dist {
+                    DistanceValue::Exceeded(max_dist)
+                } else {
+                    DistanceValue::Exact(dist)
+                };
+                assert_eq!(
+                    DamerauLevenshtein::with_max_distance(max_dist).str_distance(a, b),
+                    expected
+                );
+            }
+        }
+    }
+
     #[test]
     fn damerau_levenshtein_normalized() {
         assert_eq!(DamerauLevenshtein::default().str_normalized("", ""), 0.);
@@ -407,4 +2404,232 @@ Dan ate the clouds like cotton candy.";
             strsim::damerau_levenshtein(s1, s2)
         );
     }
+
+    #[test]
+    fn damerau_levenshtein_transposition_cost() {
+        assert_eq!(*DamerauLevenshtein::default().str_distance("ab", "ba"), 1);
+        let free = DamerauLevenshtein::with_transposition_cost(0);
+        assert_eq!(*free.str_distance("ab", "ba"), 0);
+        assert_eq!(*free.str_distance("abcdef", "badcfe"), 0);
+        assert_eq!(*free.str_distance("ab", "bc"), 2);
+        assert_eq!(free.str_distance_within("abcdef", "badcfe", 0.), Some(0.));
+        let expensive = DamerauLevenshtein::with_transposition_cost(5);
+        assert_eq!(*expensive.str_distance("ab", "ba"), 2);
+        assert_eq!(
+            *expensive.str_distance("jellyifhs", "jellyfish"),
+            *Levenshtein::default().str_distance("jellyifhs", "jellyfish")
+        );
+        assert_eq!(expensive.str_distance_within("abcdef", "badcfe", 0.5), None);
+        assert_eq!(
+            DamerauLevenshtein::default().str_distance_within("abcdef", "badcfe", 0.5),
+            Some(0.5)
+        );
+        let bounded = DamerauLevenshtein::with_max_distance(1).transposition_cost(0);
+        assert_eq!(*bounded.str_distance("abcdef", "badcfe"), 0);
+        assert_eq!(
+            bounded.str_distance("abcdef", "xyz"),
+            DistanceValue::Exceeded(1)
+        );
+    }
+
+    #[test]
+    fn distance_verbose() {
+        let a: String = "abcdefghij".repeat(20);
+        let b: String = "klmnopqrst".repeat(20);
+        let (dist, full) = Levenshtein::default().distance_verbose(a.chars(), b.chars());
+        assert_eq!(dist, DistanceValue::Exact(200));
+        assert_eq!(full.cells_evaluated, 200 * 200);
+        assert!(!full.short_circuited);
+
+        // the band is exceeded in the first rows
+        let (dist, tight) =
+            Levenshtein::with_max_distance(3).distance_verbose(a.chars(), b.chars());
+        assert_eq!(dist, DistanceValue::Exceeded(3));
+        assert!(tight.short_circuited);
+        assert!(
+            tight.cells_evaluated * 100 < full.cells_evaluated,
+            "{:?}",
+            tight
+        );
+
+        // the band is evaluated completely without exceeding it
+        let c: String = a.chars().map(|c| if c == 'j' { 'x' } else { c }).collect();
+        let (dist, loose) =
+            Levenshtein::with_max_distance(30).distance_verbose(a.chars(), c.chars());
+        assert_eq!(dist, DistanceValue::Exact(20));
+        assert!(!loose.short_circuited);
+        assert!(loose.cells_evaluated < full.cells_evaluated);
+        assert_eq!(
+            dist,
+            Levenshtein::with_max_distance(30).str_distance(&a, &c)
+        );
+
+        // the lengths alone exceed the maximum distance
+        let (dist, stats) =
+            Levenshtein::with_max_distance(3).distance_verbose(a.chars(), "a".chars());
+        assert_eq!(dist, DistanceValue::Exceeded(3));
+        assert_eq!(
+            stats,
+            Stats {
+                cells_evaluated: 0,
+                short_circuited: true
+            }
+        );
+
+        // the shorter input is the prefix of the longer one
+        let (dist, stats) =
+            Levenshtein::with_max_distance(1).distance_verbose("abcdef".chars(), "abc".chars());
+        assert_eq!(dist, DistanceValue::Exceeded(1));
+        assert_eq!(
+            stats,
+            Stats {
+                cells_evaluated: 0,
+                short_circuited: true
+            }
+        );
+
+        // the band is evaluated completely, only the last cell exceeds it
+        let (dist, stats) =
+            Levenshtein::with_max_distance(1).distance_verbose("ab".chars(), "ba".chars());
+        assert_eq!(dist, DistanceValue::Exceeded(1));
+        assert!(stats.short_circuited);
+        assert_eq!(
+            Levenshtein::default().distance_verbose("abc".chars(), "abc".chars()),
+            (DistanceValue::Exact(0), Stats::default())
+        );
+    }
+
+    #[test]
+    fn cost_fn_levenshtein() {
+        // substituting the adjacent keys 'a' and 's' costs 1, others 3
+        let typos = Levenshtein::with_cost_fn(|c1, c2| match (c1, c2) {
+            ('a', 's') | ('s', 'a') => 1,
+            _ => 3,
+        });
+        assert_eq!(typos.str_distance("sat", "aat"), 1);
+        assert_eq!(typos.str_distance("sat", "pat"), 2);
+        assert_eq!(typos.str_distance("", "abc"), 3);
+        assert_eq!(typos.str_distance("abc", "abc"), 0);
+        assert_eq!(typos.str_normalized("sat", "aat"), 1. / 3.);
+        assert_eq!(typos.str_normalized("ab", "cd"), 1.);
+        assert_eq!(typos.str_normalized("", ""), 0.);
+        // only str consult the cost function
+        assert_eq!(typos.distance(&[1, 2], &[1, 3]), 1);
+
+        // the cheap substitution is kept, the expensive one becomes an
+        // insertion and a deletion
+        let flat = Levenshtein::default().edit_ops("sap".chars(), "aat".chars());
+        assert_eq!(
+            flat,
+            vec![
+                EditOp::Substitute {
+                    a: 0,
+                    b: 0,
+                    from: 's',
+                    to: 'a'
+                },
+                EditOp::Keep {
+                    a: 1,
+                    b: 1,
+                    item: 'a'
+                },
+                EditOp::Substitute {
+                    a: 2,
+                    b: 2,
+                    from: 'p',
+                    to: 't'
+                },
+            ]
+        );
+        let ops = typos.edit_ops("sap".chars(), "aat".chars());
+        assert_eq!(
+            ops,
+            vec![
+                EditOp::Substitute {
+                    a: 0,
+                    b: 0,
+                    from: 's',
+                    to: 'a'
+                },
+                EditOp::Keep {
+                    a: 1,
+                    b: 1,
+                    item: 'a'
+                },
+                EditOp::Insert { b: 2, item: 't' },
+                EditOp::Delete { a: 2, item: 'p' },
+            ]
+        );
+        assert_eq!(typos.str_distance("sap", "aat"), 3);
+
+        // a flat cost function is the Levenshtein distance
+        let flat = Levenshtein::with_cost_fn(|_, _| 1);
+        for (a, b) in [("kitten", "sitting"), ("saturday", "sunday"), ("", "a☃")].iter() {
+            assert_eq!(
+                flat.str_distance(a, b),
+                *Levenshtein::default().str_distance(a, b)
+            );
+            assert_eq!(
+                flat.edit_ops(a.chars(), b.chars()),
+                Levenshtein::default().edit_ops(a.chars(), b.chars())
+            );
+        }
+    }
+
+    #[test]
+    fn case_aware_levenshtein() {
+        assert_eq!(
+            CaseAwareLevenshtein::new(0.).str_distance("Hello", "hello"),
+            0.
+        );
+        assert_eq!(
+            CaseAwareLevenshtein::new(1.).str_distance("Hello", "hello"),
+            1.
+        );
+        assert_eq!(
+            CaseAwareLevenshtein::new(0.3).str_distance("Hello", "hello"),
+            0.3
+        );
+        assert_eq!(
+            CaseAwareLevenshtein::new(0.3).str_distance("HELLO", "hello"),
+            1.5
+        );
+        assert_eq!(
+            CaseAwareLevenshtein::new(0.3).str_distance("kitten", "sitting"),
+            3.
+        );
+        assert_eq!(CaseAwareLevenshtein::new(0.3).str_distance("", "abc"), 3.);
+        assert_eq!(
+            CaseAwareLevenshtein::new(0.5).str_normalized("Hello", "hello"),
+            0.1
+        );
+        assert_eq!(CaseAwareLevenshtein::new(0.5).str_normalized("", ""), 0.);
+    }
+
+    #[test]
+    fn probabilistic_levenshtein() {
+        let dist = ProbabilisticLevenshtein::new(vec![(('u', 'v'), 0.9f64.ln())]);
+        // the likely substitution is preferred over a deletion and insertion
+        assert_eq!(dist.str_distance("uale", "vale"), -0.9f64.ln());
+        assert!(dist.str_distance("uale", "vale") < -2. * DEFAULT_INDEL_LOG_PROB);
+        // the matrix is directional
+        assert_eq!(
+            format!("{:.6}", dist.str_distance("vale", "uale")),
+            format!("{:.6}", -DEFAULT_UNSEEN_LOG_PROB)
+        );
+        assert_eq!(dist.str_distance("vale", "vale"), 0.);
+        assert_eq!(dist.str_distance("", "ab"), -2. * DEFAULT_INDEL_LOG_PROB);
+        assert_eq!(dist.str_normalized("", ""), 0.);
+        assert_eq!(dist.str_normalized("ab", "cd"), 1.);
+        assert_eq!(dist.str_normalized("ab", "ad"), 0.5);
+
+        // a substitution is chosen over a deletion and insertion only if it is
+        // more probable
+        let dist = ProbabilisticLevenshtein::with_log_probs(
+            vec![(('u', 'v'), 0.01f64.ln())],
+            0.5f64.ln(),
+            0.0001f64.ln(),
+        );
+        assert_eq!(dist.str_distance("uale", "vale"), -2. * 0.5f64.ln());
+    }
 }
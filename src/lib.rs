@@ -66,26 +66,105 @@
 //!
 //! assert_eq!(*Levenshtein::default().distance(&[1,2,3], &[1,2,3,4,5,6]),3);
 //! ```
+//!
+//! The items of both inputs don't need to be of the same type, as long as
+//! the items of the first input can be compared with the items of the second
+//! input, e.g. `&str` with `String`. Both inputs should yield items of the
+//! same "level" of references: `&[1u8, 2]` yields `&u8` and is compared with
+//! `&vec![1u8, 2]` or `vec![&1u8, &2]`, whereas `[1u8, 2]` yields `u8`.
+//!
+//! ```rust
+//! use str_distance::{DistanceMetric, Levenshtein, DistanceValue};
+//!
+//! let tokens = ["new", "york", "mets"];
+//! let owned: Vec<String> = vec!["new".to_string(), "york".to_string()];
+//! assert_eq!(*Levenshtein::default().distance(&tokens, &owned), 1);
+//! assert_eq!(*Levenshtein::default().distance([1u8, 2, 3], vec![1u8, 2, 4]), 1);
+//! ```
+//!
+//! ## `no_std`
+//!
+//! The crate depends on `std` via the default `std` feature. Without it the
+//! crate is `no_std` and only requires `alloc`, in which case the `libm`
+//! feature provides the float functions that are not part of `core`:
+//!
+//! ```toml
+//! [dependencies]
+//! str-distance = { version = "0.1", default-features = false, features = ["libm"] }
+//! ```
+//!
+//! Without `std` the metrics count chars in a `BTreeMap` instead of a
+//! `HashMap`.
 
 #![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature is required for the float functions");
 
-use std::ops::Deref;
+use alloc::collections::BinaryHeap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::Deref;
 
-pub use jaro::{Jaro, JaroWinkler};
-pub use levenshtein::{DamerauLevenshtein, Levenshtein};
-pub use modifiers::{Winkler, WinklerConfig};
-pub use qgram::{Cosine, Jaccard, Overlap, QGram, SorensenDice};
+pub use alignment::{Gotoh, NeedlemanWunsch, SmithWaterman};
+pub use bag::{Bag, CharHistogram};
+pub use dedup::{cluster, cluster_with_prefilter, dedup_components};
+pub use ensemble::{Ensemble, MaxMetric, MinMetric};
+pub use hamming::Hamming;
+pub use indel::{Indel, Indel as Lcs};
+pub use jaro::{CustomJaro, Jaro, JaroDetails, JaroWinkler};
+pub use length::LengthRatio;
+pub use levenshtein::{
+    CaseAwareLevenshtein, CostFnLevenshtein, DamerauLevenshtein, Levenshtein,
+    ProbabilisticLevenshtein,
+};
+pub use modifiers::{Reversed, Truncated, Winkler, WinklerConfig};
+pub use phonetic::{Caverphone, DoubleMetaphone, Editex, Mra, Soundex};
+pub use qgram::{
+    AdaptiveJaccard, Cosine, Jaccard, Overlap, PositionalQGram, QGram, QGramError, QGramProfile,
+    SorensenDice, Tversky,
+};
 pub use ratcliff::RatcliffObershelp;
-pub use token::{TokenSet, TokenSort};
+pub use set::{SetDice, SetJaccard};
+pub use sift::{Sift, Sift4, SiftAlgorithm};
+pub use signature::{simhash, simhash_distance, Signature, Signed};
+pub use token::{
+    MongeElkan, Partial, SoftTfIdf, TfIdf, TokenCosine, TokenDice, TokenOverlap, TokenSet,
+    TokenSort,
+};
 
+pub mod alignment;
+pub mod bag;
+pub mod bktree;
+pub mod dedup;
+pub mod diff;
+pub mod ensemble;
+pub mod fuzzywuzzy;
+pub mod hamming;
+pub mod indel;
 pub mod jaro;
+pub mod length;
 pub mod levenshtein;
+pub mod matcher;
+#[cfg(feature = "rayon")]
+pub mod matrix;
 pub mod modifiers;
+pub mod normalize;
+pub mod phonetic;
 pub mod qgram;
 pub mod ratcliff;
+pub mod set;
+pub mod sift;
+pub mod signature;
 pub mod token;
 mod utils;
 
+use crate::utils::{cmp_score, count_eq, DelimDistinct};
+
 /// Evaluates the distance between two strings based on the provided
 /// [`crate::DistanceMetric`].
 ///
@@ -108,6 +187,94 @@ where
     dist.str_distance(a, b)
 }
 
+/// Evaluates the distance between two lists of words based on the provided
+/// [`crate::DistanceMetric`], where every word is treated as an atomic
+/// element.
+///
+/// This is the word level counterpart of [`str_distance`], e.g.
+/// [`Levenshtein`] counts the words that are inserted, deleted or substituted
+/// instead of the chars. Q-gram metrics like [`QGram`] or [`Jaccard`] then
+/// operate on word shingles, i.e. on `q` consecutive words.
+///
+/// The words of both lists may be of different types, e.g. `&str` and
+/// `String`. Since the word types are inferred from the lists, an empty array
+/// literal needs a type annotation, e.g.
+/// `word_distance::<&str, &str, _>(&[], &b, dist)`.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{word_distance, DistanceValue, Jaccard, Levenshtein};
+/// let a = ["the", "quick", "brown", "fox"];
+/// let b = ["the", "brown", "quick", "fox"];
+/// assert_eq!(word_distance(&a, &b, Levenshtein::default()), DistanceValue::Exact(2));
+/// // the shingles "the quick", "quick brown", "brown fox" and "the brown", "brown quick", "quick fox"
+/// assert_eq!(word_distance(&a, &b, Jaccard::new(2)), 1.);
+///
+/// let owned: Vec<String> = "the quick red fox".split(' ').map(String::from).collect();
+/// assert_eq!(word_distance(&a, &owned, Levenshtein::default()), DistanceValue::Exact(1));
+/// assert_eq!(word_distance::<&str, _, _>(&[], &a, Levenshtein::default()), DistanceValue::Exact(4));
+/// ```
+pub fn word_distance<A, B, D>(a: &[A], b: &[B], dist: D) -> <D as DistanceMetric>::Dist
+where
+    A: AsRef<str>,
+    B: AsRef<str>,
+    D: DistanceMetric,
+{
+    dist.distance(
+        a.iter().map(AsRef::<str>::as_ref),
+        b.iter().map(AsRef::<str>::as_ref),
+    )
+}
+
+/// Evaluates the distance between two byte slices based on the provided
+/// [`crate::DistanceMetric`], where every byte is treated as an element.
+///
+/// This operates on bytes, not on chars: a char that is encoded as several
+/// bytes in UTF-8 counts as several elements, so e.g. [`Levenshtein`] counts
+/// the edited bytes instead of the edited chars. Use [`str_distance`] to
+/// compare str by their chars.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{bytes_distance, str_distance, DistanceValue, Levenshtein};
+/// assert_eq!(bytes_distance(b"kitten", b"sitting", Levenshtein::default()), DistanceValue::Exact(3));
+/// // 'é' is encoded as 2 bytes, that both differ from 'e'
+/// assert_eq!(bytes_distance("café".as_bytes(), "cafe".as_bytes(), Levenshtein::default()), DistanceValue::Exact(2));
+/// assert_eq!(str_distance("café", "cafe", Levenshtein::default()), DistanceValue::Exact(1));
+/// ```
+pub fn bytes_distance<D>(a: &[u8], b: &[u8], dist: D) -> <D as DistanceMetric>::Dist
+where
+    D: DistanceMetric,
+{
+    dist.distance(a, b)
+}
+
+/// Evaluates the normalized distance between two byte slices based on the
+/// provided [`crate::DistanceMetric`], so that it returns always a f64
+/// between 0 and 1.
+///
+/// Like [`bytes_distance`] this operates on bytes, not on chars, so the
+/// distance is normalized by the number of bytes instead of the number of
+/// chars.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{bytes_distance_normalized, str_distance_normalized, Levenshtein};
+/// assert_eq!(bytes_distance_normalized(b"ab", b"ac", Levenshtein::default()), 0.5);
+/// assert_eq!(bytes_distance_normalized("é".as_bytes(), "e".as_bytes(), Levenshtein::default()), 1.);
+/// assert_eq!(bytes_distance_normalized("☃".as_bytes(), "☂".as_bytes(), Levenshtein::default()), 1. / 3.);
+/// assert_eq!(str_distance_normalized("☃", "☂", Levenshtein::default()), 1.);
+/// ```
+pub fn bytes_distance_normalized<D>(a: &[u8], b: &[u8], dist: D) -> f64
+where
+    D: DistanceMetric,
+{
+    dist.normalized(a, b)
+}
+
 /// Evaluates the normalized distance between two strings based on the provided
 /// [`crate::DistanceMetric`], so that it returns always a f64 between 0 and 1.
 /// A value of '0.0' corresponds to the "zero distance", both strings are
@@ -137,6 +304,454 @@ where
     dist.str_normalized(a, b)
 }
 
+/// Evaluates the normalized distance between two strings based on the provided
+/// [`crate::DistanceMetric`] like [`str_distance_normalized`], but applies the
+/// `empty` policy if at least one of the strings is empty.
+///
+/// This is useful to deduplicate records with missing fields, which should
+/// not be considered equal just because both are missing.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{str_distance_normalized_with, EmptyPolicy, Levenshtein};
+/// assert_eq!(str_distance_normalized_with("", "", Levenshtein::default(), EmptyPolicy::Equal), 0.);
+/// assert_eq!(str_distance_normalized_with("", "", Levenshtein::default(), EmptyPolicy::MaxDistance), 1.);
+/// assert_eq!(str_distance_normalized_with("", "abc", Levenshtein::default(), EmptyPolicy::Custom(0.5)), 0.5);
+/// assert_eq!(str_distance_normalized_with("ab", "ac", Levenshtein::default(), EmptyPolicy::MaxDistance), 0.5);
+/// ```
+pub fn str_distance_normalized_with<S, T, D>(a: S, b: T, dist: D, empty: EmptyPolicy) -> f64
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+    D: DistanceMetric,
+{
+    let (a, b) = (a.as_ref(), b.as_ref());
+    if !a.is_empty() && !b.is_empty() {
+        return dist.str_normalized(a, b);
+    }
+    match empty {
+        EmptyPolicy::Equal => dist.str_normalized(a, b),
+        EmptyPolicy::MaxDistance => 1.,
+        EmptyPolicy::Custom(value) => value,
+    }
+}
+
+/// How [`str_distance_normalized_with`] treats empty strings, i.e. if both or
+/// one of the strings is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum EmptyPolicy {
+    /// The normalized distance of the metric, two empty strings are equal
+    /// with a distance of `0.`, like in [`str_distance_normalized`].
+    #[default]
+    Equal,
+    /// Empty strings are maximally dissimilar from all strings, including
+    /// other empty strings, with a distance of `1.`.
+    MaxDistance,
+    /// Empty strings have the given distance to all strings, e.g. `0.5` to
+    /// treat missing values as unknown.
+    Custom(f64),
+}
+
+/// Evaluates the normalized similarity between two strings based on the
+/// provided [`crate::DistanceMetric`], which is `1.0 -
+/// str_distance_normalized(a, b, dist)`.
+///
+/// A value of '1.0' corresponds to two strings that are considered equal by
+/// means of the metric, whereas a value of '0.0' corresponds to the maximum
+/// distance that can exist between the strings.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{str_similarity_normalized, Levenshtein, SorensenDice};
+/// assert_eq!(str_similarity_normalized("nacht", "nacht", Levenshtein::default()), 1.0);
+/// assert_eq!(str_similarity_normalized("ab", "ac", Levenshtein::default()), 0.5);
+/// assert_eq!(str_similarity_normalized("nacht", "night", SorensenDice::default()), 0.25);
+/// ```
+pub fn str_similarity_normalized<S, T, D>(a: S, b: T, dist: D) -> f64
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+    D: DistanceMetric,
+{
+    dist.str_similarity(a, b)
+}
+
+/// Evaluates the normalized distance between two strings based on the provided
+/// [`crate::DistanceMetric`], if it does not exceed the `threshold`.
+///
+/// Returns `None` if the normalized distance is above the `threshold`, see
+/// [`DistanceMetric::str_distance_within`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{str_distance_within, Levenshtein};
+/// assert_eq!(str_distance_within("kitten", "mitten", Levenshtein::default(), 0.2), Some(1. / 6.));
+/// assert_eq!(str_distance_within("kitten", "sitting", Levenshtein::default(), 0.2), None);
+/// ```
+pub fn str_distance_within<S, T, D>(a: S, b: T, dist: D, threshold: f64) -> Option<f64>
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+    D: DistanceMetric,
+{
+    dist.str_distance_within(a, b, threshold)
+}
+
+/// Evaluates both the distance and the normalized distance between two
+/// strings based on the provided [`crate::DistanceMetric`], see
+/// [`DistanceMetric::str_distance_both`].
+///
+/// This is equal to `(str_distance(a, b, dist), str_distance_normalized(a, b,
+/// dist))`, but e.g. [`Levenshtein`] and the q-gram metrics are evaluated
+/// only once.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{str_distance_both, DistanceValue, Levenshtein, QGram};
+/// assert_eq!(
+///     str_distance_both("kitten", "sitting", Levenshtein::default()),
+///     (DistanceValue::Exact(3), 3. / 7.)
+/// );
+/// assert_eq!(str_distance_both("abcd", "abce", QGram::new(2)), (2, 2. / 6.));
+/// ```
+pub fn str_distance_both<S, T, D>(a: S, b: T, dist: D) -> (<D as DistanceMetric>::Dist, f64)
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+    D: DistanceMetric,
+{
+    dist.str_distance_both(a, b)
+}
+
+/// Evaluates the similarity between two strings based on the provided
+/// [`crate::DistanceMetric`] as an integer percentage between 0 and 100, see
+/// [`DistanceMetric::score_100`].
+///
+/// The score is `(1 - normalized) * 100`, rounded half-up to the nearest
+/// integer. With [`crate::RatcliffObershelp`] this is fuzzywuzzy's `ratio`,
+/// except for scores that end in exactly .5, which fuzzywuzzy rounds half to
+/// even.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{str_score_100, RatcliffObershelp};
+/// assert_eq!(str_score_100("this is a test", "this is a test!", RatcliffObershelp), 97);
+/// ```
+#[doc(alias = "percent_similar")]
+pub fn str_score_100<S, T, D>(a: S, b: T, dist: D) -> u8
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+    D: DistanceMetric,
+{
+    dist.score_100(a, b)
+}
+
+/// Returns the number of leading elements both inputs have in common.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::common_prefix_len;
+/// assert_eq!(common_prefix_len("kitten".chars(), "kitchen".chars()), 3);
+/// assert_eq!(common_prefix_len("☃☃a".chars(), "☃☃b".chars()), 2);
+/// assert_eq!(common_prefix_len(&[1, 2, 3], &[1, 2]), 2);
+/// ```
+pub fn common_prefix_len<S, T>(a: S, b: T) -> usize
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+{
+    count_eq(a.into_iter(), b.into_iter())
+}
+
+/// Returns the number of trailing elements both inputs have in common.
+///
+/// The common prefix and suffix may overlap, e.g. `"aa"` and `"a"` have a
+/// common prefix and suffix of length 1 each.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::common_suffix_len;
+/// assert_eq!(common_suffix_len("kitten".chars(), "mitten".chars()), 5);
+/// assert_eq!(common_suffix_len("a☃☃".chars(), "b☃☃".chars()), 2);
+/// ```
+pub fn common_suffix_len<S, T>(a: S, b: T) -> usize
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::IntoIter: DoubleEndedIterator,
+    <T as IntoIterator>::IntoIter: DoubleEndedIterator,
+    <S as IntoIterator>::Item: PartialEq<<T as IntoIterator>::Item>,
+{
+    count_eq(a.into_iter().rev(), b.into_iter().rev())
+}
+
+/// Strips the common prefix and suffix of both str and returns the remaining
+/// middle parts together with the number of chars of the common prefix and of
+/// the common suffix.
+///
+/// The common suffix is stripped first, so that the prefix and suffix never
+/// overlap, e.g. `"aa"` and `"a"` have a common suffix of 1 char and no
+/// common prefix. The middle parts are what actually differs between the str,
+/// e.g. to highlight it in a diff.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::distinct_middle;
+/// assert_eq!(distinct_middle("kitten", "sitting"), ("kitten", "sitting", 0, 0));
+/// assert_eq!(distinct_middle("kitten", "kitchen"), ("t", "ch", 3, 2));
+/// ```
+pub fn distinct_middle<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str, usize, usize) {
+    let delim = DelimDistinct::new_skip_take(a.chars(), b.chars());
+    let (prefix_len, suffix_len) = (delim.prefix_len, delim.suffix_len);
+    let middle = |s: &'a str| {
+        let start = s
+            .char_indices()
+            .nth(prefix_len)
+            .map_or(s.len(), |(idx, _)| idx);
+        let end = match suffix_len {
+            0 => s.len(),
+            n => s.char_indices().rev().nth(n - 1).map_or(0, |(idx, _)| idx),
+        };
+        &s[start..end]
+    };
+    (middle(a), middle(b), prefix_len, suffix_len)
+}
+
+/// Returns the candidate with the minimum distance to the `query` together
+/// with its distance, or `None` if there are no candidates.
+///
+/// If multiple candidates share the minimum distance, the first one in the
+/// input order is returned, so the result is deterministic. Distances that
+/// are not comparable to themselves, e.g. `f64::NAN`, are considered greater
+/// than all other distances.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{find_best_match, DistanceValue, Levenshtein};
+/// let candidates = vec!["sitting", "kitchen", "mitten"];
+/// assert_eq!(
+///     find_best_match("kitten", candidates, &Levenshtein::default()),
+///     Some(("mitten", DistanceValue::Exact(1)))
+/// );
+/// ```
+pub fn find_best_match<'a, D, I>(
+    query: &str,
+    candidates: I,
+    dist: &D,
+) -> Option<(&'a str, <D as DistanceMetric>::Dist)>
+where
+    D: DistanceMetric,
+    I: IntoIterator<Item = &'a str>,
+{
+    min_by_score(candidates, |candidate| dist.str_distance(query, candidate))
+}
+
+/// Returns the candidate with the minimum normalized distance to the `query`
+/// together with its normalized distance, or `None` if there are no
+/// candidates.
+///
+/// If multiple candidates share the minimum distance, the first one in the
+/// input order is returned, so the result is deterministic. Distances that
+/// are not comparable to themselves, e.g. `f64::NAN`, are considered greater
+/// than all other distances.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{find_best_match_normalized, Levenshtein};
+/// let candidates = vec!["sitting", "kitchen", "kitten"];
+/// assert_eq!(
+///     find_best_match_normalized("kitten", candidates, &Levenshtein::default()),
+///     Some(("kitten", 0.))
+/// );
+/// ```
+pub fn find_best_match_normalized<'a, D, I>(
+    query: &str,
+    candidates: I,
+    dist: &D,
+) -> Option<(&'a str, f64)>
+where
+    D: DistanceMetric,
+    I: IntoIterator<Item = &'a str>,
+{
+    min_by_score(candidates, |candidate| {
+        dist.str_normalized(query, candidate)
+    })
+}
+
+/// Returns an iterator over the candidates whose normalized distance to the
+/// `query` is at most the `threshold`, together with their normalized
+/// distance, see [`DistanceMetric::str_distance_within`].
+///
+/// The candidates are evaluated lazily while iterating, so this works over
+/// streams of candidates that don't fit into memory.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{filter_within, Levenshtein};
+/// let candidates = vec!["sitting", "kitchen", "mitten", "bitten"];
+/// let lev = Levenshtein::default();
+/// let matches: Vec<_> = filter_within("kitten", candidates, &lev, 0.2).collect();
+/// assert_eq!(matches, vec![("mitten", 1. / 6.), ("bitten", 1. / 6.)]);
+/// ```
+pub fn filter_within<'a, D, I>(
+    query: &'a str,
+    candidates: I,
+    dist: &'a D,
+    threshold: f64,
+) -> impl Iterator<Item = (&'a str, f64)> + 'a
+where
+    D: DistanceMetric,
+    I: IntoIterator<Item = &'a str>,
+    <I as IntoIterator>::IntoIter: 'a,
+{
+    candidates.into_iter().filter_map(move |candidate| {
+        dist.str_distance_within(query, candidate, threshold)
+            .map(|dist| (candidate, dist))
+    })
+}
+
+/// Returns the `k` candidates with the smallest distance to the `query`
+/// together with their distance, sorted ascending by distance.
+///
+/// Only the `k` best candidates are kept in memory at any time. Candidates
+/// with equal distance are returned in their input order, also when only
+/// some of them are among the `k` best, so the result is deterministic.
+/// Distances that are not comparable to themselves, e.g. `f64::NAN`, are
+/// considered greater than all other distances. If there are less than `k`
+/// candidates, all of them are returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{top_k_matches, DistanceValue, Levenshtein};
+/// let candidates = vec!["sitting", "kitchen", "mitten", "bitten"];
+/// assert_eq!(
+///     top_k_matches("kitten", candidates, 2, &Levenshtein::default()),
+///     vec![
+///         ("mitten".to_string(), DistanceValue::Exact(1)),
+///         ("bitten".to_string(), DistanceValue::Exact(1))
+///     ]
+/// );
+/// ```
+pub fn top_k_matches<D, I>(
+    query: &str,
+    candidates: I,
+    k: usize,
+    dist: &D,
+) -> Vec<(String, <D as DistanceMetric>::Dist)>
+where
+    D: DistanceMetric,
+    I: IntoIterator,
+    <I as IntoIterator>::Item: AsRef<str>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+    // max heap that has the worst of the k best candidates on top
+    let mut heap = BinaryHeap::with_capacity(k);
+    for (idx, candidate) in candidates.into_iter().enumerate() {
+        let score = dist.str_distance(query, candidate.as_ref());
+        if heap.len() < k {
+            heap.push(Ranked {
+                score,
+                idx,
+                item: candidate.as_ref().to_string(),
+            });
+        } else if let Some(mut worst) = heap.peek_mut() {
+            if cmp_score(&score, &worst.score) == Ordering::Less {
+                *worst = Ranked {
+                    score,
+                    idx,
+                    item: candidate.as_ref().to_string(),
+                };
+            }
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|ranked| (ranked.item, ranked.score))
+        .collect()
+}
+
+/// An item ranked by its score, ties are ordered by the index of the item.
+struct Ranked<V> {
+    score: V,
+    idx: usize,
+    item: String,
+}
+
+impl<V: PartialOrd> Ord for Ranked<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_score(&self.score, &other.score).then(self.idx.cmp(&other.idx))
+    }
+}
+
+impl<V: PartialOrd> PartialOrd for Ranked<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: PartialOrd> PartialEq for Ranked<V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: PartialOrd> Eq for Ranked<V> {}
+
+/// Returns the first item with the minimum score, scores are compared via
+/// [`cmp_score`].
+fn min_by_score<'a, I, F, V>(items: I, score: F) -> Option<(&'a str, V)>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: Fn(&str) -> V,
+    V: PartialOrd,
+{
+    let mut best: Option<(&'a str, V)> = None;
+    for item in items {
+        let item_score = score(item);
+        match best {
+            Some((_, ref best_score)) if cmp_score(&item_score, best_score) != Ordering::Less => {}
+            _ => best = Some((item, item_score)),
+        }
+    }
+    best
+}
+
+/// A metric that evaluates the distance between two inputs.
+///
+/// # Symmetry
+///
+/// The metrics of this crate are symmetric, i.e. `dist.distance(a, b)`
+/// equals `dist.distance(b, a)`, and the same holds for the normalized and
+/// the `str` variants. The exceptions are the approximations [`Sift4`] and
+/// [`Sift`], whose greedy scan depends on the order of the inputs, and
+/// metrics that are configured to weigh both inputs differently:
+///
+///  - [`Tversky`] with `alpha != beta`.
+///  - [`MongeElkan::new`], use [`MongeElkan::symmetric`] instead.
+///  - [`ProbabilisticLevenshtein`] with a substitution matrix that is not
+///    symmetric.
+///  - [`CostFnLevenshtein`] with a cost function that is not symmetric, i.e.
+///    `cost(c1, c2) != cost(c2, c1)`.
+///  - [`TokenSet::with_combiner`] with [`token::Combiner::Weighted`], if the
+///    weights of the intersection to the first and to the second str differ.
+///  - [`SoftTfIdf`], since only the words of the first str are matched with
+///    their most similar word of the second str.
 pub trait DistanceMetric {
     /// Represents the data type in which this distance is evaluated.
     type Dist: PartialOrd;
@@ -181,6 +796,180 @@ pub trait DistanceMetric {
     {
         self.normalized(a.as_ref().chars(), b.as_ref().chars())
     }
+
+    /// Evaluates the normalized similarity, i.e. `1.0 - normalized`.
+    ///
+    /// A value of '1.0' corresponds to two inputs that are considered equal
+    /// by means of the metric, whereas a value of '0.0' corresponds to the
+    /// maximum distance that can exist between the inputs. Count based metrics
+    /// like [`Levenshtein`] are normalized first.
+    fn similarity<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        1. - self.normalized(a, b)
+    }
+
+    /// Convenience similarity for str types, i.e. `1.0 - str_normalized`.
+    fn str_similarity<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        1. - self.str_normalized(a, b)
+    }
+
+    /// Evaluates the normalized distance between two strings, if it does not
+    /// exceed the `threshold`.
+    ///
+    /// Returns `None` if the normalized distance is above the `threshold`.
+    /// Metrics may stop the evaluation early, as soon as the distance is
+    /// guaranteed to exceed the `threshold`.
+    fn str_distance_within<S, T>(&self, a: S, b: T, threshold: f64) -> Option<f64>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let dist = self.str_normalized(a, b);
+        if dist <= threshold {
+            Some(dist)
+        } else {
+            None
+        }
+    }
+
+    /// Evaluates both the distance and the normalized distance between two
+    /// strings, i.e. `(self.str_distance(a, b), self.str_normalized(a, b))`.
+    ///
+    /// By default this evaluates the metric twice. Metrics whose normalized
+    /// distance is derived from the distance and the lengths of the strings,
+    /// e.g. [`Levenshtein`] and the q-gram metrics like [`QGram`] or
+    /// [`Jaccard`], evaluate it only once.
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        (self.str_distance(a, b), self.str_normalized(a, b))
+    }
+
+    /// Evaluates the similarity between two strings as an integer percentage
+    /// between 0 and 100, following the convention of fuzzywuzzy's `ratio`.
+    ///
+    /// The score is `(1 - normalized) * 100`, rounded half-up to the nearest
+    /// integer and clamped to `0..=100`, e.g. to show a "87% match". Count
+    /// based metrics like [`Levenshtein`] are normalized first, so this works
+    /// uniformly for all metrics.
+    ///
+    /// Unlike fuzzywuzzy, which uses Python's `round`, a score of exactly
+    /// 62.5 is rounded up to 63 instead of to the even 62.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro, Levenshtein};
+    /// assert_eq!(Levenshtein::default().score_100("kitten", "sitting"), 57);
+    /// assert_eq!(Jaro.score_100("martha", "marhta"), 94);
+    /// // a normalized distance of 3 / 8 is a score of 62.5
+    /// assert_eq!(Levenshtein::default().score_100("abcdefgh", "abcdexyz"), 63);
+    /// ```
+    fn score_100<S, T>(&self, a: S, b: T) -> u8
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let score = utils::round(self.str_similarity(a, b) * 100.);
+        score.clamp(0., 100.) as u8
+    }
+
+    /// Same as [`DistanceMetric::score_100`].
+    fn percent_similar<S, T>(&self, a: S, b: T) -> u8
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.score_100(a, b)
+    }
+
+    /// Wraps the metric in a [`Winkler`] with the default [`WinklerConfig`].
+    ///
+    /// Like the other adapters this mirrors the iterator adapters, so that
+    /// nested wrappers read in the order they are applied. Since the
+    /// preprocessing of [`normalize::Normalized`] only applies to str, it
+    /// should be the outermost wrapper.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro, TokenSort, Winkler};
+    /// // same as `Normalized::new(TokenSort::new(Winkler::new(Jaro)))` that only lowercases
+    /// let dist = Jaro.winkler().token_sort().case_insensitive();
+    /// assert_eq!(dist.str_distance("Martha Smith", "SMITH MARTHA"), 0.);
+    /// assert_eq!(
+    ///     dist.str_distance("Martha Smith", "SMITH MARHTA"),
+    ///     TokenSort::new(Winkler::new(Jaro)).str_distance("martha smith", "smith marhta")
+    /// );
+    /// ```
+    fn winkler(self) -> Winkler<Self>
+    where
+        Self: Sized,
+    {
+        Winkler::new(self)
+    }
+
+    /// Wraps the metric in a [`TokenSet`].
+    fn token_set(self) -> TokenSet<Self>
+    where
+        Self: Sized,
+    {
+        TokenSet::new(self)
+    }
+
+    /// Wraps the metric in a [`TokenSort`].
+    fn token_sort(self) -> TokenSort<Self>
+    where
+        Self: Sized,
+    {
+        TokenSort::new(self)
+    }
+
+    /// Wraps the metric in a [`Partial`].
+    fn partial(self) -> Partial<Self>
+    where
+        Self: Sized,
+    {
+        Partial::new(self)
+    }
+
+    /// Wraps the metric in a [`normalize::Normalized`] that only lowercases
+    /// the str.
+    fn case_insensitive(self) -> normalize::Normalized<Self>
+    where
+        Self: Sized,
+    {
+        let lowercase = normalize::Normalizer::new()
+            .collapse_whitespace(false)
+            .trim(false);
+        normalize::Normalized::with_normalizer(self, lowercase)
+    }
+
+    /// Wraps the metric in a [`normalize::Normalized`] that preprocesses the
+    /// str with the `normalizer`.
+    fn normalized_preprocessed(
+        self,
+        normalizer: normalize::Normalizer,
+    ) -> normalize::Normalized<Self>
+    where
+        Self: Sized,
+    {
+        normalize::Normalized::with_normalizer(self, normalizer)
+    }
 }
 
 /// Convenience trait to use a distance on a type directly.
@@ -201,15 +990,136 @@ impl<T: AsRef<str>> DistanceElement for T {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd)]
+/// An object safe companion of [`DistanceMetric`] for `str`s, that allows
+/// choosing metrics at runtime via `Box<dyn StrMetric>`.
+///
+/// Implemented for every [`DistanceMetric`] whose distance converts into an
+/// `f64` via [`ToF64`], including the count based metrics like [`QGram`] and
+/// [`Indel`].
+///
+/// The methods are suffixed with `_f64`, so that they don't collide with the
+/// methods of [`DistanceMetric`] if both traits are in scope.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{Jaro, Levenshtein, StrMetric};
+/// let metrics: Vec<Box<dyn StrMetric>> = vec![Box::new(Levenshtein::default()), Box::new(Jaro)];
+/// let dists: Vec<_> = metrics.iter().map(|m| m.str_distance_f64("kitten", "sitting")).collect();
+/// assert_eq!(dists[0], 3.);
+/// ```
+pub trait StrMetric {
+    /// Evaluates the distance between two str, see
+    /// [`DistanceMetric::str_distance`].
+    fn str_distance_f64(&self, a: &str, b: &str) -> f64;
+
+    /// Evaluates the normalized distance between two str, see
+    /// [`DistanceMetric::str_normalized`].
+    fn str_normalized_f64(&self, a: &str, b: &str) -> f64;
+}
+
+impl<D> StrMetric for D
+where
+    D: DistanceMetric,
+    <D as DistanceMetric>::Dist: ToF64,
+{
+    fn str_distance_f64(&self, a: &str, b: &str) -> f64 {
+        self.str_distance(a, b).to_f64()
+    }
+
+    fn str_normalized_f64(&self, a: &str, b: &str) -> f64 {
+        self.str_normalized(a, b)
+    }
+}
+
+/// Converts the distance of a [`DistanceMetric`] into an `f64`, see
+/// [`StrMetric`].
+///
+/// Unlike `Into<f64>` this is implemented for `usize`, the distance of the
+/// count based metrics, which may lose precision for distances above `2^53`.
+pub trait ToF64 {
+    /// Converts the distance into an `f64`.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($ty:ty),*) => {
+        $(
+            impl ToF64 for $ty {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(f64, f32, usize, u8, u16, u32, i8, i16, i32);
+
+impl ToF64 for DistanceValue {
+    fn to_f64(self) -> f64 {
+        self.into()
+    }
+}
+
+/// The distance of a metric with an optional maximum distance, e.g.
+/// [`Levenshtein::with_max_distance`].
+///
+/// Equality and ordering take the variant into account: `Exceeded(10)` is not
+/// equal to `Exact(10)`, since the exceeded distance is greater than 10. Use
+/// [`DistanceValue::value`] or the [`Deref`] to `usize` to compare the numeric
+/// values only.
+///
+/// Every [`DistanceValue::Exact`] is less than every
+/// [`DistanceValue::Exceeded`], values of the same variant are ordered by
+/// their numeric value. For the distances of the same metric this is the
+/// order of the actual distances.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::DistanceValue;
+/// assert_ne!(DistanceValue::Exact(10), DistanceValue::Exceeded(10));
+/// assert_eq!(DistanceValue::Exact(10).value(), DistanceValue::Exceeded(10).value());
+/// assert_eq!(
+///     std::cmp::min(DistanceValue::Exceeded(2), DistanceValue::Exact(2)),
+///     DistanceValue::Exact(2)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum DistanceValue {
+    /// The exact distance, at most the maximum distance.
     Exact(usize),
+    /// The distance exceeds the maximum distance, which is the value.
     Exceeded(usize),
 }
 
-impl Into<usize> for DistanceValue {
-    fn into(self) -> usize {
-        *self
+impl DistanceValue {
+    /// The numeric value, the exact distance or the exceeded maximum distance.
+    pub fn value(&self) -> usize {
+        **self
+    }
+
+    /// Whether this is the exact distance.
+    pub fn is_exact(&self) -> bool {
+        matches!(self, DistanceValue::Exact(_))
+    }
+
+    /// Whether the distance exceeds the maximum distance.
+    pub fn is_exceeded(&self) -> bool {
+        matches!(self, DistanceValue::Exceeded(_))
+    }
+}
+
+impl From<DistanceValue> for usize {
+    fn from(val: DistanceValue) -> Self {
+        *val
+    }
+}
+
+impl From<DistanceValue> for f64 {
+    fn from(val: DistanceValue) -> Self {
+        *val as f64
     }
 }
 
@@ -222,3 +1132,714 @@ impl Deref for DistanceValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_100() {
+        assert_eq!(
+            str_score_100("this is a test", "this is a test!", RatcliffObershelp),
+            97
+        );
+        assert_eq!(
+            str_score_100(
+                "fuzzy wuzzy was a bear",
+                "wuzzy fuzzy was a bear",
+                RatcliffObershelp
+            ),
+            91
+        );
+        assert_eq!(
+            str_score_100("New York Mets", "New York Meats", RatcliffObershelp),
+            96
+        );
+        assert_eq!(str_score_100("", "", RatcliffObershelp), 100);
+        assert_eq!(str_score_100("abc", "xyz", RatcliffObershelp), 0);
+        assert_eq!(Levenshtein::default().score_100("ab", "ac"), 50);
+        // halves are rounded up, fuzzywuzzy rounds 62.5 down to the even 62
+        assert_eq!(Levenshtein::default().score_100("abcdefgh", "abcdefgx"), 88);
+        assert_eq!(Levenshtein::default().score_100("abcdefgh", "abcdexyz"), 63);
+        assert_eq!(Levenshtein::default().score_100("kitten", "sitting"), 57);
+        assert_eq!(
+            Levenshtein::with_max_distance(1).score_100("kitten", "sitting"),
+            0
+        );
+        assert_eq!(Jaro.score_100("martha", "marhta"), 94);
+        assert_eq!(JaroWinkler::default().score_100("martha", "marhta"), 96);
+        assert_eq!(SorensenDice::new(2).score_100("nacht", "night"), 25);
+        assert_eq!(Jaccard::new(2).score_100("nacht", "night"), 14);
+        assert_eq!(Cosine::new(2).score_100("abc", "abc"), 100);
+        assert_eq!(Indel.score_100("kitten", "sitting"), 62);
+        assert_eq!(Jaro.percent_similar("martha", "marhta"), 94);
+    }
+
+    /// Asserts that the similarity and the distance of `dist` add up to 1.
+    fn assert_complementary<D: DistanceMetric<Dist = f64>>(dist: D) {
+        for (a, b) in [
+            ("", ""),
+            ("kitten", "sitting"),
+            ("nacht", "night"),
+            ("abc", "xyz"),
+        ]
+        .iter()
+        {
+            let (similarity, distance) = (dist.str_similarity(a, b), dist.str_distance(a, b));
+            if distance.is_nan() {
+                assert!(similarity.is_nan());
+            } else {
+                assert!((similarity + distance - 1.).abs() < 1e-12, "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn similarity() {
+        assert_complementary(Jaro);
+        assert_complementary(JaroWinkler::default());
+        assert_complementary(Cosine::new(2));
+        assert_complementary(Jaccard::new(2));
+        assert_complementary(SorensenDice::new(2));
+        assert_complementary(RatcliffObershelp);
+
+        // count based metrics are normalized
+        for (a, b) in [("", ""), ("kitten", "sitting"), ("abc", "xyz")].iter() {
+            let lev = Levenshtein::default();
+            assert_eq!(lev.str_similarity(a, b) + lev.str_normalized(a, b), 1.);
+        }
+        assert_eq!(
+            str_similarity_normalized("kitten", "sitting", Levenshtein::default()),
+            1. - 3. / 7.
+        );
+        assert_eq!(
+            Levenshtein::default().similarity(&[1, 2, 3], &[1, 2, 4]),
+            1. - 1. / 3.
+        );
+        assert_eq!(
+            str_similarity_normalized("", "", Levenshtein::default()),
+            1.
+        );
+    }
+
+    #[test]
+    fn adapters() {
+        let words = [
+            "",
+            "Martha",
+            "MARHTA",
+            "New York Mets",
+            "  new york  METS",
+            "Mets New York",
+        ];
+        let composed = Jaro.winkler().token_sort().case_insensitive();
+        let nested = normalize::Normalized::with_normalizer(
+            TokenSort::new(Winkler::new(Jaro)),
+            normalize::Normalizer::new()
+                .collapse_whitespace(false)
+                .trim(false),
+        );
+        let partial = Levenshtein::default()
+            .partial()
+            .token_set()
+            .normalized_preprocessed(normalize::Normalizer::new().strip_punctuation(true));
+        let partial_nested = normalize::Normalized::with_normalizer(
+            TokenSet::new(Partial::new(Levenshtein::default())),
+            normalize::Normalizer::new().strip_punctuation(true),
+        );
+        for a in words.iter() {
+            for b in words.iter() {
+                assert_eq!(composed.str_distance(a, b), nested.str_distance(a, b));
+                assert_eq!(
+                    partial.str_distance(a, b),
+                    partial_nested.str_distance(a, b)
+                );
+            }
+        }
+        assert_eq!(composed.str_distance("Martha", "martha"), 0.);
+        assert_eq!(partial.str_distance("New York", "new york, mets!"), 0.);
+    }
+
+    #[test]
+    fn word_level_distance() {
+        let a: Vec<&str> = "the quick brown fox jumps".split(' ').collect();
+
+        // swapping two adjacent words
+        let b: Vec<&str> = "the brown quick fox jumps".split(' ').collect();
+        assert_eq!(
+            word_distance(&a, &b, Levenshtein::default()),
+            DistanceValue::Exact(2)
+        );
+        assert_eq!(
+            word_distance(&a, &b, DamerauLevenshtein::default()),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(word_distance(&a, &b, QGram::new(1)), 0);
+        // 3 of the 4 shingles of each list differ
+        assert_eq!(word_distance(&a, &b, QGram::new(2)), 6);
+
+        // moving a word to the end is a deletion and an insertion
+        let c: Vec<&str> = "quick brown fox jumps the".split(' ').collect();
+        assert_eq!(
+            word_distance(&a, &c, Levenshtein::default()),
+            DistanceValue::Exact(2)
+        );
+        assert_eq!(
+            word_distance(&a, &c, DamerauLevenshtein::default()),
+            DistanceValue::Exact(2)
+        );
+
+        // a word is only equal to the exact same word
+        let d = ["The", "quick", "brown", "fox", "jumps"];
+        assert_eq!(
+            word_distance(&a, &d, Levenshtein::default()),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(
+            word_distance(&a, &a, Levenshtein::default()),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(
+            word_distance(&a, &[] as &[&str], Levenshtein::default()),
+            DistanceValue::Exact(5)
+        );
+    }
+
+    #[test]
+    fn mixed_item_types() {
+        let borrowed = ["new", "york", "mets"];
+        let owned: Vec<String> = borrowed.iter().map(|s| s.to_string()).collect();
+        let lev = Levenshtein::default();
+        assert_eq!(lev.distance(&borrowed, &owned), DistanceValue::Exact(0));
+        assert_eq!(lev.distance(&owned, &borrowed), DistanceValue::Exact(0));
+        assert_eq!(
+            lev.distance(&borrowed, &owned[1..]),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(lev.normalized(&borrowed[..2], &owned), 1. / 3.);
+        assert_eq!(Jaro.distance(&borrowed, &owned), 0.);
+        assert_eq!(Indel.distance(&borrowed[1..], &owned), 1);
+        assert_eq!(QGram::new(2).distance(&borrowed, &owned), 0);
+        assert_eq!(SetJaccard.distance(&borrowed[..2], &owned[..1]), 0.5);
+        assert_eq!(
+            word_distance(&borrowed, &owned, lev.clone()),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(
+            word_distance(&owned, &["new", "jersey"], lev.clone()),
+            DistanceValue::Exact(2)
+        );
+
+        // owned and borrowed bytes
+        let bytes = [1u8, 2, 3];
+        assert_eq!(
+            lev.distance(bytes, vec![1u8, 2, 3]),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(lev.distance(&bytes, &vec![1u8, 2]), DistanceValue::Exact(1));
+        assert_eq!(
+            lev.distance(&bytes, vec![&1u8, &2]),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(
+            lev.distance(bytes.iter(), [1u8, 3].iter()),
+            DistanceValue::Exact(1)
+        );
+    }
+
+    #[test]
+    fn byte_level_distance() {
+        let (a, b) = ("naïve café", "naive cafe");
+        // every accented char is 2 bytes, that both differ from the plain char
+        assert_eq!(
+            bytes_distance(a.as_bytes(), b.as_bytes(), Levenshtein::default()),
+            DistanceValue::Exact(4)
+        );
+        assert_eq!(
+            str_distance(a, b, Levenshtein::default()),
+            DistanceValue::Exact(2)
+        );
+        assert_eq!(
+            bytes_distance_normalized(a.as_bytes(), b.as_bytes(), Levenshtein::default()),
+            4. / 12.
+        );
+        assert_eq!(
+            str_distance_normalized(a, b, Levenshtein::default()),
+            2. / 10.
+        );
+
+        // ASCII bytes and chars agree
+        assert_eq!(
+            bytes_distance(b"kitten", b"sitting", Levenshtein::default()),
+            str_distance("kitten", "sitting", Levenshtein::default())
+        );
+        assert_eq!(
+            bytes_distance_normalized(b"kitten", b"sitting", Jaro),
+            str_distance_normalized("kitten", "sitting", Jaro)
+        );
+        // binary data, that isn't valid UTF-8
+        assert_eq!(
+            bytes_distance(&[0xff, 0x00, 0xfe], &[0xff, 0xfe], Levenshtein::default()),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(
+            bytes_distance_normalized(b"", b"", Levenshtein::default()),
+            0.
+        );
+    }
+
+    #[test]
+    fn distance_value() {
+        let exact = DistanceValue::Exact(3);
+        let exceeded = DistanceValue::Exceeded(3);
+        assert_eq!(exact.value(), 3);
+        assert_eq!(exceeded.value(), 3);
+        assert!(exact.is_exact() && !exact.is_exceeded());
+        assert!(exceeded.is_exceeded() && !exceeded.is_exact());
+        assert_ne!(exact, exceeded);
+
+        assert!(DistanceValue::Exact(2) < DistanceValue::Exact(3));
+        assert!(DistanceValue::Exact(3) < DistanceValue::Exceeded(3));
+        assert!(DistanceValue::Exceeded(2) < DistanceValue::Exceeded(3));
+        assert_eq!(exact.clone().min(exceeded.clone()), exact);
+        assert_eq!(exact.clone().max(exceeded.clone()), exceeded);
+
+        let lev = Levenshtein::with_max_distance(2);
+        let mut dists: Vec<_> = ["kitten", "sitting", "kitchen", "mitten"]
+            .iter()
+            .map(|s| lev.str_distance("kitten", s))
+            .collect();
+        dists.sort();
+        assert_eq!(
+            dists,
+            vec![
+                DistanceValue::Exact(0),
+                DistanceValue::Exact(1),
+                DistanceValue::Exact(2),
+                DistanceValue::Exceeded(2)
+            ]
+        );
+        assert_eq!(f64::from(DistanceValue::Exceeded(2)), 2.);
+        assert_eq!(usize::from(DistanceValue::Exact(2)), 2);
+    }
+
+    #[test]
+    fn best_match() {
+        let candidates = ["sitting", "kitchen", "mitten", "bitten"];
+        assert_eq!(
+            find_best_match(
+                "kitten",
+                candidates.iter().copied(),
+                &Levenshtein::default()
+            ),
+            Some(("mitten", DistanceValue::Exact(1)))
+        );
+        let (best, score) = find_best_match(
+            "kitten",
+            candidates.iter().copied(),
+            &SorensenDice::default(),
+        )
+        .unwrap();
+        assert_eq!(best, "mitten");
+        assert_eq!(format!("{:.6}", score), "0.200000");
+        assert_eq!(
+            find_best_match("kitten", Vec::new(), &Levenshtein::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn best_match_normalized() {
+        let candidates = vec!["sitting", "kitchen", "mitten"];
+        let (best, score) =
+            find_best_match_normalized("kitten", candidates, &Levenshtein::default()).unwrap();
+        assert_eq!(best, "mitten");
+        assert_eq!(format!("{:.6}", score), "0.166667");
+        assert_eq!(
+            find_best_match_normalized("kitten", Vec::new(), &Levenshtein::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn filter_candidates_within() {
+        let candidates = [
+            "kitten", "sitting", "mitten", "kitchen", "smitten", "bitten", "", "written",
+        ];
+        let lev = Levenshtein::default();
+        let matches: Vec<_> =
+            filter_within("kitten", candidates.iter().copied(), &lev, 0.3).collect();
+        assert_eq!(
+            matches,
+            vec![
+                ("kitten", 0.),
+                ("mitten", 1. / 6.),
+                ("kitchen", 2. / 7.),
+                ("smitten", 2. / 7.),
+                ("bitten", 1. / 6.),
+                ("written", 2. / 7.),
+            ]
+        );
+        for (candidate, dist) in matches {
+            assert_eq!(dist, lev.str_normalized("kitten", candidate));
+        }
+
+        // the candidates are only evaluated while iterating
+        let mut evaluated = 0;
+        let stream = (0..).map(|i| {
+            evaluated += 1;
+            if i % 2 == 0 {
+                "kitten"
+            } else {
+                "dog"
+            }
+        });
+        let first: Vec<_> = filter_within("kitten", stream, &lev, 0.).take(2).collect();
+        assert_eq!(first, vec![("kitten", 0.), ("kitten", 0.)]);
+        assert_eq!(evaluated, 3);
+    }
+
+    #[test]
+    fn distinct_middles() {
+        assert_eq!(
+            distinct_middle("hungry kitten is hungry", "hungry hippo is hungry"),
+            ("kitten", "hippo", 7, 10)
+        );
+        assert_eq!(
+            distinct_middle("hungry hippo is hungry", "hungry kitten is hungry"),
+            ("hippo", "kitten", 7, 10)
+        );
+        assert_eq!(distinct_middle("", ""), ("", "", 0, 0));
+        assert_eq!(distinct_middle("abc", "abc"), ("", "", 0, 3));
+        assert_eq!(distinct_middle("aa", "a"), ("a", "", 0, 1));
+        assert_eq!(distinct_middle("abc", ""), ("abc", "", 0, 0));
+        // the lengths count chars, the middles are sliced at char boundaries
+        assert_eq!(distinct_middle("☃a☃☃", "☃b☃☃"), ("a", "b", 1, 2));
+        assert_eq!(distinct_middle("é☃x", "é☃"), ("x", "", 2, 0));
+    }
+
+    #[test]
+    fn top_k() {
+        let candidates = ["sitting", "bitten", "kitchen", "mitten", "kitten"];
+        assert_eq!(
+            top_k_matches("kitten", candidates.iter(), 2, &Levenshtein::default()),
+            vec![
+                ("kitten".to_string(), DistanceValue::Exact(0)),
+                ("bitten".to_string(), DistanceValue::Exact(1)),
+            ]
+        );
+        assert_eq!(
+            top_k_matches("kitten", candidates.iter(), 3, &Levenshtein::default()),
+            vec![
+                ("kitten".to_string(), DistanceValue::Exact(0)),
+                ("bitten".to_string(), DistanceValue::Exact(1)),
+                ("mitten".to_string(), DistanceValue::Exact(1)),
+            ]
+        );
+        let all = top_k_matches("kitten", candidates.iter(), 10, &Levenshtein::default());
+        assert_eq!(
+            all.iter().map(|(s, _)| s.as_str()).collect::<Vec<_>>(),
+            vec!["kitten", "bitten", "mitten", "kitchen", "sitting"]
+        );
+        assert!(top_k_matches("kitten", candidates.iter(), 0, &Levenshtein::default()).is_empty());
+    }
+
+    #[test]
+    fn distance_both() {
+        /// Asserts that the pair equals the separate evaluations.
+        fn assert_both<D>(dist: D)
+        where
+            D: DistanceMetric,
+            D::Dist: core::fmt::Debug + PartialEq,
+        {
+            let words = [
+                "", "a", "ab", "kitten", "sitting", "saturday", "sunday", "a☃c", "abc",
+            ];
+            for a in words.iter() {
+                for b in words.iter() {
+                    let (raw, normalized) = dist.str_distance_both(a, b);
+                    assert_eq!(raw, dist.str_distance(a, b), "{} {}", a, b);
+                    assert_eq!(normalized, dist.str_normalized(a, b), "{} {}", a, b);
+                }
+            }
+        }
+
+        assert_both(Levenshtein::default());
+        assert_both(Levenshtein::with_max_distance(2));
+        for q in 1..4 {
+            assert_both(QGram::new(q));
+            assert_both(QGram::with_padding(q));
+            assert_both(Cosine::new(q));
+            assert_both(Jaccard::new(q));
+            assert_both(Jaccard::with_padding(q));
+            assert_both(SorensenDice::new(q));
+            assert_both(Overlap::new(q));
+            assert_both(Tversky::new(q, 0.5, 0.25));
+        }
+        // falls back to separate evaluations
+        assert_both(Jaro);
+        assert_both(DamerauLevenshtein::default());
+
+        assert_eq!(
+            str_distance_both("kitten", "sitting", Levenshtein::with_max_distance(2)),
+            (DistanceValue::Exceeded(2), 1.)
+        );
+    }
+
+    #[test]
+    fn empty_policy() {
+        let lev = Levenshtein::default();
+        for (a, b) in [("", ""), ("", "abc"), ("abc", "")].iter() {
+            assert_eq!(
+                str_distance_normalized_with(a, b, lev.clone(), EmptyPolicy::Equal),
+                str_distance_normalized(a, b, lev.clone())
+            );
+            assert_eq!(
+                str_distance_normalized_with(a, b, lev.clone(), EmptyPolicy::MaxDistance),
+                1.
+            );
+            assert_eq!(
+                str_distance_normalized_with(a, b, Jaro, EmptyPolicy::Custom(0.25)),
+                0.25
+            );
+        }
+        assert_eq!(
+            str_distance_normalized_with("", "", lev.clone(), EmptyPolicy::default()),
+            0.
+        );
+        assert_eq!(
+            str_distance_normalized_with("", "abc", lev.clone(), EmptyPolicy::Equal),
+            1.
+        );
+        // non empty strings are not affected
+        for empty in [
+            EmptyPolicy::Equal,
+            EmptyPolicy::MaxDistance,
+            EmptyPolicy::Custom(0.),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                str_distance_normalized_with("kitten", "sitting", lev.clone(), *empty),
+                3. / 7.
+            );
+        }
+    }
+
+    #[test]
+    fn distance_by() {
+        let is_vowel = |c: &char| "aeiou".contains(*c);
+        let vowels_eq = |a: &char, b: &char| a == b || (is_vowel(a) && is_vowel(b));
+        let (a, b) = ("cat".chars(), "cot".chars());
+        let lev = Levenshtein::default();
+        assert_eq!(*lev.distance_by(a.clone(), b.clone(), vowels_eq), 0);
+        assert_eq!(Indel.distance_by(a.clone(), b.clone(), vowels_eq), 0);
+        assert_eq!(
+            RatcliffObershelp.distance_by(a.clone(), b.clone(), vowels_eq),
+            0.
+        );
+        assert_eq!(
+            QGram::new(2).distance_by(a.clone(), b.clone(), vowels_eq),
+            0
+        );
+        assert!(Cosine::new(2).distance_by(a.clone(), b.clone(), vowels_eq) < 1e-12);
+        assert_eq!(
+            Jaccard::new(2).distance_by(a.clone(), b.clone(), vowels_eq),
+            0.
+        );
+        assert_eq!(
+            SorensenDice::new(2).distance_by(a.clone(), b.clone(), vowels_eq),
+            0.
+        );
+        assert_eq!(
+            Overlap::new(2).distance_by(a.clone(), b.clone(), vowels_eq),
+            0.
+        );
+        assert_eq!(Tversky::new(2, 0.5, 0.5).distance_by(a, b, vowels_eq), 0.);
+        assert_eq!(
+            *lev.distance_by("cat".chars(), "cut".chars(), |a, b| a == b),
+            1
+        );
+
+        // the predicate may compare different types
+        let words = ["New", "York"];
+        let lower = ["new".to_string(), "yrok".to_string()];
+        let eq = |a: &&str, b: &String| a.eq_ignore_ascii_case(b);
+        assert_eq!(*lev.distance_by(words, lower.iter().cloned(), eq), 1);
+        assert_eq!(Indel.distance_by(words, lower.iter().cloned(), eq), 2);
+        assert_eq!(
+            RatcliffObershelp.distance_by(words, lower.iter().cloned(), eq),
+            0.5
+        );
+
+        // a plain equality predicate matches the generic distance
+        for (s1, s2) in [("kitten", "sitting"), ("", "abc"), ("abc", "cba")].iter() {
+            let (s1, s2) = (s1.chars(), s2.chars());
+            let eq = |a: &char, b: &char| a == b;
+            assert_eq!(
+                lev.distance_by(s1.clone(), s2.clone(), eq),
+                lev.distance(s1.clone(), s2.clone())
+            );
+            assert_eq!(
+                Indel.distance_by(s1.clone(), s2.clone(), eq),
+                Indel.distance(s1.clone(), s2.clone())
+            );
+            assert_eq!(
+                RatcliffObershelp.distance_by(s1.clone(), s2.clone(), eq),
+                RatcliffObershelp.distance(s1.clone(), s2.clone())
+            );
+            assert_eq!(
+                Jaccard::new(2).distance_by(s1.clone(), s2.clone(), eq),
+                Jaccard::new(2).distance(s1, s2)
+            );
+        }
+    }
+
+    #[test]
+    fn ascii_fast_path() {
+        let long = "abcdefghij".repeat(8);
+        let long_edited = long.replacen("e", "x", 3);
+        let words = [
+            "",
+            "a",
+            "kitten",
+            "sitting",
+            "martha",
+            "marhta",
+            "a☃c",
+            "abc",
+            "héllo",
+            "hello",
+            &long,
+            &long_edited,
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                for lev in [Levenshtein::default(), Levenshtein::with_max_distance(2)].iter() {
+                    assert_eq!(
+                        lev.str_distance(a, b),
+                        lev.distance(a.chars(), b.chars()),
+                        "{} {}",
+                        a,
+                        b
+                    );
+                }
+                let (s1, s2) = utils::order_by_len_asc(a, b);
+                assert_eq!(
+                    Jaro.str_distance(a, b),
+                    Jaro.distance(s1.chars(), s2.chars()),
+                    "{} {}",
+                    a,
+                    b
+                );
+                let jaro = Jaro::with_params(0.5, 0.4);
+                assert_eq!(
+                    jaro.str_distance(a, b),
+                    jaro.distance(s1.chars(), s2.chars()),
+                    "{} {}",
+                    a,
+                    b
+                );
+                for q in 1..4 {
+                    let (a, b) = (a.chars(), b.chars());
+                    let qgram = QGram::with_padding(q);
+                    assert_eq!(
+                        qgram.str_distance(a.as_str(), b.as_str()),
+                        qgram.distance_hashed(a.clone(), b.clone())
+                    );
+                    let jaccard = Jaccard::new(q);
+                    assert_eq!(
+                        jaccard.str_distance(a.as_str(), b.as_str()),
+                        jaccard.distance_hashed(a, b)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ties_keep_input_order() {
+        let lev = Levenshtein::default();
+        for candidates in [["bitten", "mitten"], ["mitten", "bitten"]].iter() {
+            let first = candidates[0];
+            assert_eq!(
+                find_best_match("kitten", candidates.iter().copied(), &lev),
+                Some((first, DistanceValue::Exact(1)))
+            );
+            assert_eq!(
+                find_best_match_normalized("kitten", candidates.iter().copied(), &lev),
+                Some((first, 1. / 6.))
+            );
+            assert_eq!(
+                top_k_matches("kitten", candidates.iter(), 1, &lev),
+                vec![(first.to_string(), DistanceValue::Exact(1))]
+            );
+            assert_eq!(
+                top_k_matches("kitten", candidates.iter(), 2, &lev)
+                    .into_iter()
+                    .map(|(s, _)| s)
+                    .collect::<Vec<_>>(),
+                candidates.to_vec()
+            );
+        }
+
+        // `NaN` never wins over a comparable score
+        let scores = [("a", f64::NAN), ("b", 0.5), ("c", 0.5), ("d", f64::NAN)];
+        let score = |item: &str| scores.iter().find(|(s, _)| *s == item).unwrap().1;
+        let items = scores.iter().map(|(s, _)| *s);
+        assert_eq!(min_by_score(items.clone(), score), Some(("b", 0.5)));
+        let (item, nan) = min_by_score(items.filter(|s| *s != "b" && *s != "c"), score).unwrap();
+        assert_eq!(item, "a");
+        assert!(nan.is_nan());
+    }
+
+    #[test]
+    fn common_prefix_suffix() {
+        assert_eq!(common_prefix_len("".chars(), "".chars()), 0);
+        assert_eq!(common_prefix_len("".chars(), "abc".chars()), 0);
+        assert_eq!(common_prefix_len("abc".chars(), "abc".chars()), 3);
+        assert_eq!(common_prefix_len("xabc".chars(), "abc".chars()), 0);
+        // counted in chars, not bytes
+        assert_eq!(common_prefix_len("häus".chars(), "hände".chars()), 2);
+        assert_eq!(common_prefix_len("häus".bytes(), "hände".bytes()), 3);
+
+        assert_eq!(common_suffix_len("".chars(), "".chars()), 0);
+        assert_eq!(common_suffix_len("abc".chars(), "".chars()), 0);
+        assert_eq!(common_suffix_len("abc".chars(), "abc".chars()), 3);
+        assert_eq!(common_suffix_len("abcx".chars(), "abc".chars()), 0);
+        assert_eq!(common_suffix_len("straße".chars(), "maße".chars()), 3);
+        assert_eq!(common_suffix_len(&[1, 2, 3], &[0, 2, 3]), 2);
+
+        assert_eq!(common_prefix_len("aa".chars(), "a".chars()), 1);
+        assert_eq!(common_suffix_len("aa".chars(), "a".chars()), 1);
+    }
+
+    #[test]
+    fn boxed_str_metrics() {
+        let metrics: Vec<Box<dyn StrMetric>> = vec![
+            Box::new(Levenshtein::with_max_distance(2)),
+            Box::new(Jaro),
+            Box::new(TokenSet::new(RatcliffObershelp)),
+        ];
+        let dists: Vec<_> = metrics
+            .iter()
+            .map(|metric| metric.str_distance_f64("kitten", "sitting"))
+            .collect();
+        assert_eq!(dists[0], 2.);
+        assert_eq!(dists[1], Jaro.str_distance("kitten", "sitting"));
+        assert_eq!(
+            metrics[0].str_normalized_f64("kitten", "sitting"),
+            Levenshtein::with_max_distance(2).str_normalized("kitten", "sitting")
+        );
+        assert_eq!(metrics[2].str_distance_f64("a b", "b a"), 0.);
+
+        // count based metrics with a `usize` distance
+        let metrics: Vec<Box<dyn StrMetric>> = vec![Box::new(QGram::new(2)), Box::new(Indel)];
+        assert_eq!(metrics[0].str_distance_f64("abc", "cab"), 2.);
+        assert_eq!(metrics[1].str_distance_f64("kitten", "sitting"), 5.);
+        assert_eq!(
+            metrics[1].str_normalized_f64("kitten", "sitting"),
+            Indel.str_normalized("kitten", "sitting")
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::levenshtein::MyersPattern;
+use crate::{
+    Cosine, DistanceMetric, DistanceValue, Jaccard, Jaro, Levenshtein, Overlap, QGram,
+    QGramProfile, SorensenDice, Tversky,
+};
+
+/// A [`DistanceMetric`] that can precompute the parts of the evaluation that
+/// only depend on one input, to compare a fixed reference str with many other
+/// str.
+///
+/// Use it via [`Matcher`].
+pub trait Prepare: DistanceMetric {
+    /// The precomputed state of the reference str.
+    type Prepared;
+
+    /// Precomputes the state of the `reference`.
+    fn prepare(&self, reference: &str) -> Self::Prepared;
+
+    /// Evaluates the distance between the reference the `prepared` state was
+    /// created from and the `candidate`.
+    ///
+    /// This is equal to `self.str_distance(reference, candidate)`.
+    fn distance_prepared(&self, prepared: &Self::Prepared, candidate: &str) -> Self::Dist;
+}
+
+/// Compares a fixed reference str with other str, e.g. the items of a stream.
+///
+/// The parts of the evaluation that only depend on the reference are computed
+/// once when the `Matcher` is created:
+///
+///  - [`Levenshtein`] creates the bit vectors of Myers' algorithm, unless
+///    it has a maximum distance and the reference has more than 64 chars.
+///  - The q-gram metrics ([`QGram`], [`Cosine`], [`Jaccard`],
+///    [`SorensenDice`], [`Overlap`] and [`Tversky`]) create the
+///    [`QGramProfile`] of the reference.
+///  - [`Jaro`] collects the chars of the reference.
+///  - [`crate::normalize::Normalized`] preprocesses the reference before
+///    preparing it with the inner metric.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, DistanceValue, Levenshtein};
+/// # use str_distance::matcher::Matcher;
+/// let matcher = Matcher::new(Levenshtein::default(), "kitten");
+/// assert_eq!(matcher.distance_to("sitting"), DistanceValue::Exact(3));
+/// assert_eq!(matcher.distance_to("mitten"), Levenshtein::default().str_distance("kitten", "mitten"));
+/// ```
+pub struct Matcher<D: Prepare> {
+    /// The metric to compare the str with.
+    dist: D,
+    /// The str to compare all other str with.
+    reference: String,
+    /// The precomputed state of the `reference`.
+    prepared: D::Prepared,
+}
+
+impl<D: Prepare> Matcher<D> {
+    /// Creates a new [`Matcher`] that compares `reference` with other str
+    /// using distance `D`.
+    pub fn new(dist: D, reference: impl Into<String>) -> Self {
+        let reference = reference.into();
+        let prepared = dist.prepare(&reference);
+        Self {
+            dist,
+            reference,
+            prepared,
+        }
+    }
+
+    /// Evaluates the distance between the reference and the `candidate`.
+    ///
+    /// This is equal to `dist.str_distance(reference, candidate)`.
+    pub fn distance_to(&self, candidate: &str) -> D::Dist {
+        self.dist.distance_prepared(&self.prepared, candidate)
+    }
+
+    /// The str all other str are compared with.
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// The metric the str are compared with.
+    pub fn metric(&self) -> &D {
+        &self.dist
+    }
+}
+
+/// The precomputed state of the reference of a [`Levenshtein`] metric.
+pub struct LevenshteinReference {
+    /// The reference str.
+    reference: String,
+    /// The bit vectors of the reference, unless it is evaluated within the
+    /// band of the maximum distance.
+    pattern: Option<MyersPattern>,
+}
+
+impl Prepare for Levenshtein {
+    type Prepared = LevenshteinReference;
+
+    fn prepare(&self, reference: &str) -> Self::Prepared {
+        let chars: Vec<_> = reference.chars().collect();
+        let pattern = if self.uses_myers(chars.len()) {
+            Some(MyersPattern::new(&chars))
+        } else {
+            None
+        };
+        LevenshteinReference {
+            reference: reference.to_string(),
+            pattern,
+        }
+    }
+
+    fn distance_prepared(&self, prepared: &Self::Prepared, candidate: &str) -> DistanceValue {
+        match prepared.pattern {
+            Some(ref pattern) => {
+                let candidate: Vec<_> = candidate.chars().collect();
+                self.bounded(pattern.distance(&candidate))
+            }
+            None => self.str_distance(&prepared.reference, candidate),
+        }
+    }
+}
+
+/// Implements [`Prepare`] for q-gram metrics via their `profile` and
+/// `distance_to_profile` functions.
+macro_rules! impl_prepare_qgram {
+    ($($metric:ty),*) => {
+        $(
+            impl Prepare for $metric {
+                type Prepared = QGramProfile;
+
+                fn prepare(&self, reference: &str) -> Self::Prepared {
+                    self.profile(reference)
+                }
+
+                fn distance_prepared(
+                    &self,
+                    prepared: &Self::Prepared,
+                    candidate: &str,
+                ) -> Self::Dist {
+                    self.distance_to_profile(prepared, candidate)
+                }
+            }
+        )*
+    };
+}
+
+impl_prepare_qgram!(QGram, Cosine, Jaccard, SorensenDice, Overlap, Tversky);
+
+impl Prepare for Jaro {
+    type Prepared = (String, Vec<char>);
+
+    fn prepare(&self, reference: &str) -> Self::Prepared {
+        (reference.to_string(), reference.chars().collect())
+    }
+
+    fn distance_prepared(&self, (reference, chars): &Self::Prepared, candidate: &str) -> f64 {
+        let candidate_chars: Vec<_> = candidate.chars().collect();
+        // same order as `Jaro::str_distance`, the shorter str first
+        if reference.len() <= candidate.len() {
+            self.distance_slice(chars, &candidate_chars)
+        } else {
+            self.distance_slice(&candidate_chars, chars)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that the [`Matcher`] of every word agrees with the metric
+    /// created by `new_dist`.
+    fn assert_matches<D>(new_dist: impl Fn() -> D)
+    where
+        D: Prepare,
+        D::Dist: std::fmt::Debug,
+    {
+        let dist = new_dist();
+        let long = "a".repeat(70) + "b";
+        let words = [
+            "",
+            "a",
+            "kitten",
+            "sitting",
+            "mitten",
+            "saturday",
+            "sunday",
+            "a☃c",
+            "abc",
+            "cba",
+            long.as_str(),
+            &long[1..],
+        ];
+        for reference in words.iter() {
+            let matcher = Matcher::new(new_dist(), *reference);
+            assert_eq!(matcher.reference(), *reference);
+            for candidate in words.iter() {
+                let expected = dist.str_distance(reference, candidate);
+                let dist = matcher.distance_to(candidate);
+                // compare by debug output to treat `NaN` as equal
+                assert_eq!(
+                    format!("{:?}", dist),
+                    format!("{:?}", expected),
+                    "{} {}",
+                    reference,
+                    candidate
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn matcher_levenshtein() {
+        assert_matches(Levenshtein::default);
+        assert_matches(|| Levenshtein::with_max_distance(2));
+    }
+
+    #[test]
+    fn matcher_qgram() {
+        for q in 1..4 {
+            assert_matches(|| QGram::new(q));
+            assert_matches(|| Cosine::new(q));
+            assert_matches(|| Jaccard::new(q));
+            assert_matches(|| SorensenDice::new(q));
+            assert_matches(|| Overlap::new(q));
+            assert_matches(|| Tversky::new(q, 0.5, 0.25));
+            assert_matches(|| QGram::with_padding(q));
+            assert_matches(|| Cosine::with_padding(q));
+            assert_matches(|| Jaccard::with_padding(q));
+            assert_matches(|| SorensenDice::with_padding(q));
+            assert_matches(|| Overlap::with_padding(q));
+            assert_matches(|| Tversky::with_padding(q, 0.5, 0.25));
+        }
+    }
+
+    #[test]
+    fn matcher_jaro() {
+        assert_matches(|| Jaro);
+    }
+}
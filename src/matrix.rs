@@ -0,0 +1,84 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use crate::DistanceMetric;
+
+/// Evaluates the distances between all pairs of `items` in parallel.
+///
+/// The entry `[i][j]` of the returned matrix is the distance between
+/// `items[i]` and `items[j]`. Since the metric is assumed to be symmetric,
+/// only the upper triangle including the diagonal is evaluated and mirrored
+/// to the lower triangle. The rows of the upper triangle are evaluated in
+/// parallel.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceValue, Levenshtein};
+/// # use str_distance::matrix::distance_matrix;
+/// let matrix = distance_matrix(&["kitten", "sitting", "mitten"], &Levenshtein::default());
+/// assert_eq!(*matrix[0][1], 3);
+/// assert_eq!(matrix[1][0], matrix[0][1]);
+/// assert_eq!(*matrix[2][2], 0);
+/// ```
+pub fn distance_matrix<D, S>(items: &[S], dist: &D) -> Vec<Vec<D::Dist>>
+where
+    D: DistanceMetric + Sync,
+    D::Dist: Send + Clone,
+    S: AsRef<str> + Sync,
+{
+    // row i holds the distances of item i to the items i..n
+    let upper: Vec<Vec<D::Dist>> = items
+        .par_iter()
+        .enumerate()
+        .map(|(i, a)| items[i..].iter().map(|b| dist.str_distance(a, b)).collect())
+        .collect();
+
+    (0..items.len())
+        .map(|i| {
+            (0..items.len())
+                .map(|j| {
+                    if j < i {
+                        upper[j][i - j].clone()
+                    } else {
+                        upper[i][j - i].clone()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Jaro, Levenshtein};
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let items = [
+            "", "kitten", "sitting", "mitten", "saturday", "sunday", "a☃c", "abc",
+        ];
+        let sequential = |dist: &dyn Fn(&str, &str) -> f64| -> Vec<Vec<f64>> {
+            items
+                .iter()
+                .map(|a| items.iter().map(|b| dist(a, b)).collect())
+                .collect()
+        };
+
+        let jaro = distance_matrix(&items, &Jaro);
+        assert_eq!(jaro, sequential(&|a, b| Jaro.str_distance(a, b)));
+
+        let lev: Vec<Vec<f64>> = distance_matrix(&items, &Levenshtein::default())
+            .into_iter()
+            .map(|row| row.into_iter().map(f64::from).collect())
+            .collect();
+        assert_eq!(
+            lev,
+            sequential(&|a, b| *Levenshtein::default().str_distance(a, b) as f64)
+        );
+
+        assert!(distance_matrix::<Jaro, &str>(&[], &Jaro).is_empty());
+    }
+}
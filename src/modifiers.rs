@@ -1,4 +1,6 @@
-use std::cmp;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp;
 
 use crate::utils::{count_eq, order_by_len_asc};
 use crate::{DistanceMetric, Jaro};
@@ -11,6 +13,9 @@ pub struct WinklerConfig {
     threshold: f64,
     /// max length of common prefix. Default to 4
     max_length: usize,
+    /// Whether leading and trailing whitespace of str is ignored. Default to
+    /// false
+    trim: bool,
 }
 
 impl WinklerConfig {
@@ -24,8 +29,53 @@ impl WinklerConfig {
             scaling,
             threshold,
             max_length,
+            trim: false,
         }
     }
+
+    /// Creates a config whose boost grows linearly with the length of the
+    /// common prefix up to `max_length`, where it reaches `max_boost`.
+    ///
+    /// Unlike [`WinklerConfig::new`], the scaling factor is derived from
+    /// `max_length`, so that the boost never exceeds `max_boost` times the
+    /// original distance. This allows long prefixes, e.g. for ids or SKUs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_boost` is not between zero and one.
+    pub fn with_max_boost(max_boost: f64, threshold: f64, max_length: usize) -> Self {
+        assert!((0. ..=1.).contains(&max_boost));
+        let scaling = if max_length == 0 {
+            0.
+        } else {
+            max_boost / max_length as f64
+        };
+        Self {
+            scaling,
+            threshold,
+            max_length,
+            trim: false,
+        }
+    }
+
+    /// Whether to ignore leading and trailing whitespace of str, both for the
+    /// inner distance and the common prefix.
+    ///
+    /// This only affects the str methods, e.g. [`DistanceMetric::str_distance`]
+    /// and [`DistanceMetric::str_normalized`], the str are trimmed without
+    /// allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, Jaro, Winkler, WinklerConfig};
+    /// let winkler = Winkler::with_config(Jaro, WinklerConfig::default().trim(true));
+    /// assert_eq!(winkler.str_distance("foo", "foo "), 0.);
+    /// ```
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
 }
 
 impl Default for WinklerConfig {
@@ -34,6 +84,7 @@ impl Default for WinklerConfig {
             scaling: 0.1,
             threshold: 0.7,
             max_length: 4,
+            trim: false,
         }
     }
 }
@@ -100,10 +151,23 @@ where
         S: AsRef<str>,
         T: AsRef<str>,
     {
-        let (s1, s2) = order_by_len_asc(s1.as_ref(), s2.as_ref());
+        let (s1, s2) = (s1.as_ref(), s2.as_ref());
+        let (s1, s2) = if self.config.trim {
+            order_by_len_asc(s1.trim(), s2.trim())
+        } else {
+            order_by_len_asc(s1, s2)
+        };
         self.distance(s1.chars(), s2.chars())
     }
 
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+
     fn normalized<S, T>(&self, a: S, b: T) -> f64
     where
         S: IntoIterator,
@@ -125,3 +189,265 @@ impl Default for Winkler<Jaro> {
         }
     }
 }
+
+/// `Truncated` modifies a [`DistanceMetric`] to only compare the first
+/// `max_chars` elements of both inputs, e.g. the first chars of str.
+///
+/// This bounds the cost of comparing long inputs, at the price of ignoring
+/// everything after the first `max_chars` elements. str are truncated at char
+/// boundaries without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, DistanceValue, Levenshtein, Truncated};
+/// let truncated = Truncated::new(Levenshtein::default(), 6);
+/// assert_eq!(truncated.str_distance("kitten sitting", "kitten mitten"), DistanceValue::Exact(0));
+/// assert_eq!(truncated.str_distance("kitten", "sitting"), DistanceValue::Exact(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Truncated<D: DistanceMetric> {
+    /// The base distance to modify.
+    inner: D,
+    /// The maximum number of elements of each input to compare.
+    max_chars: usize,
+}
+
+impl<D: DistanceMetric> Truncated<D> {
+    /// Creates a new [`Truncated`] distance, that compares the first
+    /// `max_chars` chars or elements of both inputs using distance `D`.
+    pub fn new(inner: D, max_chars: usize) -> Self {
+        Self { inner, max_chars }
+    }
+
+    /// The maximum number of chars or elements of each input to compare.
+    pub fn max_chars(&self) -> usize {
+        self.max_chars
+    }
+
+    /// The prefix of `s` with at most `max_chars` chars.
+    fn truncate<'a>(&self, s: &'a str) -> &'a str {
+        match s.char_indices().nth(self.max_chars) {
+            Some((idx, _)) => &s[..idx],
+            None => s,
+        }
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Truncated<D> {
+    type Dist = <D as DistanceMetric>::Dist;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.distance(
+            a.into_iter().take(self.max_chars),
+            b.into_iter().take(self.max_chars),
+        )
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_distance(self.truncate(a.as_ref()), self.truncate(b.as_ref()))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.normalized(
+            a.into_iter().take(self.max_chars),
+            b.into_iter().take(self.max_chars),
+        )
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_normalized(self.truncate(a.as_ref()), self.truncate(b.as_ref()))
+    }
+}
+
+/// `Reversed` modifies a [`DistanceMetric`] to compare both inputs in reverse
+/// order, e.g. the chars of str from the last to the first.
+///
+/// This turns metrics that favor common prefixes into metrics that favor
+/// common suffixes, e.g. `Reversed<JaroWinkler>` diminishes the distance of
+/// strs with a common suffix instead of a common prefix.
+///
+/// Since the inner metric may treat str differently than other inputs, the
+/// reversed str are collected before they are compared. The chars are
+/// reversed, not the bytes, so multibyte chars are preserved.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Jaro, JaroWinkler, Reversed};
+/// let suffix_boost = Reversed::new(JaroWinkler::default());
+/// assert!(suffix_boost.str_distance("mation", "nation") < Jaro.str_distance("mation", "nation"));
+/// assert_eq!(suffix_boost.str_distance("nations", "nationx"), Jaro.str_distance("nations", "nationx"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reversed<D: DistanceMetric> {
+    /// The base distance to modify.
+    inner: D,
+}
+
+impl<D: DistanceMetric> Reversed<D> {
+    /// Creates a new [`Reversed`] distance, that compares the reversed inputs
+    /// using distance `D`.
+    pub fn new(inner: D) -> Self {
+        Self { inner }
+    }
+
+    /// The chars of `s` in reverse order.
+    fn reverse(s: &str) -> String {
+        s.chars().rev().collect()
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Reversed<D> {
+    type Dist = <D as DistanceMetric>::Dist;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.inner.distance(a.iter().rev(), b.iter().rev())
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_distance(Self::reverse(a.as_ref()), Self::reverse(b.as_ref()))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.inner.normalized(a.iter().rev(), b.iter().rev())
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_normalized(Self::reverse(a.as_ref()), Self::reverse(b.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DistanceValue, JaroWinkler, Levenshtein};
+
+    #[test]
+    fn truncated() {
+        let prefix = "a".repeat(100);
+        let a = format!("{}{}", prefix, "b".repeat(1000));
+        let b = format!("{}{}", prefix, "c".repeat(500));
+        let truncated = Truncated::new(Levenshtein::default(), prefix.len());
+        assert_eq!(truncated.str_distance(&a, &b), DistanceValue::Exact(0));
+        assert_eq!(truncated.str_normalized(&a, &b), 0.);
+        assert_eq!(
+            Truncated::new(Levenshtein::default(), 110).str_distance(&a, &b),
+            DistanceValue::Exact(10)
+        );
+        assert_eq!(
+            Levenshtein::default().str_distance(&a, &b),
+            DistanceValue::Exact(1000)
+        );
+
+        // truncated at char boundaries
+        let truncated = Truncated::new(Levenshtein::default(), 2);
+        assert_eq!(
+            truncated.str_distance("☃☃x", "☃☃y"),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(truncated.str_distance("é☃", "e☃"), DistanceValue::Exact(1));
+        assert_eq!(truncated.str_distance("☃", ""), DistanceValue::Exact(1));
+        assert_eq!(
+            truncated.distance(&[1, 2, 3], &[1, 2, 4]),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(truncated.normalized(&[1, 2, 3], &[1, 3]), 0.5);
+        assert_eq!(
+            Truncated::new(Jaro, 0).str_distance("abc", "xyz"),
+            Jaro.str_distance("", "")
+        );
+    }
+
+    #[test]
+    fn reversed() {
+        let jw = JaroWinkler::default();
+        let suffix_boost = Reversed::new(JaroWinkler::default());
+        // common suffix, but no common prefix
+        let (a, b) = ("mation", "nation");
+        assert!(suffix_boost.str_distance(a, b) < Jaro.str_distance(a, b));
+        assert_eq!(jw.str_distance(a, b), Jaro.str_distance(a, b));
+        // the same boost as Jaro-Winkler of the reversed strs
+        assert_eq!(
+            suffix_boost.str_distance(a, b),
+            jw.str_distance("noitam", "noitan")
+        );
+        assert_eq!(
+            suffix_boost.str_distance("nations", "nationx"),
+            Jaro.str_distance("nations", "nationx")
+        );
+        assert_eq!(
+            suffix_boost.str_normalized(a, b),
+            suffix_boost.str_distance(a, b)
+        );
+
+        // chars are reversed, not bytes
+        let lev = Reversed::new(Levenshtein::default());
+        assert_eq!(lev.str_distance("a☃é", "b☃é"), DistanceValue::Exact(1));
+        assert_eq!(
+            lev.distance(&[1, 2, 3], &[1, 2, 4]),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(
+            Reversed::new(Jaro).distance(&[1, 2, 3], &[3, 2, 1]),
+            Jaro.distance(&[3, 2, 1], &[1, 2, 3])
+        );
+        assert_eq!(lev.normalized(&[1, 2], &[1, 3]), 0.5);
+    }
+}
@@ -0,0 +1,470 @@
+use alloc::string::String;
+
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::UnicodeNormalization;
+
+use crate::matcher::Prepare;
+use crate::DistanceMetric;
+
+/// Preprocesses str before they are compared, e.g. to treat
+/// `"  Hello,  World! "` and `"hello world"` as equal.
+///
+/// The steps are applied in the following order, each can be toggled
+/// independently:
+///
+///  1. Unicode normalization to one of the forms of [`Normalization`], e.g.
+///     NFC to treat composed and decomposed chars as equal. Disabled by
+///     default, requires the `unicode-normalization` feature.
+///  2. Lowercasing, see [`str::to_lowercase`]. Enabled by default.
+///  3. Removing all chars that are neither alphanumeric nor whitespace, see
+///     [`char::is_alphanumeric`]. Disabled by default.
+///  4. Collapsing every run of whitespace into a single space. Enabled by
+///     default.
+///  5. Trimming leading and trailing whitespace. Enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::normalize::Normalizer;
+/// let normalizer = Normalizer::new().strip_punctuation(true);
+/// assert_eq!(normalizer.normalize("  Hello,  World! "), "hello world");
+/// assert_eq!(Normalizer::new().normalize("  Hello,  World! "), "hello, world!");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Normalizer {
+    /// The Unicode normalization form the str is normalized to, if any.
+    #[cfg(feature = "unicode-normalization")]
+    unicode: Option<Normalization>,
+    /// Whether the str is lowercased.
+    lowercase: bool,
+    /// Whether chars that are neither alphanumeric nor whitespace are removed.
+    strip_punctuation: bool,
+    /// Whether runs of whitespace are collapsed into a single space.
+    collapse_whitespace: bool,
+    /// Whether leading and trailing whitespace is removed.
+    trim: bool,
+}
+
+impl Normalizer {
+    /// Creates a new [`Normalizer`] that lowercases, collapses whitespace and
+    /// trims.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "unicode-normalization")]
+            unicode: None,
+            lowercase: true,
+            strip_punctuation: false,
+            collapse_whitespace: true,
+            trim: true,
+        }
+    }
+
+    /// Sets whether the str is normalized to Unicode NFKC.
+    ///
+    /// This is a shorthand for
+    /// `unicode_normalization(Some(Normalization::Nfkc))`.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn nfkc(self, nfkc: bool) -> Self {
+        self.unicode_normalization(if nfkc {
+            Some(Normalization::Nfkc)
+        } else {
+            None
+        })
+    }
+
+    /// Sets the Unicode normalization form the str is normalized to, `None`
+    /// disables the Unicode normalization.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn unicode_normalization(mut self, normalization: Option<Normalization>) -> Self {
+        self.unicode = normalization;
+        self
+    }
+
+    /// Sets whether the str is lowercased.
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Sets whether chars that are neither alphanumeric nor whitespace are
+    /// removed.
+    pub fn strip_punctuation(mut self, strip_punctuation: bool) -> Self {
+        self.strip_punctuation = strip_punctuation;
+        self
+    }
+
+    /// Sets whether runs of whitespace are collapsed into a single space.
+    pub fn collapse_whitespace(mut self, collapse_whitespace: bool) -> Self {
+        self.collapse_whitespace = collapse_whitespace;
+        self
+    }
+
+    /// Sets whether leading and trailing whitespace is removed.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Applies all enabled steps to `s`.
+    pub fn normalize(&self, s: &str) -> String {
+        #[cfg(feature = "unicode-normalization")]
+        let unicode: String;
+        #[cfg(feature = "unicode-normalization")]
+        let s = match self.unicode {
+            Some(normalization) => {
+                unicode = normalization.apply(s);
+                unicode.as_str()
+            }
+            None => s,
+        };
+
+        let lowercase: String;
+        let s = if self.lowercase {
+            lowercase = s.to_lowercase();
+            lowercase.as_str()
+        } else {
+            s
+        };
+
+        let mut normalized = String::with_capacity(s.len());
+        // whitespace that is only pushed once the next char is known
+        let mut pending_whitespace = String::new();
+        for c in s.chars() {
+            if c.is_whitespace() {
+                if self.collapse_whitespace {
+                    if pending_whitespace.is_empty() {
+                        pending_whitespace.push(' ');
+                    }
+                } else {
+                    pending_whitespace.push(c);
+                }
+            } else if !self.strip_punctuation || c.is_alphanumeric() {
+                if !self.trim || !normalized.is_empty() {
+                    normalized.push_str(&pending_whitespace);
+                }
+                pending_whitespace.clear();
+                normalized.push(c);
+            }
+        }
+        if !self.trim {
+            normalized.push_str(&pending_whitespace);
+        }
+        normalized
+    }
+}
+
+/// The Unicode normalization forms, see
+/// [UAX #15](https://unicode.org/reports/tr15/).
+///
+/// Requires the `unicode-normalization` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::normalize::Normalization;
+/// // "ñ" as a single char and as "n" followed by a combining tilde
+/// assert_eq!(Normalization::Nfc.apply("n\u{303}"), "\u{f1}");
+/// assert_eq!(Normalization::Nfd.apply("\u{f1}"), "n\u{303}");
+/// assert_eq!(Normalization::Nfkc.apply("ﬁ"), "fi");
+/// ```
+#[cfg(feature = "unicode-normalization")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+#[cfg(feature = "unicode-normalization")]
+impl Normalization {
+    /// Normalizes `s` to this form.
+    pub fn apply(&self, s: &str) -> String {
+        match self {
+            Normalization::Nfc => s.nfc().collect(),
+            Normalization::Nfd => s.nfd().collect(),
+            Normalization::Nfkc => s.nfkc().collect(),
+            Normalization::Nfkd => s.nfkd().collect(),
+        }
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `Normalized` modifies the inner distance `dist` to compare the str after
+/// preprocessing them with a [`Normalizer`].
+///
+/// For other types than strings this is just a delegate to the inner metric.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, DistanceValue, Levenshtein};
+/// # use str_distance::normalize::{Normalized, Normalizer};
+/// let lev = Normalized::with_normalizer(Levenshtein::default(), Normalizer::new().strip_punctuation(true));
+/// assert_eq!(lev.str_distance("  Hello,  World! ", "hello world"), DistanceValue::Exact(0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Normalized<D: DistanceMetric> {
+    /// The base distance to modify.
+    inner: D,
+    /// How the str are preprocessed.
+    normalizer: Normalizer,
+}
+
+impl<D: DistanceMetric> Normalized<D> {
+    /// Create a new [`Normalized`] distance metric using distance `D` as base,
+    /// that preprocesses the str with the default [`Normalizer`].
+    pub fn new(inner: D) -> Self {
+        Self::with_normalizer(inner, Normalizer::default())
+    }
+
+    /// Create a new [`Normalized`] distance metric using distance `D` as base,
+    /// that preprocesses the str with the `normalizer`.
+    pub fn with_normalizer(inner: D, normalizer: Normalizer) -> Self {
+        Self { inner, normalizer }
+    }
+
+    /// The [`Normalizer`] that preprocesses the str.
+    pub fn normalizer(&self) -> &Normalizer {
+        &self.normalizer
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Normalized<D> {
+    type Dist = <D as DistanceMetric>::Dist;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner.str_distance(
+            self.normalizer.normalize(a.as_ref()),
+            self.normalizer.normalize(b.as_ref()),
+        )
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.normalized(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner.str_normalized(
+            self.normalizer.normalize(a.as_ref()),
+            self.normalizer.normalize(b.as_ref()),
+        )
+    }
+
+    fn str_distance_within<S, T>(&self, a: S, b: T, threshold: f64) -> Option<f64>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner.str_distance_within(
+            self.normalizer.normalize(a.as_ref()),
+            self.normalizer.normalize(b.as_ref()),
+            threshold,
+        )
+    }
+}
+
+/// Normalizes the reference once, so that a [`crate::matcher::Matcher`] only
+/// normalizes the candidates.
+impl<D: Prepare> Prepare for Normalized<D> {
+    type Prepared = <D as Prepare>::Prepared;
+
+    fn prepare(&self, reference: &str) -> Self::Prepared {
+        self.inner.prepare(&self.normalizer.normalize(reference))
+    }
+
+    fn distance_prepared(&self, prepared: &Self::Prepared, candidate: &str) -> Self::Dist {
+        self.inner
+            .distance_prepared(prepared, &self.normalizer.normalize(candidate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::Matcher;
+    use crate::{DistanceValue, Jaro, Levenshtein};
+
+    #[test]
+    fn normalizer_steps() {
+        let s = "  Hello,\t\tWORLD!  ";
+        assert_eq!(Normalizer::new().normalize(s), "hello, world!");
+        assert_eq!(
+            Normalizer::new().strip_punctuation(true).normalize(s),
+            "hello world"
+        );
+        assert_eq!(
+            Normalizer::new().lowercase(false).normalize(s),
+            "Hello, WORLD!"
+        );
+        assert_eq!(
+            Normalizer::new().collapse_whitespace(false).normalize(s),
+            "hello,\t\tworld!"
+        );
+        assert_eq!(
+            Normalizer::new().trim(false).normalize(s),
+            " hello, world! "
+        );
+        let unchanged = Normalizer::new()
+            .lowercase(false)
+            .collapse_whitespace(false)
+            .trim(false);
+        assert_eq!(unchanged.normalize(s), s);
+        // punctuation between words is removed without a space
+        assert_eq!(
+            Normalizer::new()
+                .strip_punctuation(true)
+                .normalize("rock'n'roll - ÜBER"),
+            "rocknroll über"
+        );
+        assert_eq!(Normalizer::new().normalize(" \t "), "");
+        assert_eq!(Normalizer::new().normalize(""), "");
+        // the final sigma is lowercased according to its position
+        assert_eq!(Normalizer::new().normalize("ΟΔΟΣ"), "οδος");
+    }
+
+    #[test]
+    fn messy_variants_collapse() {
+        let normalizer = Normalizer::new().strip_punctuation(true);
+        let lev = Normalized::with_normalizer(Levenshtein::default(), normalizer);
+        assert_eq!(
+            lev.str_distance("  Hello,  World! ", "hello world"),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(
+            lev.str_distance("HELLO...\n\nworld", "  hello   WORLD?"),
+            DistanceValue::Exact(0)
+        );
+        assert_eq!(lev.str_normalized("Hello, World!", "hello world"), 0.);
+        assert_eq!(
+            lev.str_distance_within("Hello, World!", "hello world", 0.),
+            Some(0.)
+        );
+        assert_eq!(
+            lev.str_distance("Hello, World!", "hello word"),
+            DistanceValue::Exact(1)
+        );
+        assert_eq!(
+            Levenshtein::default().str_distance("  Hello,  World! ", "hello world"),
+            DistanceValue::Exact(8)
+        );
+
+        let jaro = Normalized::with_normalizer(Jaro, normalizer);
+        assert_eq!(jaro.str_distance("  Hello,  World! ", "hello world"), 0.);
+        // other types than str are not normalized
+        assert_eq!(jaro.distance(&["A"], &["a"]), 1.);
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn normalizer_nfkc() {
+        let normalizer = Normalizer::new().nfkc(true);
+        assert_eq!(
+            normalizer.normalize("Ｈｅｌｌｏ　Ｗｏｒｌｄ"),
+            "hello world"
+        );
+        assert_eq!(normalizer.normalize("ﬁne"), "fine");
+        assert_eq!(Normalizer::new().normalize("ﬁne"), "ﬁne");
+        let lev = Normalized::with_normalizer(Levenshtein::default(), normalizer);
+        assert_eq!(
+            lev.str_distance("Ｈｅｌｌｏ， ＷＯＲＬＤ", "hello, world"),
+            DistanceValue::Exact(0)
+        );
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn normalizer_unicode_forms() {
+        let (composed, decomposed) = ("ni\u{f1}o", "nin\u{303}o");
+        let lev = Levenshtein::default();
+        assert_eq!(
+            lev.str_distance(composed, decomposed),
+            DistanceValue::Exact(2)
+        );
+
+        for normalization in [Normalization::Nfc, Normalization::Nfd].iter() {
+            let normalizer = Normalizer::new().unicode_normalization(Some(*normalization));
+            let normalized = Normalized::with_normalizer(lev.clone(), normalizer);
+            assert_eq!(
+                normalized.str_distance(composed, decomposed),
+                DistanceValue::Exact(0)
+            );
+        }
+        let nfc = Normalizer::new().unicode_normalization(Some(Normalization::Nfc));
+        assert_eq!(nfc.normalize(decomposed), composed);
+        // NFC keeps compatibility chars, unlike NFKC
+        assert_eq!(nfc.normalize("ﬁne"), "ﬁne");
+        assert_eq!(
+            Normalizer::new()
+                .unicode_normalization(Some(Normalization::Nfkd))
+                .normalize("ﬁ\u{f1}"),
+            "fin\u{303}"
+        );
+        assert_eq!(
+            Normalizer::new().nfkc(true),
+            Normalizer::new().unicode_normalization(Some(Normalization::Nfkc))
+        );
+
+        // the reference is normalized once
+        let matcher = Matcher::new(
+            Normalized::with_normalizer(lev.clone(), nfc),
+            decomposed.to_uppercase(),
+        );
+        assert_eq!(matcher.distance_to(composed), DistanceValue::Exact(0));
+        assert_eq!(matcher.distance_to("nino"), DistanceValue::Exact(1));
+        let matcher = Matcher::new(Normalized::with_normalizer(Jaro, nfc), composed);
+        assert_eq!(matcher.distance_to(decomposed), 0.);
+    }
+
+    #[test]
+    fn normalized_matcher() {
+        let normalizer = Normalizer::new().strip_punctuation(true);
+        let lev = Normalized::with_normalizer(Levenshtein::default(), normalizer);
+        let matcher = Matcher::new(lev.clone(), "  Hello,  World! ");
+        for candidate in ["hello world", "HELLO...\n\nworld", "hello word", ""].iter() {
+            assert_eq!(
+                matcher.distance_to(candidate),
+                lev.str_distance("  Hello,  World! ", candidate)
+            );
+        }
+        // the reference is kept as it is
+        assert_eq!(matcher.reference(), "  Hello,  World! ");
+    }
+}
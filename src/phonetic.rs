@@ -0,0 +1,1695 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::{DistanceMetric, Levenshtein};
+
+/// Encodes `s` with the American Soundex algorithm.
+///
+/// The code consists of the first letter of `s` followed by three digits that
+/// encode the following consonants, padded with `0`. Letters are compared
+/// case insensitive, all chars that are not ASCII letters are ignored. If `s`
+/// contains no ASCII letters, the code is empty.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::phonetic::soundex;
+/// assert_eq!(soundex("Robert"), "R163");
+/// assert_eq!(soundex("Rupert"), "R163");
+/// assert_eq!(soundex("Lee"), "L000");
+/// assert_eq!(soundex("123"), "");
+/// ```
+pub fn soundex(s: &str) -> String {
+    let mut letters = s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase());
+
+    let first = match letters.next() {
+        Some(c) => c,
+        None => return String::new(),
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    let mut last_digit = soundex_digit(first);
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+        match c {
+            // letters with the same digit separated by `H` or `W` are coded once
+            'H' | 'W' => continue,
+            _ => {
+                let digit = soundex_digit(c);
+                if let Some(d) = digit {
+                    if digit != last_digit {
+                        code.push(d);
+                    }
+                }
+                last_digit = digit;
+            }
+        }
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+/// The Soundex digit of an uppercase ASCII letter, `None` for vowels and
+/// `H`, `W` and `Y`.
+fn soundex_digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+/// A phonetic distance that considers two strings equal if they share the
+/// same [`soundex`] code.
+///
+/// The distance is `0.` if both codes are equal and `1.` otherwise. A str
+/// without ASCII letters has an empty code, that carries no phonetic
+/// information, so such a str is only at a distance of `0.` to an equal str.
+///
+/// For other types than strings, the inputs are compared for equality.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Soundex};
+/// assert_eq!(Soundex.str_distance("Robert", "Rupert"), 0.);
+/// assert_eq!(Soundex.str_distance("Robert", "Rubin"), 1.);
+/// assert_eq!(Soundex.str_distance("123", "!!"), 1.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Soundex;
+
+impl DistanceMetric for Soundex {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        if a.into_iter().eq(b) {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let (code_a, code_b) = (soundex(a), soundex(b));
+        if code_a.is_empty() || code_b.is_empty() {
+            // empty codes only match equal strs
+            return if a == b { 0. } else { 1. };
+        }
+        if code_a == code_b {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The maximum length of the keys of [`double_metaphone`].
+const DOUBLE_METAPHONE_KEY_LEN: usize = 4;
+
+/// Encodes `s` with the Double Metaphone algorithm by Lawrence Philips and
+/// returns the primary key and, if it differs, the alternate key.
+///
+/// Both keys have at most four chars. The alternate key accounts for
+/// alternative pronunciations of names of non English origin, e.g. `"Schmidt"`
+/// is encoded as `("XMT", Some("SMT"))`. Letters are compared case
+/// insensitive, chars that are not letters are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::phonetic::double_metaphone;
+/// assert_eq!(double_metaphone("Thompson"), ("TMPS".to_string(), None));
+/// assert_eq!(double_metaphone("Smith"), ("SM0".to_string(), Some("XMT".to_string())));
+/// assert_eq!(double_metaphone("Schmidt"), ("XMT".to_string(), Some("SMT".to_string())));
+/// ```
+pub fn double_metaphone(s: &str) -> (String, Option<String>) {
+    let (primary, alternate) = DoubleMetaphoneEncoder::new(s).encode();
+    if primary == alternate {
+        (primary, None)
+    } else {
+        (primary, Some(alternate))
+    }
+}
+
+/// The state of the encoding of a single str with [`double_metaphone`].
+///
+/// The index of the current char is an `isize`, since many rules inspect
+/// chars before it.
+struct DoubleMetaphoneEncoder {
+    /// The uppercase chars of the str.
+    value: Vec<char>,
+    /// Whether the str is likely of Slavic or Germanic origin.
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl DoubleMetaphoneEncoder {
+    fn new(s: &str) -> Self {
+        let value: Vec<_> = s.trim().chars().flat_map(char::to_uppercase).collect();
+        let slavo_germanic = value.contains(&'W')
+            || value.contains(&'K')
+            || value.windows(2).any(|w| w == ['C', 'Z'])
+            || value.windows(4).any(|w| w == ['W', 'I', 'T', 'Z']);
+        Self {
+            value,
+            slavo_germanic,
+            primary: String::with_capacity(DOUBLE_METAPHONE_KEY_LEN),
+            alternate: String::with_capacity(DOUBLE_METAPHONE_KEY_LEN),
+        }
+    }
+
+    /// The index of the last char.
+    fn last(&self) -> isize {
+        self.value.len() as isize - 1
+    }
+
+    /// The char at `index`, or `'\0'` if `index` is out of bounds.
+    fn at(&self, index: isize) -> char {
+        if index < 0 || index > self.last() {
+            '\0'
+        } else {
+            self.value[index as usize]
+        }
+    }
+
+    /// Whether the `len` chars starting at `start` equal any of the
+    /// `patterns`.
+    fn contains(&self, start: isize, len: usize, patterns: &[&str]) -> bool {
+        if start < 0 || start as usize + len > self.value.len() {
+            return false;
+        }
+        let slice = &self.value[start as usize..start as usize + len];
+        patterns
+            .iter()
+            .any(|pattern| pattern.chars().eq(slice.iter().copied()))
+    }
+
+    fn is_vowel(c: char) -> bool {
+        matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= DOUBLE_METAPHONE_KEY_LEN
+            && self.alternate.len() >= DOUBLE_METAPHONE_KEY_LEN
+    }
+
+    fn add_primary(&mut self, s: &str) {
+        let remaining = DOUBLE_METAPHONE_KEY_LEN.saturating_sub(self.primary.len());
+        self.primary.extend(s.chars().take(remaining));
+    }
+
+    fn add_alternate(&mut self, s: &str) {
+        let remaining = DOUBLE_METAPHONE_KEY_LEN.saturating_sub(self.alternate.len());
+        self.alternate.extend(s.chars().take(remaining));
+    }
+
+    /// Adds `primary` to the primary key and `alternate` to the alternate key.
+    fn add(&mut self, primary: &str, alternate: &str) {
+        self.add_primary(primary);
+        self.add_alternate(alternate);
+    }
+
+    /// Adds `s` to both keys.
+    fn add_both(&mut self, s: &str) {
+        self.add(s, s);
+    }
+
+    /// Skips the next char if it is `c`.
+    fn skip_if(&self, index: isize, c: char) -> isize {
+        if self.at(index + 1) == c {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode(mut self) -> (String, String) {
+        let mut index = if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            1
+        } else {
+            0
+        };
+
+        while !self.is_complete() && index <= self.last() {
+            index = match self.at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    // vowels are only kept at the start
+                    if index == 0 {
+                        self.add_both("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.add_both("P");
+                    self.skip_if(index, 'B')
+                }
+                'Ç' => {
+                    self.add_both("S");
+                    index + 1
+                }
+                'C' => self.handle_c(index),
+                'D' => self.handle_d(index),
+                'F' => {
+                    self.add_both("F");
+                    self.skip_if(index, 'F')
+                }
+                'G' => self.handle_g(index),
+                'H' => self.handle_h(index),
+                'J' => self.handle_j(index),
+                'K' => {
+                    self.add_both("K");
+                    self.skip_if(index, 'K')
+                }
+                'L' => self.handle_l(index),
+                'M' => {
+                    self.add_both("M");
+                    if self.condition_m0(index) {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.add_both("N");
+                    self.skip_if(index, 'N')
+                }
+                'Ñ' => {
+                    self.add_both("N");
+                    index + 1
+                }
+                'P' => self.handle_p(index),
+                'Q' => {
+                    self.add_both("K");
+                    self.skip_if(index, 'Q')
+                }
+                'R' => self.handle_r(index),
+                'S' => self.handle_s(index),
+                'T' => self.handle_t(index),
+                'V' => {
+                    self.add_both("F");
+                    self.skip_if(index, 'V')
+                }
+                'W' => self.handle_w(index),
+                'X' => self.handle_x(index),
+                'Z' => self.handle_z(index),
+                _ => index + 1,
+            };
+        }
+        (self.primary, self.alternate)
+    }
+
+    fn handle_c(&mut self, index: isize) -> isize {
+        if self.condition_c0(index) {
+            // various germanic
+            self.add_both("K");
+            index + 2
+        } else if index == 0 && self.contains(index, 6, &["CAESAR"]) {
+            self.add_both("S");
+            index + 2
+        } else if self.contains(index, 2, &["CH"]) {
+            self.handle_ch(index)
+        } else if self.contains(index, 2, &["CZ"]) && !self.contains(index - 2, 4, &["WICZ"]) {
+            // "Czerny"
+            self.add("S", "X");
+            index + 2
+        } else if self.contains(index + 1, 3, &["CIA"]) {
+            // "focaccia"
+            self.add_both("X");
+            index + 3
+        } else if self.contains(index, 2, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            // double "C", but not "McClellan"
+            self.handle_cc(index)
+        } else if self.contains(index, 2, &["CK", "CG", "CQ"]) {
+            self.add_both("K");
+            index + 2
+        } else if self.contains(index, 2, &["CI", "CE", "CY"]) {
+            // italian vs. english
+            if self.contains(index, 3, &["CIO", "CIE", "CIA"]) {
+                self.add("S", "X");
+            } else {
+                self.add_both("S");
+            }
+            index + 2
+        } else {
+            self.add_both("K");
+            if self.contains(index + 1, 2, &[" C", " Q", " G"]) {
+                // "Mac Caffrey", "Mac Gregor"
+                index + 3
+            } else if self.contains(index + 1, 1, &["C", "K", "Q"])
+                && !self.contains(index + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_cc(&mut self, index: isize) -> isize {
+        if self.contains(index + 2, 1, &["I", "E", "H"]) && !self.contains(index + 2, 2, &["HU"]) {
+            // "bellocchio" but not "bacchus"
+            if (index == 1 && self.at(index - 1) == 'A')
+                || self.contains(index - 1, 5, &["UCCEE", "UCCES"])
+            {
+                // "accident", "accede", "succeed"
+                self.add_both("KS");
+            } else {
+                // "bacci", "bertucci", other italian
+                self.add_both("X");
+            }
+            index + 3
+        } else {
+            // Pierce's rule
+            self.add_both("K");
+            index + 2
+        }
+    }
+
+    fn handle_ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.contains(index, 4, &["CHAE"]) {
+            // "Michael"
+            self.add("K", "X");
+        } else if self.condition_ch0(index) || self.condition_ch1(index) {
+            // greek roots, germanic
+            self.add_both("K");
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.add_both("K");
+            } else {
+                self.add("X", "K");
+            }
+        } else {
+            self.add_both("X");
+        }
+        index + 2
+    }
+
+    fn handle_d(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["DG"]) {
+            if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+                // "edge"
+                self.add_both("J");
+                index + 3
+            } else {
+                // "edgar"
+                self.add_both("TK");
+                index + 2
+            }
+        } else if self.contains(index, 2, &["DT", "DD"]) {
+            self.add_both("T");
+            index + 2
+        } else {
+            self.add_both("T");
+            index + 1
+        }
+    }
+
+    fn handle_g(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            self.handle_gh(index)
+        } else if self.at(index + 1) == 'N' {
+            if index == 1 && Self::is_vowel(self.at(0)) && !self.slavo_germanic {
+                self.add("KN", "N");
+            } else if !self.contains(index + 2, 2, &["EY"])
+                && self.at(index + 1) != 'Y'
+                && !self.slavo_germanic
+            {
+                self.add("N", "KN");
+            } else {
+                self.add_both("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, 2, &["LI"]) && !self.slavo_germanic {
+            // "tagliaro"
+            self.add("KL", "L");
+            index + 2
+        } else if index == 0
+            && (self.at(index + 1) == 'Y'
+                || self.contains(
+                    index + 1,
+                    2,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            // -ges-, -gep-, -gel-, -gie- at the beginning
+            self.add("K", "J");
+            index + 2
+        } else if (self.contains(index + 1, 2, &["ER"]) || self.at(index + 1) == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(index - 1, 1, &["E", "I"])
+            && !self.contains(index - 1, 3, &["RGY", "OGY"])
+        {
+            // -ger-, -gy-
+            self.add("K", "J");
+            index + 2
+        } else if self.contains(index + 1, 1, &["E", "I", "Y"])
+            || self.contains(index - 1, 4, &["AGGI", "OGGI"])
+        {
+            // italian "biaggi"
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(index + 1, 2, &["ET"])
+            {
+                // obvious germanic
+                self.add_both("K");
+            } else if self.contains(index + 1, 3, &["IER"]) {
+                self.add_both("J");
+            } else {
+                self.add("J", "K");
+            }
+            index + 2
+        } else {
+            self.add_both("K");
+            self.skip_if(index, 'G')
+        }
+    }
+
+    fn handle_gh(&mut self, index: isize) -> isize {
+        if index > 0 && !Self::is_vowel(self.at(index - 1)) {
+            self.add_both("K");
+        } else if index == 0 {
+            // "ghislane", "ghiradelli"
+            if self.at(index + 2) == 'I' {
+                self.add_both("J");
+            } else {
+                self.add_both("K");
+            }
+        } else if (index > 1 && self.contains(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, 1, &["B", "H"]))
+        {
+            // Parker's rule, e.g. "hugh"
+        } else if index > 2
+            && self.at(index - 1) == 'U'
+            && self.contains(index - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // "laugh", "McLaughlin", "cough", "gough", "rough", "tough"
+            self.add_both("F");
+        } else if self.at(index - 1) != 'I' {
+            self.add_both("K");
+        }
+        index + 2
+    }
+
+    fn handle_h(&mut self, index: isize) -> isize {
+        // only keep if first or between two vowels
+        if (index == 0 || Self::is_vowel(self.at(index - 1))) && Self::is_vowel(self.at(index + 1))
+        {
+            self.add_both("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            // obvious spanish, "Jose", "San Jacinto"
+            if (index == 0 && self.at(index + 4) == ' ')
+                || self.value.len() == 4
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.add_both("H");
+            } else {
+                self.add("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            // "Yankelovich", "Jankelowicz"
+            self.add("J", "A");
+        } else if Self::is_vowel(self.at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.at(index + 1), 'A' | 'O')
+        {
+            // spanish pronunciation of e.g. "bajador"
+            self.add("J", "H");
+        } else if index == self.last() {
+            self.add_primary("J");
+        } else if !self.contains(index + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(index - 1, 1, &["S", "K", "L"])
+        {
+            self.add_both("J");
+        }
+        self.skip_if(index, 'J')
+    }
+
+    fn handle_l(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'L' {
+            if self.condition_l0(index) {
+                // spanish e.g. "cabrillo", "gallegos"
+                self.add_primary("L");
+            } else {
+                self.add_both("L");
+            }
+            index + 2
+        } else {
+            self.add_both("L");
+            index + 1
+        }
+    }
+
+    fn handle_p(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            self.add_both("F");
+            index + 2
+        } else {
+            // also account for "campbell" and "raspberry"
+            self.add_both("P");
+            if self.contains(index + 1, 1, &["P", "B"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_r(&mut self, index: isize) -> isize {
+        if index == self.last()
+            && !self.slavo_germanic
+            && self.contains(index - 2, 2, &["IE"])
+            && !self.contains(index - 4, 2, &["ME", "MA"])
+        {
+            // french e.g. "rogier", but not "hochmeier"
+            self.add_alternate("R");
+        } else {
+            self.add_both("R");
+        }
+        self.skip_if(index, 'R')
+    }
+
+    fn handle_s(&mut self, index: isize) -> isize {
+        if self.contains(index - 1, 3, &["ISL", "YSL"]) {
+            // "island", "isle", "carlisle", "carlysle"
+            index + 1
+        } else if index == 0 && self.contains(index, 5, &["SUGAR"]) {
+            self.add("X", "S");
+            index + 1
+        } else if self.contains(index, 2, &["SH"]) {
+            if self.contains(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                // germanic
+                self.add_both("S");
+            } else {
+                self.add_both("X");
+            }
+            index + 2
+        } else if self.contains(index, 3, &["SIO", "SIA"]) || self.contains(index, 4, &["SIAN"]) {
+            // italian and armenian
+            if self.slavo_germanic {
+                self.add_both("S");
+            } else {
+                self.add("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(index + 1, 1, &["Z"])
+        {
+            // german and anglicisations, e.g. "smith" matches "schmidt" and
+            // "snider" matches "schneider"
+            self.add("S", "X");
+            self.skip_if(index, 'Z')
+        } else if self.contains(index, 2, &["SC"]) {
+            self.handle_sc(index)
+        } else {
+            if index == self.last() && self.contains(index - 2, 2, &["AI", "OI"]) {
+                // french e.g. "resnais", "artois"
+                self.add_alternate("S");
+            } else {
+                self.add_both("S");
+            }
+            if self.contains(index + 1, 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_sc(&mut self, index: isize) -> isize {
+        if self.at(index + 2) == 'H' {
+            // Schlesinger's rule
+            if self.contains(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // dutch origin, e.g. "school", "schooner"
+                if self.contains(index + 3, 2, &["ER", "EN"]) {
+                    // "schermerhorn", "schenker"
+                    self.add("X", "SK");
+                } else {
+                    self.add_both("SK");
+                }
+            } else if index == 0 && !Self::is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.add("X", "S");
+            } else {
+                self.add_both("X");
+            }
+        } else if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+            self.add_both("S");
+        } else {
+            self.add_both("SK");
+        }
+        index + 3
+    }
+
+    fn handle_t(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["TION"]) || self.contains(index, 3, &["TIA", "TCH"]) {
+            self.add_both("X");
+            index + 3
+        } else if self.contains(index, 2, &["TH"]) || self.contains(index, 3, &["TTH"]) {
+            if self.contains(index + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                // "thomas", "thames" or germanic
+                self.add_both("T");
+            } else {
+                self.add("0", "T");
+            }
+            index + 2
+        } else {
+            self.add_both("T");
+            if self.contains(index + 1, 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_w(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["WR"]) {
+            self.add_both("R");
+            index + 2
+        } else if index == 0
+            && (Self::is_vowel(self.at(index + 1)) || self.contains(index, 2, &["WH"]))
+        {
+            if Self::is_vowel(self.at(index + 1)) {
+                // "Wasserman" matches "Vasserman"
+                self.add("A", "F");
+            } else {
+                // "Uomo" matches "Womo"
+                self.add_both("A");
+            }
+            index + 1
+        } else if (index == self.last() && Self::is_vowel(self.at(index - 1)))
+            || self.contains(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            // "Arnow" matches "Arnoff"
+            self.add_alternate("F");
+            index + 1
+        } else if self.contains(index, 4, &["WICZ", "WITZ"]) {
+            // polish e.g. "filipowicz"
+            self.add("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            // "Xavier"
+            self.add_both("S");
+            return index + 1;
+        }
+        if !(index == self.last()
+            && (self.contains(index - 3, 3, &["IAU", "EAU"])
+                || self.contains(index - 2, 2, &["AU", "OU"])))
+        {
+            // not french e.g. "breaux"
+            self.add_both("KS");
+        }
+        if self.contains(index + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_z(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            // chinese pinyin e.g. "zhao"
+            self.add_both("J");
+            index + 2
+        } else {
+            if self.contains(index + 1, 2, &["ZO", "ZI", "ZA"])
+                || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+            {
+                self.add("S", "TS");
+            } else {
+                self.add_both("S");
+            }
+            self.skip_if(index, 'Z')
+        }
+    }
+
+    /// Whether `CH` is pronounced as `K` in germanic words like "bacher".
+    fn condition_c0(&self, index: isize) -> bool {
+        if self.contains(index, 4, &["CHIA"]) {
+            true
+        } else if index <= 1
+            || Self::is_vowel(self.at(index - 2))
+            || !self.contains(index - 1, 3, &["ACH"])
+        {
+            false
+        } else {
+            let c = self.at(index + 2);
+            (c != 'I' && c != 'E') || self.contains(index - 2, 6, &["BACHER", "MACHER"])
+        }
+    }
+
+    /// Whether a leading `CH` is of greek origin, e.g. "character".
+    fn condition_ch0(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    /// Whether `CH` is pronounced as `K`, e.g. "orchestra".
+    fn condition_ch1(&self, index: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, 1, &["T", "S"])
+            || ((self.contains(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(
+                    index + 2,
+                    1,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || index + 1 == self.last()))
+    }
+
+    /// Whether a double `L` is of spanish origin.
+    fn condition_l0(&self, index: isize) -> bool {
+        let len = self.value.len() as isize;
+        (index == len - 3 && self.contains(index - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(len - 2, 2, &["AS", "OS"])
+                || self.contains(len - 1, 1, &["A", "O"]))
+                && self.contains(index - 1, 4, &["ALLE"]))
+    }
+
+    /// Whether the char after `M` is silent, e.g. "dumb", "thumb".
+    fn condition_m0(&self, index: isize) -> bool {
+        self.at(index + 1) == 'M'
+            || (self.contains(index - 1, 3, &["UMB"])
+                && (index + 1 == self.last() || self.contains(index + 2, 2, &["ER"])))
+    }
+}
+
+/// A phonetic distance that considers two strings equal if any of their
+/// primary or alternate [`double_metaphone`] keys match.
+///
+/// The distance is `0.` if the keys match and `1.` otherwise.
+///
+/// For other types than strings, the inputs are compared for equality.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, DoubleMetaphone};
+/// assert_eq!(DoubleMetaphone.str_distance("Smith", "Schmidt"), 0.);
+/// assert_eq!(DoubleMetaphone.str_distance("Smith", "Jones"), 1.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoubleMetaphone;
+
+impl DistanceMetric for DoubleMetaphone {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        if a.into_iter().eq(b) {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (primary_a, alternate_a) = double_metaphone(a.as_ref());
+        let (primary_b, alternate_b) = double_metaphone(b.as_ref());
+        let keys_a = [Some(primary_a), alternate_a];
+        let keys_b = [Some(primary_b), alternate_b];
+        let matches = keys_a
+            .iter()
+            .flatten()
+            .any(|key| keys_b.iter().flatten().any(|other| key == other));
+        if matches {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The length of the codes of [`caverphone`].
+const CAVERPHONE_CODE_LEN: usize = 10;
+
+/// Encodes `s` with the Caverphone 2.0 algorithm by David Hood, that was
+/// designed for English names and places.
+///
+/// The code consists of exactly ten chars: the uppercase letters of the
+/// encoded consonants and of vowels at the start or the end, padded with `1`.
+/// Letters are compared case insensitive, all chars that are not ASCII
+/// letters are ignored. If `s` contains no ASCII letters, the code consists
+/// of `1`s only.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::phonetic::caverphone;
+/// assert_eq!(caverphone("Thompson"), "TMPSN11111");
+/// assert_eq!(caverphone("Stevenson"), "STFNSN1111");
+/// assert_eq!(caverphone("Peter"), "PTA1111111");
+/// ```
+pub fn caverphone(s: &str) -> String {
+    let mut code: String = s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    replace_suffix(&mut code, "e", "");
+    for (from, to) in [
+        ("cough", "cou2f"),
+        ("rough", "rou2f"),
+        ("tough", "tou2f"),
+        ("enough", "enou2f"),
+        ("trough", "trou2f"),
+        ("gn", "2n"),
+    ]
+    .iter()
+    {
+        replace_prefix(&mut code, from, to);
+    }
+    replace_suffix(&mut code, "mb", "m2");
+    for (from, to) in [
+        ("cq", "2q"),
+        ("ci", "si"),
+        ("ce", "se"),
+        ("cy", "sy"),
+        ("tch", "2ch"),
+        ("c", "k"),
+        ("q", "k"),
+        ("x", "k"),
+        ("v", "f"),
+        ("dg", "2g"),
+        ("tio", "sio"),
+        ("tia", "sia"),
+        ("d", "t"),
+        ("ph", "fh"),
+        ("b", "p"),
+        ("sh", "s2"),
+        ("z", "s"),
+    ]
+    .iter()
+    {
+        code = code.replace(from, to);
+    }
+
+    // vowels are only kept at the start, `3` marks the others for now
+    if code.starts_with(|c| "aeiou".contains(c)) {
+        code.replace_range(..1, "A");
+    }
+    code = code.replace(|c| "aeiou".contains(c), "3").replace('j', "y");
+    replace_prefix(&mut code, "y3", "Y3");
+    replace_prefix(&mut code, "y", "A");
+    code = code
+        .replace('y', "3")
+        .replace("3gh3", "3kh3")
+        .replace("gh", "22")
+        .replace('g', "k");
+    for c in "stpkfmn".chars() {
+        code = collapse_runs(&code, c);
+    }
+
+    // `w`, `h`, `r` and `l` are only kept before vowels, `2` marks silent
+    // letters
+    code = code.replace("w3", "W3").replace("wh3", "Wh3");
+    replace_suffix(&mut code, "w", "3");
+    code = code.replace('w', "2");
+    replace_prefix(&mut code, "h", "A");
+    code = code.replace('h', "2").replace("r3", "R3");
+    replace_suffix(&mut code, "r", "3");
+    code = code.replace('r', "2").replace("l3", "L3");
+    replace_suffix(&mut code, "l", "3");
+    code = code.replace('l', "2").replace('2', "");
+    replace_suffix(&mut code, "3", "A");
+    code = code.replace('3', "");
+
+    code.truncate(CAVERPHONE_CODE_LEN);
+    while code.len() < CAVERPHONE_CODE_LEN {
+        code.push('1');
+    }
+    code
+}
+
+/// Replaces `from` with `to` if `s` starts with `from`.
+fn replace_prefix(s: &mut String, from: &str, to: &str) {
+    if s.starts_with(from) {
+        s.replace_range(..from.len(), to);
+    }
+}
+
+/// Replaces `from` with `to` if `s` ends with `from`.
+fn replace_suffix(s: &mut String, from: &str, to: &str) {
+    if s.ends_with(from) {
+        let start = s.len() - from.len();
+        s.replace_range(start.., to);
+    }
+}
+
+/// Replaces every run of the lowercase ASCII letter `c` with a single
+/// uppercase `c`.
+fn collapse_runs(s: &str, c: char) -> String {
+    let mut collapsed = String::with_capacity(s.len());
+    let mut prev = None;
+    for x in s.chars() {
+        if x == c {
+            if prev != Some(c) {
+                collapsed.push(c.to_ascii_uppercase());
+            }
+        } else {
+            collapsed.push(x);
+        }
+        prev = Some(x);
+    }
+    collapsed
+}
+
+/// A phonetic distance that compares the [`caverphone`] codes of two strings
+/// with the `inner` distance, [`Levenshtein`] by default.
+///
+/// The distance is `0` if both codes are equal, otherwise the `inner`
+/// distance between the codes tells how much they differ.
+///
+/// For other types than strings, the inputs are compared with the `inner`
+/// distance.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{Caverphone, DistanceMetric, DistanceValue, Jaro};
+/// assert_eq!(Caverphone::default().str_distance("Stevenson", "Stephenson"), DistanceValue::Exact(0));
+/// // "TMSN111111" and "TMS1111111"
+/// assert_eq!(Caverphone::default().str_distance("Tomson", "Thomas"), DistanceValue::Exact(1));
+/// assert_eq!(Caverphone::new(Jaro).str_distance("Peter", "Pieta"), 0.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Caverphone<D: DistanceMetric> {
+    /// The base distance to compare the codes with.
+    inner: D,
+}
+
+impl<D: DistanceMetric> Caverphone<D> {
+    /// Creates a new [`Caverphone`] distance that compares the codes with the
+    /// `inner` distance.
+    pub fn new(inner: D) -> Self {
+        Self { inner }
+    }
+}
+
+impl Default for Caverphone<Levenshtein> {
+    fn default() -> Self {
+        Self::new(Levenshtein::default())
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Caverphone<D> {
+    type Dist = <D as DistanceMetric>::Dist;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_distance(caverphone(a.as_ref()), caverphone(b.as_ref()))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.inner.normalized(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner
+            .str_normalized(caverphone(a.as_ref()), caverphone(b.as_ref()))
+    }
+}
+
+/// The maximum length of the codices of [`mra_codex`].
+const MRA_CODEX_LEN: usize = 6;
+
+/// Encodes `s` with the Match Rating Approach by Western Airlines.
+///
+/// All vowels but the first letter and the second letter of double letters
+/// are removed. Codices longer than six letters are reduced to their first
+/// and last three letters. Letters are compared case insensitive, all chars
+/// that are not ASCII letters are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::phonetic::mra_codex;
+/// assert_eq!(mra_codex("Byrne"), "BYRN");
+/// assert_eq!(mra_codex("Boern"), "BRN");
+/// assert_eq!(mra_codex("Catherine"), "CTHRN");
+/// assert_eq!(mra_codex("Schwarzenegger"), "SCHNGR");
+/// ```
+pub fn mra_codex(s: &str) -> String {
+    let mut codex: Vec<char> = Vec::new();
+    for c in s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+    {
+        if !codex.is_empty() && "AEIOU".contains(c) {
+            continue;
+        }
+        if codex.last() != Some(&c) {
+            codex.push(c);
+        }
+    }
+    if codex.len() > MRA_CODEX_LEN {
+        codex.drain(3..codex.len() - 3);
+    }
+    codex.into_iter().collect()
+}
+
+/// The minimum similarity rating of two [`mra_codex`] codices for a match,
+/// based on the sum of their lengths.
+fn mra_min_rating(len_sum: usize) -> usize {
+    match len_sum {
+        0..=4 => 5,
+        5..=7 => 4,
+        8..=11 => 3,
+        _ => 2,
+    }
+}
+
+/// Removes the chars that are equal at the same position in `a` and `b`.
+fn remove_positional_matches(a: &[char], b: &[char]) -> (Vec<char>, Vec<char>) {
+    let mut unmatched_a = Vec::with_capacity(a.len());
+    let mut unmatched_b = Vec::with_capacity(b.len());
+    for idx in 0..cmp::max(a.len(), b.len()) {
+        match (a.get(idx), b.get(idx)) {
+            (Some(x), Some(y)) if x == y => {}
+            (x, y) => {
+                unmatched_a.extend(x);
+                unmatched_b.extend(y);
+            }
+        }
+    }
+    (unmatched_a, unmatched_b)
+}
+
+/// A phonetic distance that compares the [`mra_codex`] codices of two strings
+/// with the comparison rules of the Match Rating Approach.
+///
+/// Codices whose lengths differ by 3 or more never match. Otherwise the chars
+/// that are equal at the same position are removed from both codices, first
+/// from left to right and then from right to left. The similarity rating is
+/// `6` minus the number of unmatched chars of the longer codex, both strings
+/// match if it reaches the minimum rating for the sum of the lengths of the
+/// codices.
+///
+/// The distance is `0.` if both strings match and `1.` otherwise. Equal
+/// codices always match, an empty codex matches only another empty codex.
+///
+/// For other types than strings, the inputs are compared for equality.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Mra};
+/// assert_eq!(Mra.str_distance("Byrne", "Boern"), 0.);
+/// assert_eq!(Mra.str_distance("Byrne", "Smith"), 1.);
+/// assert_eq!(Mra.rating("Byrne", "Boern"), Some(5));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mra;
+
+impl Mra {
+    /// The similarity rating of the [`mra_codex`] codices of `a` and `b`, a
+    /// value between `0` and `6`, or `None` if the lengths of the codices
+    /// differ by 3 or more.
+    pub fn rating(&self, a: &str, b: &str) -> Option<usize> {
+        let a: Vec<_> = mra_codex(a).chars().collect();
+        let b: Vec<_> = mra_codex(b).chars().collect();
+        Self::rating_codices(&a, &b)
+    }
+
+    /// The similarity rating of the codices `a` and `b`.
+    fn rating_codices(a: &[char], b: &[char]) -> Option<usize> {
+        if cmp::max(a.len(), b.len()) - cmp::min(a.len(), b.len()) >= 3 {
+            return None;
+        }
+        let (mut a, mut b) = remove_positional_matches(a, b);
+        a.reverse();
+        b.reverse();
+        let (a, b) = remove_positional_matches(&a, &b);
+        Some(MRA_CODEX_LEN.saturating_sub(cmp::max(a.len(), b.len())))
+    }
+}
+
+impl DistanceMetric for Mra {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        if a.into_iter().eq(b) {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = mra_codex(a.as_ref()).chars().collect();
+        let b: Vec<_> = mra_codex(b.as_ref()).chars().collect();
+        let matches = if a == b {
+            true
+        } else if a.is_empty() || b.is_empty() {
+            false
+        } else {
+            matches!(
+                Self::rating_codices(&a, &b),
+                Some(rating) if rating >= mra_min_rating(a.len() + b.len())
+            )
+        };
+        if matches {
+            0.
+        } else {
+            1.
+        }
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The letter groups of [`Editex`], letters of the same group sound similar.
+/// A letter may be part of multiple groups.
+const EDITEX_GROUPS: [&str; 10] = [
+    "AEIOUY", "BP", "CKQ", "DT", "LR", "MN", "GJ", "FPV", "SXZ", "CSZ",
+];
+
+/// The Editex distance by Zobel and Dart, an edit distance that charges less
+/// for replacing letters that sound similar.
+///
+/// Replacing a letter costs `0` if both letters are equal, `1` if both are
+/// part of the same letter group, e.g. `'D'` and `'T'` or `'B'` and `'P'`,
+/// and `2` otherwise. Inserting or deleting a letter costs the same as
+/// replacing the preceding letter with it, where the first letter is preceded
+/// by a space. Since `H` and `W` are often silent, a letter that follows them
+/// costs at most `1`. Letters are compared case insensitive.
+///
+/// The normalized distance divides by twice the length of the longer str.
+///
+/// For other types than strings, there are no groups: replacing, inserting or
+/// deleting an element costs `2`, unless it equals the other or the preceding
+/// element.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Editex};
+/// assert_eq!(Editex.str_distance("Niall", "Neil"), 2);
+/// assert_eq!(Editex.str_distance("Tom", "Dom"), 1);
+/// assert_eq!(Editex.str_distance("Tom", "Mom"), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Editex;
+
+impl Editex {
+    /// The distance between two str of uppercase chars.
+    fn distance_chars(a: &[char], b: &[char]) -> usize {
+        editex(&editex_indel_costs(a), &editex_indel_costs(b), |i, j| {
+            editex_replace(a[i], b[j])
+        })
+    }
+}
+
+/// The cost of replacing `a` with `b`.
+fn editex_replace(a: char, b: char) -> usize {
+    if a == b {
+        0
+    } else if EDITEX_GROUPS
+        .iter()
+        .any(|group| group.contains(a) && group.contains(b))
+    {
+        1
+    } else {
+        2
+    }
+}
+
+/// The cost of inserting or deleting `c` after `prev`.
+fn editex_indel(prev: char, c: char) -> usize {
+    if (prev == 'H' || prev == 'W') && prev != c {
+        1
+    } else {
+        editex_replace(prev, c)
+    }
+}
+
+/// The cost of inserting or deleting each char of `s`, the first char is
+/// preceded by a space.
+fn editex_indel_costs(s: &[char]) -> Vec<usize> {
+    core::iter::once(' ')
+        .chain(s.iter().copied())
+        .zip(s.iter().copied())
+        .map(|(prev, c)| editex_indel(prev, c))
+        .collect()
+}
+
+/// The Editex distance of two inputs, given the costs of inserting or
+/// deleting each element and the cost of replacing the element `i` of the
+/// first with the element `j` of the second input.
+fn editex(indel_a: &[usize], indel_b: &[usize], replace: impl Fn(usize, usize) -> usize) -> usize {
+    // the first row of the matrix, inserting the elements of `b`
+    let mut row: Vec<usize> = core::iter::once(0)
+        .chain(indel_b.iter().scan(0, |sum, cost| {
+            *sum += cost;
+            Some(*sum)
+        }))
+        .collect();
+
+    for (i, indel_a) in indel_a.iter().enumerate() {
+        let mut diag = row[0];
+        row[0] += indel_a;
+        for (j, indel_b) in indel_b.iter().enumerate() {
+            let dist = cmp::min(
+                cmp::min(row[j + 1] + indel_a, row[j] + indel_b),
+                diag + replace(i, j),
+            );
+            diag = row[j + 1];
+            row[j + 1] = dist;
+        }
+    }
+    row[indel_b.len()]
+}
+
+/// The uppercase chars of `s`.
+fn uppercase(s: &str) -> Vec<char> {
+    s.chars().flat_map(char::to_uppercase).collect()
+}
+
+/// Normalizes the Editex distance by twice the length of the longer input.
+fn normalize_editex(dist: usize, len_a: usize, len_b: usize) -> f64 {
+    if len_a + len_b == 0 {
+        0.
+    } else {
+        dist as f64 / (2 * cmp::max(len_a, len_b)) as f64
+    }
+}
+
+impl DistanceMetric for Editex {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        // the first element is never equal to the preceding space
+        let indel_costs = |len: usize, eq_prev: &dyn Fn(usize) -> bool| -> Vec<usize> {
+            (0..len)
+                .map(|i| if i > 0 && eq_prev(i) { 0 } else { 2 })
+                .collect()
+        };
+        editex(
+            &indel_costs(a.len(), &|i| a[i - 1] == a[i]),
+            &indel_costs(b.len(), &|j| b[j - 1] == b[j]),
+            |i, j| if a[i] == b[j] { 0 } else { 2 },
+        )
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        Editex::distance_chars(&uppercase(a.as_ref()), &uppercase(b.as_ref()))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let (len_a, len_b) = (a.clone().count(), b.clone().count());
+        normalize_editex(self.distance(a, b), len_a, len_b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (uppercase(a.as_ref()), uppercase(b.as_ref()));
+        normalize_editex(Editex::distance_chars(&a, &b), a.len(), b.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editex() {
+        assert_eq!(Editex.str_distance("cat", "hat"), 2);
+        assert_eq!(Editex.str_distance("Niall", "Neil"), 2);
+        assert_eq!(Editex.str_distance("aluminum", "Catalan"), 12);
+        assert_eq!(Editex.str_distance("ATCG", "TAGC"), 6);
+        assert_eq!(Editex.str_distance("", ""), 0);
+        assert_eq!(Editex.str_distance("", "ab"), 4);
+        assert_eq!(Editex.str_distance("Smith", "SMITH"), 0);
+        // the second `L` is deleted for free after the first one
+        assert_eq!(Editex.str_distance("Nial", "Niall"), 0);
+        // a letter after a silent `H` costs 1
+        assert_eq!(Editex.str_distance("Tomas", "Thomas"), 2);
+        assert_eq!(Editex.str_normalized("", ""), 0.);
+        assert_eq!(Editex.str_normalized("cat", "hat"), 2. / 6.);
+        assert_eq!(Editex.str_normalized("abc", "dfg"), 1.);
+
+        // other types are compared without groups
+        assert_eq!(Editex.distance(&[1, 2, 3], &[1, 2, 3]), 0);
+        assert_eq!(Editex.distance(&[1, 2, 3], &[1, 4, 3]), 2);
+        assert_eq!(Editex.distance(&[1, 2, 2], &[1, 2]), 0);
+        assert_eq!(Editex.normalized(&[1, 2], &[3, 4]), 1.);
+    }
+
+    #[test]
+    fn editex_ranks_phonetically() {
+        let lev = crate::Levenshtein::default();
+        for (a, similar, different) in [
+            ("Tom", "Dom", "Mom"),
+            ("Bart", "Part", "Cart"),
+            ("Smith", "Smyth", "Smish"),
+            ("Carl", "Karl", "Darl"),
+        ]
+        .iter()
+        {
+            // Levenshtein can't tell the replacements apart
+            assert_eq!(lev.str_distance(a, similar), lev.str_distance(a, different));
+            assert!(Editex.str_distance(a, similar) < Editex.str_distance(a, different));
+        }
+    }
+
+    #[test]
+    fn soundex_codes() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Rubin"), "R150");
+        // `H` and `W` do not separate letters with the same digit
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Ashcroft"), "A261");
+        // vowels do
+        assert_eq!(soundex("Tymczak"), "T522");
+        // the first letter shares its digit with the following letter
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Honeyman"), "H555");
+        assert_eq!(soundex("o'hara"), "O600");
+        assert_eq!(soundex("Müller"), "M460");
+        assert_eq!(soundex(""), "");
+    }
+
+    #[test]
+    fn soundex_distance() {
+        assert_eq!(Soundex.str_distance("Robert", "Rupert"), 0.);
+        assert_eq!(Soundex.str_distance("Ashcraft", "Ashcroft"), 0.);
+        assert_eq!(Soundex.str_distance("Robert", "Rubin"), 1.);
+        assert_eq!(Soundex.str_normalized("Tymczak", "tymczak"), 0.);
+        assert_eq!(Soundex.distance(&[1, 2], &[1, 2]), 0.);
+        assert_eq!(Soundex.distance(&[1, 2], &[1, 3]), 1.);
+        // strs without letters have an empty code
+        assert_eq!(Soundex.str_distance("123", "!!"), 1.);
+        assert_eq!(Soundex.str_distance("", "Robert"), 1.);
+        assert_eq!(Soundex.str_distance("123", "123"), 0.);
+        assert_eq!(Soundex.str_distance("", ""), 0.);
+    }
+
+    fn assert_keys(s: &str, primary: &str, alternate: Option<&str>) {
+        let keys = double_metaphone(s);
+        assert_eq!(
+            (keys.0.as_str(), keys.1.as_deref()),
+            (primary, alternate),
+            "{}",
+            s
+        );
+    }
+
+    #[test]
+    fn double_metaphone_keys() {
+        assert_keys("Smith", "SM0", Some("XMT"));
+        assert_keys("Schmidt", "XMT", Some("SMT"));
+        assert_keys("Thompson", "TMPS", None);
+        assert_keys("Jose", "HS", None);
+        assert_keys("Xavier", "SF", Some("SFR"));
+        assert_keys("Knight", "NT", None);
+        assert_keys("Michael", "MKL", Some("MXL"));
+        assert_keys("Wasserman", "ASRM", Some("FSRM"));
+        assert_keys("Cabrillo", "KPRL", Some("KPR"));
+        assert_keys("Caesar", "SSR", None);
+        assert_keys("Laugh", "LF", None);
+        assert_keys("Filipowicz", "FLPT", Some("FLPF"));
+        assert_keys("", "", None);
+    }
+
+    #[test]
+    fn caverphone_codes() {
+        assert_eq!(caverphone("Thompson"), "TMPSN11111");
+        assert_eq!(caverphone("Tomson"), "TMSN111111");
+        assert_eq!(caverphone("Stevenson"), "STFNSN1111");
+        assert_eq!(caverphone("Peter"), "PTA1111111");
+        assert_eq!(caverphone("Lee"), "LA11111111");
+        assert_eq!(caverphone("Tom"), "TM11111111");
+        assert_eq!(caverphone("Anastha"), "ANSTA11111");
+        assert_eq!(caverphone("Davis-Carter"), "TFSKTA1111");
+        assert_eq!(caverphone("Escarmant"), "ASKMNT1111");
+        assert_eq!(caverphone("McCall-Reid"), "MKRT111111");
+        assert_eq!(caverphone("Saxon"), "SKN1111111");
+        assert_eq!(caverphone("Scott"), "SKT1111111");
+        assert_eq!(caverphone("Mclaverty"), "MKLFTA1111");
+        assert_eq!(caverphone("Enough"), "ANF1111111");
+        assert_eq!(caverphone(""), "1111111111");
+        assert_eq!(caverphone("123"), "1111111111");
+    }
+
+    #[test]
+    fn caverphone_distance() {
+        let caverphone = Caverphone::default();
+        assert_eq!(
+            caverphone.str_distance("Smith", "Smyth"),
+            crate::DistanceValue::Exact(0)
+        );
+        assert_eq!(
+            caverphone.str_distance("Stevenson", "Stephenson"),
+            crate::DistanceValue::Exact(0)
+        );
+        assert_eq!(caverphone.str_normalized("Peter", "peter"), 0.);
+        assert_eq!(
+            caverphone.str_distance("Tomson", "Thomas"),
+            crate::DistanceValue::Exact(1)
+        );
+        assert_eq!(caverphone.str_normalized("Tomson", "Thomas"), 0.1);
+        assert_eq!(
+            caverphone.distance(&[1, 2], &[1, 3]),
+            crate::DistanceValue::Exact(1)
+        );
+    }
+
+    #[test]
+    fn mra_codices() {
+        assert_eq!(mra_codex("Byrne"), "BYRN");
+        assert_eq!(mra_codex("Boern"), "BRN");
+        assert_eq!(mra_codex("Smith"), "SMTH");
+        assert_eq!(mra_codex("Smyth"), "SMYTH");
+        assert_eq!(mra_codex("Catherine"), "CTHRN");
+        assert_eq!(mra_codex("Kathryn"), "KTHRYN");
+        // the first vowel is kept, double letters are reduced
+        assert_eq!(mra_codex("Aubrey"), "ABRY");
+        assert_eq!(mra_codex("Gibbons"), "GBNS");
+        // the first and last three letters
+        assert_eq!(mra_codex("Schwarzenegger"), "SCHNGR");
+        assert_eq!(mra_codex("o'Neill"), "ONL");
+        assert_eq!(mra_codex(""), "");
+    }
+
+    #[test]
+    fn mra_distance() {
+        assert_eq!(Mra.str_distance("Byrne", "Boern"), 0.);
+        assert_eq!(Mra.rating("Byrne", "Boern"), Some(5));
+        assert_eq!(Mra.str_distance("Smith", "Smyth"), 0.);
+        assert_eq!(Mra.rating("Smith", "Smyth"), Some(5));
+        assert_eq!(Mra.str_distance("Catherine", "Kathryn"), 0.);
+        assert_eq!(Mra.rating("Catherine", "Kathryn"), Some(4));
+        assert_eq!(Mra.str_distance("Byrne", "Smith"), 1.);
+        assert_eq!(Mra.rating("Byrne", "Smith"), Some(2));
+        // lengths of the codices differ by 3
+        assert_eq!(Mra.rating("Al", "Schwarzenegger"), None);
+        assert_eq!(Mra.str_distance("Al", "Schwarzenegger"), 1.);
+        assert_eq!(Mra.str_distance("", ""), 0.);
+        assert_eq!(Mra.str_distance("", "A"), 1.);
+        assert_eq!(Mra.distance(&[1, 2], &[1, 2]), 0.);
+        assert_eq!(Mra.distance(&[1, 2], &[1, 3]), 1.);
+    }
+
+    #[test]
+    fn double_metaphone_distance() {
+        assert_eq!(DoubleMetaphone.str_distance("Smith", "Schmidt"), 0.);
+        assert_eq!(DoubleMetaphone.str_distance("Snider", "Schneider"), 0.);
+        assert_eq!(DoubleMetaphone.str_distance("Wasserman", "Vasserman"), 0.);
+        assert_eq!(DoubleMetaphone.str_distance("Arnow", "Arnoff"), 0.);
+        assert_eq!(DoubleMetaphone.str_distance("Smith", "Jones"), 1.);
+        assert_eq!(DoubleMetaphone.str_normalized("Thompson", "thompson"), 0.);
+        assert_eq!(DoubleMetaphone.distance(&[1, 2], &[1, 3]), 1.);
+    }
+}
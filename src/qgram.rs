@@ -1,5 +1,38 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::utils::{eq_by, sqrt, Map};
 use crate::DistanceMetric;
-use std::cmp;
+use core::cmp;
+use core::fmt;
+use core::hash::Hash;
+use core::iter;
+
+/// The error of the fallible constructors of the q-gram metrics, e.g.
+/// [`QGram::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QGramError {
+    /// The length of the q-grams is 0.
+    ZeroLength,
+    /// A weight of [`Tversky`] is negative.
+    NegativeWeight,
+    /// A weight of [`Tversky`] is NaN.
+    NanWeight,
+}
+
+impl fmt::Display for QGramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QGramError::ZeroLength => f.write_str("the length of the q-grams must not be 0"),
+            QGramError::NegativeWeight => f.write_str("the weights must not be negative"),
+            QGramError::NanWeight => f.write_str("the weights must not be NaN"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QGramError {}
 
 /// Represents a QGram metric where `q` is the length of a q-gram fragment.
 ///
@@ -15,17 +48,175 @@ use std::cmp;
 pub struct QGram {
     /// Length of the fragment
     q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
 }
 
 impl QGram {
-    /// Creates a new [`QGram]` of length `q`.
+    /// Creates a new [`QGram`] of length `q`.
     ///
     /// # Panics
     ///
     /// Panics if `q` is 0.
     pub fn new(q: usize) -> Self {
-        assert_ne!(q, 0);
-        Self { q }
+        Self::try_new(q).unwrap()
+    }
+
+    /// Creates a new [`QGram`] of length `q`, or an error if `q` is 0.
+    pub fn try_new(q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self { q, padded: false })
+    }
+
+    /// Creates a new [`QGram`] of length `q`, that pads the inputs with `q - 1`
+    /// boundary markers on both sides.
+    ///
+    /// The markers are synthetic elements that are distinct from all elements
+    /// of the inputs, e.g. the padded bigrams of `"abc"` are `#a`, `ab`, `bc`
+    /// and `c#`. This way the prefixes and suffixes of the inputs form q-grams
+    /// of their own and the first and last elements appear in as many q-grams
+    /// as all others, which matters for short inputs. Empty inputs are not
+    /// padded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, QGram};
+    /// assert_eq!(QGram::new(2).str_distance("abc", "cab"), 2);
+    /// assert_eq!(QGram::with_padding(2).str_distance("abc", "cab"), 6);
+    /// ```
+    pub fn with_padding(q: usize) -> Self {
+        Self::try_with_padding(q).unwrap()
+    }
+
+    /// Creates a new [`QGram`] like [`QGram::with_padding`], or an error if `q`
+    /// is 0.
+    pub fn try_with_padding(q: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q)?
+        })
+    }
+
+    /// The frequency vector of `s`, i.e. all distinct q-grams of `s` and how
+    /// often they appear, ordered by the q-grams.
+    ///
+    /// Like [`QGramProfile::iter`], the q-grams of padded inputs that contain
+    /// boundary markers are not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::QGram;
+    /// assert_eq!(
+    ///     QGram::new(2).profile_vec("banana"),
+    ///     vec![("an".to_string(), 2), ("ba".to_string(), 1), ("na".to_string(), 2)]
+    /// );
+    /// ```
+    pub fn profile_vec(&self, s: &str) -> Vec<(String, usize)> {
+        let mut counts: Vec<_> = self
+            .profile(s)
+            .iter()
+            .map(|(qgram, n)| (qgram.iter().collect::<String>(), n))
+            .collect();
+        counts.sort_unstable();
+        counts
+    }
+
+    /// Evaluates the distance between the slices `a` and `b`.
+    ///
+    /// Same as [`DistanceMetric::distance`], but the q-grams are taken from
+    /// the borrowed slices directly instead of collecting the inputs first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, QGram};
+    /// let a = ["the", "quick", "brown", "fox"];
+    /// let b = ["the", "quick", "red", "fox"];
+    /// assert_eq!(QGram::new(2).distance_slice(&a, &b), 4);
+    /// assert_eq!(QGram::new(2).distance_slice(&a, &b), QGram::new(2).distance(&a, &b));
+    /// ```
+    pub fn distance_slice<A, B>(&self, a: &[A], b: &[B]) -> usize
+    where
+        A: PartialEq + PartialEq<B>,
+        B: PartialEq,
+    {
+        slice_distance(self, a, b)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, QGram};
+    /// let a = vec![1u32, 2, 3, 4];
+    /// let b = vec![1u32, 2, 4, 3];
+    /// assert_eq!(QGram::new(2).distance_hashed(&a, &b), 4);
+    /// assert_eq!(QGram::new(2).distance_hashed(&a, &b), QGram::new(2).distance(&a, &b));
+    /// ```
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> usize
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
+    }
+
+    /// Counts the distinct q-grams of both inputs, to compute set based
+    /// coefficients like [`Overlap`] or [`Jaccard`] from them.
+    ///
+    /// Returns a tuple of the number of distinct q-grams in `a`, the number
+    /// of distinct q-grams in `b` and the number of distinct q-grams that
+    /// occur in both. Unlike the free function [`set_counts`] this respects
+    /// the padding, like the `set_counts` methods of the set based metrics,
+    /// e.g. [`Jaccard::set_counts`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::QGram;
+    /// // only "ht" occurs in both
+    /// assert_eq!(QGram::new(2).overlap_counts("night".chars(), "nacht".chars()), (4, 4, 1));
+    /// ```
+    pub fn overlap_counts<S, T>(&self, a: S, b: T) -> (usize, usize, usize)
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        padded_set_counts(self, a, b)
+    }
+
+    /// The total number of q-grams of two inputs with `len_a` and `len_b`
+    /// elements, or `None` if an input is too short to normalize the distance
+    /// by it.
+    fn num_qgrams(&self, len_a: usize, len_b: usize) -> Option<usize> {
+        // the count is only evaluated for inputs that are long enough, since it
+        // underflows for shorter ones
+        if self.padded {
+            // a padded input has `len + q - 1` q-grams, unless it is empty
+            (cmp::min(len_a, len_b) > 0).then(|| len_a + len_b + 2 * self.q - 2)
+        } else {
+            (cmp::min(len_a, len_b) > self.q).then(|| len_a + len_b + 2 - 2 * self.q)
+        }
     }
 }
 
@@ -43,14 +234,7 @@ impl DistanceMetric for QGram {
     {
         let a: Vec<_> = a.into_iter().collect();
         let b: Vec<_> = b.into_iter().collect();
-
-        let iter_a = QGramIter::new(&a, self.q);
-        let iter_b = QGramIter::new(&b, self.q);
-
-        eq_map(iter_a, iter_b)
-            .into_iter()
-            .map(|(n1, n2)| if n1 > n2 { n1 - n2 } else { n2 - n1 })
-            .sum()
+        self.distance_slice(&a, &b)
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -68,15 +252,93 @@ impl DistanceMetric for QGram {
         let len_a = a.clone().count();
         let len_b = b.clone().count();
 
-        if cmp::min(len_a, len_b) <= self.q {
-            if a.eq(b) {
-                0.
-            } else {
-                1.
-            }
+        match self.num_qgrams(len_a, len_b) {
+            Some(norm) => self.distance(a, b) as f64 / norm as f64,
+            None if a.eq(b) => 0.,
+            None => 1.,
+        }
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
         } else {
-            self.distance(a, b) as f64 / (len_a + len_b - 2 * self.q + 2) as f64
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().chars().collect();
+        let b: Vec<_> = b.as_ref().chars().collect();
+
+        match self.num_qgrams(a.len(), b.len()) {
+            Some(norm) => {
+                qgram_distance(padded_eq_map_hashed(self.q, self.padded, &a, &b)) as f64
+                    / norm as f64
+            }
+            None if a == b => 0.,
+            None => 1.,
+        }
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().chars().collect();
+        let b: Vec<_> = b.as_ref().chars().collect();
+
+        let dist = qgram_distance(padded_eq_map_hashed(self.q, self.padded, &a, &b));
+        let normalized = match self.num_qgrams(a.len(), b.len()) {
+            Some(norm) => dist as f64 / norm as f64,
+            None if a == b => 0.,
+            None => 1.,
+        };
+        (dist, normalized)
+    }
+
+    fn str_distance_within<S, T>(&self, a: S, b: T, threshold: f64) -> Option<f64>
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let (len_a, len_b) = (a.chars().count(), b.chars().count());
+
+        let norm = match self.num_qgrams(len_a, len_b) {
+            Some(norm) => norm as f64,
+            None => {
+                let dist = if a == b { 0. } else { 1. };
+                return Some(dist).filter(|dist| *dist <= threshold);
+            }
+        };
+        // the inputs have `|len_a - len_b|` more or less q-grams, every one of
+        // them adds at least 1 to the distance
+        if len_a.abs_diff(len_b) as f64 / norm > threshold {
+            return None;
         }
+
+        let a: Vec<_> = a.chars().collect();
+        let b: Vec<_> = b.chars().collect();
+        let mut dist = 0;
+        for (n1, n2) in padded_eq_map_hashed(self.q, self.padded, &a, &b) {
+            dist += n1.abs_diff(n2);
+            if dist as f64 / norm > threshold {
+                // the partial sum already exceeds the threshold
+                return None;
+            }
+        }
+        Some(dist as f64 / norm)
     }
 }
 
@@ -89,24 +351,82 @@ impl DistanceMetric for QGram {
 /// where `v(s, q)` denotes the vec on the space of q-grams of length q,
 /// that contains the  number of times a q-gram appears for the str s.
 ///
-/// If both inputs are empty a value of `0.` is returned. If one input is empty
-/// and the other is not, a value of `1.` is returned. This avoids a return of
-/// `f64::NaN` for those cases.
+/// If an input has no q-grams, i.e. it is empty or shorter than `q` without
+/// padding, a value of `0.` is returned if both inputs are equal and `1.`
+/// otherwise. This avoids a return of `f64::NaN` for those cases.
 #[derive(Debug, Clone)]
 pub struct Cosine {
     /// Length of the fragment
     q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
 }
 
 impl Cosine {
-    /// Creates a new [`Cosine]` metric of length `q`.
+    /// Creates a new [`Cosine`] metric of length `q`.
     ///
     /// # Panics
     ///
     /// Panics if `q` is 0.
     pub fn new(q: usize) -> Self {
-        assert_ne!(q, 0);
-        Self { q }
+        Self::try_new(q).unwrap()
+    }
+
+    /// Creates a new [`Cosine`] of length `q`, or an error if `q` is 0.
+    pub fn try_new(q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self { q, padded: false })
+    }
+
+    /// Creates a new [`Cosine`] of length `q`, that pads the inputs with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(q: usize) -> Self {
+        Self::try_with_padding(q).unwrap()
+    }
+
+    /// Creates a new [`Cosine`] like [`Cosine::with_padding`], or an error if
+    /// `q` is 0.
+    pub fn try_with_padding(q: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q)?
+        })
+    }
+
+    /// Evaluates the distance between the slices `a` and `b`.
+    ///
+    /// Same as [`DistanceMetric::distance`], but the q-grams are taken from
+    /// the borrowed slices directly instead of collecting the inputs first.
+    pub fn distance_slice<A, B>(&self, a: &[A], b: &[B]) -> f64
+    where
+        A: PartialEq + PartialEq<B>,
+        B: PartialEq,
+    {
+        slice_distance(self, a, b)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
     }
 }
 
@@ -124,22 +444,7 @@ impl DistanceMetric for Cosine {
     {
         let a: Vec<_> = a.into_iter().collect();
         let b: Vec<_> = b.into_iter().collect();
-
-        // edge case where an input is empty
-        if a.is_empty() || b.is_empty() {
-            return if a.len() == b.len() { 0. } else { 1. };
-        }
-
-        let iter_a = QGramIter::new(&a, self.q);
-        let iter_b = QGramIter::new(&b, self.q);
-
-        let (norm_a, norm_b, norm_prod) = eq_map(iter_a, iter_b).into_iter().fold(
-            (0usize, 0usize, 0usize),
-            |(norm_a, norm_b, norm_prod), (n1, n2)| {
-                (norm_a + n1 * n1, norm_b + n2 * n2, norm_prod + n1 * n2)
-            },
-        );
-        1.0 - norm_prod as f64 / ((norm_a as f64).sqrt() * (norm_b as f64).sqrt())
+        self.distance_slice(&a, &b)
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -151,7 +456,36 @@ impl DistanceMetric for Cosine {
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
     {
-        normalized_qgram(self, self.q, a, b)
+        normalized_qgram(self, self.q, self.padded, a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
+        } else {
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        normalized_qgram_str(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        qgram_str_both(self, self.q, self.padded, a.as_ref(), b.as_ref())
     }
 }
 
@@ -165,24 +499,82 @@ impl DistanceMetric for Cosine {
 ///
 /// where ``Q(s, q)``  denotes the set of q-grams of length n for the str s.
 ///
-/// If both inputs are empty a value of `0.` is returned. If one input is empty
-/// and the other is not, a value of `1.` is returned. This avoids a return of
-/// `f64::NaN` for those cases.
+/// If an input has no q-grams, i.e. it is empty or shorter than `q` without
+/// padding, a value of `0.` is returned if both inputs are equal and `1.`
+/// otherwise. This avoids a return of `f64::NaN` for those cases.
 #[derive(Debug, Clone)]
 pub struct Jaccard {
     /// Length of the fragment
     q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
 }
 
 impl Jaccard {
-    /// Creates a new [`Jaccard]` of length `q`.
+    /// Creates a new [`Jaccard`] of length `q`.
     ///
     /// # Panics
     ///
     /// Panics if `q` is 0.
     pub fn new(q: usize) -> Self {
-        assert_ne!(q, 0);
-        Self { q }
+        Self::try_new(q).unwrap()
+    }
+
+    /// Creates a new [`Jaccard`] of length `q`, or an error if `q` is 0.
+    pub fn try_new(q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self { q, padded: false })
+    }
+
+    /// Creates a new [`Jaccard`] of length `q`, that pads the inputs with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(q: usize) -> Self {
+        Self::try_with_padding(q).unwrap()
+    }
+
+    /// Creates a new [`Jaccard`] like [`Jaccard::with_padding`], or an error if
+    /// `q` is 0.
+    pub fn try_with_padding(q: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q)?
+        })
+    }
+
+    /// Evaluates the distance between the slices `a` and `b`.
+    ///
+    /// Same as [`DistanceMetric::distance`], but the q-grams are taken from
+    /// the borrowed slices directly instead of collecting the inputs first.
+    pub fn distance_slice<A, B>(&self, a: &[A], b: &[B]) -> f64
+    where
+        A: PartialEq + PartialEq<B>,
+        B: PartialEq,
+    {
+        slice_distance(self, a, b)
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
     }
 }
 
@@ -200,18 +592,7 @@ impl DistanceMetric for Jaccard {
     {
         let a: Vec<_> = a.into_iter().collect();
         let b: Vec<_> = b.into_iter().collect();
-
-        // edge case where an input is empty
-        if a.is_empty() || b.is_empty() {
-            return if a.len() == b.len() { 0. } else { 1. };
-        }
-
-        let iter_a = QGramIter::new(&a, self.q);
-        let iter_b = QGramIter::new(&b, self.q);
-
-        let (num_dist_a, num_dist_b, num_intersect) = count_distinct_intersect(iter_a, iter_b);
-
-        1.0 - num_intersect as f64 / ((num_dist_a + num_dist_b) as f64 - num_intersect as f64)
+        self.distance_slice(&a, &b)
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -223,50 +604,109 @@ impl DistanceMetric for Jaccard {
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
     {
-        normalized_qgram(self, self.q, a, b)
+        normalized_qgram(self, self.q, self.padded, a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
+        } else {
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        normalized_qgram_str(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        qgram_str_both(self, self.q, self.padded, a.as_ref(), b.as_ref())
     }
 }
 
-/// Represents a SorensenDice metric where `q` is the length of a q-gram
-/// fragment.
+/// A [`Jaccard`] distance that picks the length of the q-grams per comparison
+/// from the length of the shorter input, so that short inputs are compared by
+/// shorter q-grams.
 ///
-/// The distance corresponds to
+/// The length of the q-grams is
 ///
 /// ```text
-///     1 - 2 * |Q(s1, q) ∩ Q(s2, q)|  / (|Q(s1, q)| + |Q(s2, q))|)
+///     min(max_q, max(1, min(|s1|, |s2|) / len_per_q))
 /// ```
 ///
-/// where `Q(s, q)`  denotes the set of q-grams of length n for the str s
+/// so every input with at least one element has q-grams. This avoids the
+/// jump to a distance of `1.` that a fixed `q` produces as soon as an input is
+/// shorter than `q`, e.g. for a single substituted char.
 ///
-/// If both inputs are empty a value of `1.` is returned. If one input is empty
-/// and the other is not, a value of `0.` is returned. This avoids a return of
-/// `f64::NaN` for those cases.
+/// If both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{AdaptiveJaccard, DistanceMetric, Jaccard};
+/// let adaptive = AdaptiveJaccard::new(2, 4);
+/// // unigrams for inputs shorter than 8 chars
+/// assert_eq!(adaptive.str_distance("ab", "ac"), Jaccard::new(1).str_distance("ab", "ac"));
+/// assert_eq!(Jaccard::new(2).str_normalized("ab", "ac"), 1.);
+/// // bigrams for longer ones
+/// assert_eq!(adaptive.str_distance("nachtzug", "nightjar"), Jaccard::new(2).str_distance("nachtzug", "nightjar"));
+/// ```
 #[derive(Debug, Clone)]
-pub struct SorensenDice {
-    /// Length of the fragment
-    q: usize,
+pub struct AdaptiveJaccard {
+    /// The maximum length of the q-grams.
+    max_q: usize,
+    /// The number of elements of the shorter input per element of a q-gram.
+    len_per_q: usize,
 }
 
-impl SorensenDice {
-    /// Creates a new [`SorensenDice]` of length `q`.
+impl AdaptiveJaccard {
+    /// Creates a new [`AdaptiveJaccard`], whose q-grams have one element per
+    /// `len_per_q` elements of the shorter input, but at most `max_q`.
     ///
     /// # Panics
     ///
-    /// Panics if `q` is 0.
-    pub fn new(q: usize) -> Self {
-        assert_ne!(q, 0);
-        Self { q }
+    /// Panics if `max_q` or `len_per_q` is 0.
+    pub fn new(max_q: usize, len_per_q: usize) -> Self {
+        assert_ne!(max_q, 0);
+        assert_ne!(len_per_q, 0);
+        Self { max_q, len_per_q }
     }
-}
 
-impl Default for SorensenDice {
-    /// Use a bigram as default fragment length.
-    fn default() -> Self {
-        SorensenDice::new(2)
+    /// The maximum length of the q-grams.
+    pub fn max_q(&self) -> usize {
+        self.max_q
+    }
+
+    /// The number of elements of the shorter input per element of a q-gram.
+    pub fn len_per_q(&self) -> usize {
+        self.len_per_q
+    }
+
+    /// The length of the q-grams to compare inputs with `len_a` and `len_b`
+    /// elements with.
+    pub fn q(&self, len_a: usize, len_b: usize) -> usize {
+        cmp::min(
+            self.max_q,
+            cmp::max(1, cmp::min(len_a, len_b) / self.len_per_q),
+        )
     }
 }
 
-impl DistanceMetric for SorensenDice {
+impl DistanceMetric for AdaptiveJaccard {
     type Dist = f64;
 
     fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
@@ -280,17 +720,17 @@ impl DistanceMetric for SorensenDice {
     {
         let a: Vec<_> = a.into_iter().collect();
         let b: Vec<_> = b.into_iter().collect();
+        Jaccard::new(self.q(a.len(), b.len())).distance_slice(&a, &b)
+    }
 
-        // edge case where an input is empty
-        if a.is_empty() || b.is_empty() {
-            return if a.len() == b.len() { 0. } else { 1. };
-        }
-
-        let iter_a = QGramIter::new(&a, self.q);
-        let iter_b = QGramIter::new(&b, self.q);
-
-        let (num_dist_a, num_dist_b, num_intersect) = count_distinct_intersect(iter_a, iter_b);
-        1.0 - 2.0 * num_intersect as f64 / (num_dist_a + num_dist_b) as f64
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        let q = self.q(a.chars().count(), b.chars().count());
+        Jaccard::new(q).str_distance(a, b)
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -302,50 +742,104 @@ impl DistanceMetric for SorensenDice {
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
     {
-        normalized_qgram(self, self.q, a, b)
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
     }
 }
 
-/// Represents a Overlap metric where `q` is the length of a q-gram
+/// Represents a SorensenDice metric where `q` is the length of a q-gram
 /// fragment.
 ///
 /// The distance corresponds to
 ///
 /// ```text
-///     1 - |Q(s1, q) ∩ Q(s2, q)|  / min(|Q(s1, q)|, |Q(s2, q)|)
+///     1 - 2 * |Q(s1, q) ∩ Q(s2, q)|  / (|Q(s1, q)| + |Q(s2, q))|)
 /// ```
 ///
 /// where `Q(s, q)`  denotes the set of q-grams of length n for the str s
 ///
-/// If both inputs are empty a value of `1.` is returned. If one input is empty
-/// and the other is not, a value of `0.` is returned. This avoids a return of
-/// `f64::NaN` for those cases.
+/// If an input has no q-grams, i.e. it is empty or shorter than `q` without
+/// padding, a value of `0.` is returned if both inputs are equal and `1.`
+/// otherwise. This avoids a return of `f64::NaN` for those cases.
 #[derive(Debug, Clone)]
-pub struct Overlap {
+pub struct SorensenDice {
     /// Length of the fragment
     q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
 }
 
-impl Overlap {
-    /// Creates a new [`Overlap]` of length `q`.
+impl SorensenDice {
+    /// Creates a new [`SorensenDice`] of length `q`.
     ///
     /// # Panics
     ///
     /// Panics if `q` is 0.
     pub fn new(q: usize) -> Self {
-        assert_ne!(q, 0);
-        Self { q }
+        Self::try_new(q).unwrap()
+    }
+
+    /// Creates a new [`SorensenDice`] of length `q`, or an error if `q` is 0.
+    pub fn try_new(q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self { q, padded: false })
+    }
+
+    /// Creates a new [`SorensenDice`] of length `q`, that pads the inputs with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(q: usize) -> Self {
+        Self::try_with_padding(q).unwrap()
+    }
+
+    /// Creates a new [`SorensenDice`] like [`SorensenDice::with_padding`], or
+    /// an error if `q` is 0.
+    pub fn try_with_padding(q: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q)?
+        })
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
     }
 }
 
-impl Default for Overlap {
-    /// Use a monogram as default overlap fragment length.
+impl Default for SorensenDice {
+    /// Use a bigram as default fragment length.
     fn default() -> Self {
-        Overlap::new(1)
+        SorensenDice::new(2)
     }
 }
 
-impl DistanceMetric for Overlap {
+impl DistanceMetric for SorensenDice {
     type Dist = f64;
 
     fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
@@ -359,17 +853,7 @@ impl DistanceMetric for Overlap {
     {
         let a: Vec<_> = a.into_iter().collect();
         let b: Vec<_> = b.into_iter().collect();
-
-        // edge case where an input is empty
-        if a.is_empty() || b.is_empty() {
-            return if a.len() == b.len() { 0. } else { 1. };
-        }
-
-        let iter_a = QGramIter::new(&a, self.q);
-        let iter_b = QGramIter::new(&b, self.q);
-
-        let (num_dist_a, num_dist_b, num_intersect) = count_distinct_intersect(iter_a, iter_b);
-        1.0 - num_intersect as f64 / cmp::min(num_dist_a, num_dist_b) as f64
+        slice_distance(self, &a, &b)
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -381,15 +865,1059 @@ impl DistanceMetric for Overlap {
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
     {
-        normalized_qgram(self, self.q, a, b)
+        normalized_qgram(self, self.q, self.padded, a, b)
     }
-}
 
-/// A Iterator that behaves similar to [`std::slice::Chunks`], but increases the
-/// start index into the slice only by one each iteration.
-#[derive(Debug, Clone)]
-pub struct QGramIter<'a, T> {
-    items: &'a [T],
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
+        } else {
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        normalized_qgram_str(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        qgram_str_both(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+}
+
+/// Represents a Overlap metric where `q` is the length of a q-gram
+/// fragment.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - |Q(s1, q) ∩ Q(s2, q)|  / min(|Q(s1, q)|, |Q(s2, q)|)
+/// ```
+///
+/// where `Q(s, q)`  denotes the set of q-grams of length n for the str s
+///
+/// If an input has no q-grams, i.e. it is empty or shorter than `q` without
+/// padding, a value of `0.` is returned if both inputs are equal and `1.`
+/// otherwise. This avoids a return of `f64::NaN` for those cases.
+#[derive(Debug, Clone)]
+pub struct Overlap {
+    /// Length of the fragment
+    q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
+}
+
+impl Overlap {
+    /// Creates a new [`Overlap`] of length `q`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn new(q: usize) -> Self {
+        Self::try_new(q).unwrap()
+    }
+
+    /// Creates a new [`Overlap`] of length `q`, or an error if `q` is 0.
+    pub fn try_new(q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self { q, padded: false })
+    }
+
+    /// Creates a new [`Overlap`] of length `q`, that pads the inputs with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(q: usize) -> Self {
+        Self::try_with_padding(q).unwrap()
+    }
+
+    /// Creates a new [`Overlap`] like [`Overlap::with_padding`], or an error if
+    /// `q` is 0.
+    pub fn try_with_padding(q: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q)?
+        })
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
+    }
+}
+
+impl Default for Overlap {
+    /// Use a monogram as default overlap fragment length.
+    fn default() -> Self {
+        Overlap::new(1)
+    }
+}
+
+impl DistanceMetric for Overlap {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        slice_distance(self, &a, &b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        normalized_qgram(self, self.q, self.padded, a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
+        } else {
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        normalized_qgram_str(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        qgram_str_both(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+}
+
+/// Represents a Tversky index metric where `q` is the length of a q-gram
+/// fragment and `alpha` and `beta` weight the q-grams unique to either input.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - |A ∩ B| / (|A ∩ B| + alpha * |A - B| + beta * |B - A|)
+/// ```
+///
+/// where `A` and `B` denote the sets of q-grams `Q(s1, q)` and `Q(s2, q)`.
+///
+/// `alpha = beta = 1` is equivalent to [`Jaccard`] and `alpha = beta = 0.5` is
+/// equivalent to [`SorensenDice`]. For `alpha != beta` the metric is not
+/// symmetric.
+///
+/// If an input has no q-grams, i.e. it is empty or shorter than `q` without
+/// padding, a value of `0.` is returned if both inputs are equal and `1.`
+/// otherwise. This avoids a return of `f64::NaN` for those cases.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Tversky};
+/// // only penalize q-grams of the first str that are missing in the second
+/// let tversky = Tversky::new(2, 1., 0.);
+/// assert_eq!(tversky.str_distance("ab", "abc"), 0.);
+/// assert_eq!(tversky.str_distance("abc", "ab"), 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tversky {
+    /// Length of the fragment
+    q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
+    /// The weight of the q-grams only present in the first input.
+    alpha: f64,
+    /// The weight of the q-grams only present in the second input.
+    beta: f64,
+}
+
+impl Tversky {
+    /// Creates a new [`Tversky`] of length `q`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0 or `alpha` or `beta` are negative or NaN.
+    pub fn new(q: usize, alpha: f64, beta: f64) -> Self {
+        Self::try_new(q, alpha, beta).unwrap()
+    }
+
+    /// Creates a new [`Tversky`] of length `q`, or an error if `q` is 0 or
+    /// `alpha` or `beta` are negative or NaN.
+    pub fn try_new(q: usize, alpha: f64, beta: f64) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        if alpha.is_nan() || beta.is_nan() {
+            return Err(QGramError::NanWeight);
+        }
+        if alpha < 0. || beta < 0. {
+            return Err(QGramError::NegativeWeight);
+        }
+        Ok(Self {
+            q,
+            padded: false,
+            alpha,
+            beta,
+        })
+    }
+
+    /// Creates a new [`Tversky`] of length `q`, that pads the inputs with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0 or `alpha` or `beta` are negative or NaN.
+    pub fn with_padding(q: usize, alpha: f64, beta: f64) -> Self {
+        Self::try_with_padding(q, alpha, beta).unwrap()
+    }
+
+    /// Creates a new [`Tversky`] like [`Tversky::with_padding`], or an error if
+    /// `q` is 0 or `alpha` or `beta` are negative or NaN.
+    pub fn try_with_padding(q: usize, alpha: f64, beta: f64) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q, alpha, beta)?
+        })
+    }
+
+    /// `1 - |A ∩ B| / (|A ∩ B| + alpha * |A - B| + beta * |B - A|)`
+    fn tversky_distance(
+        &self,
+        (num_dist_a, num_dist_b, num_intersect): (usize, usize, usize),
+    ) -> f64 {
+        let intersect = num_intersect as f64;
+        let denominator = intersect
+            + self.alpha * (num_dist_a - num_intersect) as f64
+            + self.beta * (num_dist_b - num_intersect) as f64;
+        if denominator == 0. {
+            // no common q-grams and both differences are weighted with 0
+            1.
+        } else {
+            1.0 - intersect / denominator
+        }
+    }
+
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but counts the q-grams via hashing.
+    ///
+    /// This requires the elements to implement `Hash` and `Ord`, but avoids
+    /// the quadratic number of comparisons of the generic distance. Without
+    /// the `std` feature the q-grams are counted via ordering instead, so
+    /// `Ord` is required with the `std` feature as well.
+    pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator<Item = <S as IntoIterator>::Item>,
+        <S as IntoIterator>::Item: Hash + Ord,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        hashed_distance(self, &a, &b)
+    }
+}
+
+impl DistanceMetric for Tversky {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        slice_distance(self, &a, &b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        normalized_qgram(self, self.q, self.padded, a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if a.is_ascii() && b.is_ascii() {
+            self.distance_hashed(a.bytes(), b.bytes())
+        } else {
+            self.distance_hashed(a.chars(), b.chars())
+        }
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        normalized_qgram_str(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+
+    fn str_distance_both<S, T>(&self, a: S, b: T) -> (Self::Dist, f64)
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        qgram_str_both(self, self.q, self.padded, a.as_ref(), b.as_ref())
+    }
+}
+
+/// Implements `distance_by` for q-gram metrics, that compare the q-grams
+/// element-wise via an equality predicate instead of `PartialEq`.
+macro_rules! impl_distance_by {
+    ($($metric:ty => $dist:ty),*) => {
+        $(
+            impl $metric {
+                /// Evaluates the distance between `a` and `b` like
+                /// [`DistanceMetric::distance`], but compares the elements of
+                /// the q-grams with the predicate `eq` instead of `PartialEq`.
+                ///
+                /// Since the q-grams of an input are also compared with each
+                /// other, both inputs need to yield the same type and `eq`
+                /// should be an equivalence relation.
+                pub fn distance_by<S, T, F>(&self, a: S, b: T, eq: F) -> $dist
+                where
+                    S: IntoIterator,
+                    T: IntoIterator<Item = <S as IntoIterator>::Item>,
+                    <S as IntoIterator>::IntoIter: Clone,
+                    <T as IntoIterator>::IntoIter: Clone,
+                    F: Fn(&<S as IntoIterator>::Item, &<S as IntoIterator>::Item) -> bool,
+                {
+                    self.distance(eq_by(a, &eq), eq_by(b, &eq))
+                }
+            }
+        )*
+    };
+}
+
+impl_distance_by!(
+    QGram => usize,
+    Cosine => f64,
+    Jaccard => f64,
+    SorensenDice => f64,
+    Overlap => f64,
+    Tversky => f64
+);
+
+/// The q-gram metrics that are derived from the numbers of times each q-gram
+/// appears in both inputs, see [`eq_map`].
+trait QGramCounts {
+    /// The type of the distance.
+    type Dist;
+
+    /// The length of the q-grams.
+    fn q(&self) -> usize;
+
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    fn padded(&self) -> bool;
+
+    /// The distance if an input with `len_a` or `len_b` elements has no
+    /// q-grams, or `None` if the distance is derived from the counts.
+    fn distance_without_qgrams(
+        &self,
+        len_a: usize,
+        len_b: usize,
+        eq: impl FnOnce() -> bool,
+    ) -> Option<Self::Dist>;
+
+    /// The distance based on the numbers of times each q-gram appears in both
+    /// inputs.
+    fn distance_from_counts(&self, counts: Vec<(usize, usize)>) -> Self::Dist;
+}
+
+impl QGramCounts for QGram {
+    type Dist = usize;
+
+    fn q(&self) -> usize {
+        self.q
+    }
+
+    fn padded(&self) -> bool {
+        self.padded
+    }
+
+    fn distance_without_qgrams(
+        &self,
+        _len_a: usize,
+        _len_b: usize,
+        _eq: impl FnOnce() -> bool,
+    ) -> Option<usize> {
+        // the number of differing q-grams is defined for all inputs
+        None
+    }
+
+    fn distance_from_counts(&self, counts: Vec<(usize, usize)>) -> usize {
+        qgram_distance(counts)
+    }
+}
+
+/// Implements [`QGramCounts`] for the q-gram coefficients, that are undefined
+/// for inputs without q-grams, see [`distance_without_qgrams`].
+macro_rules! impl_qgram_coefficient {
+    ($($metric:ty => $distance:expr),*) => {
+        $(
+            impl QGramCounts for $metric {
+                type Dist = f64;
+
+                fn q(&self) -> usize {
+                    self.q
+                }
+
+                fn padded(&self) -> bool {
+                    self.padded
+                }
+
+                fn distance_without_qgrams(
+                    &self,
+                    len_a: usize,
+                    len_b: usize,
+                    eq: impl FnOnce() -> bool,
+                ) -> Option<f64> {
+                    distance_without_qgrams(self.q, self.padded, len_a, len_b, eq)
+                }
+
+                fn distance_from_counts(&self, counts: Vec<(usize, usize)>) -> f64 {
+                    let distance: fn(&Self, Vec<(usize, usize)>) -> f64 = $distance;
+                    distance(self, counts)
+                }
+            }
+        )*
+    };
+}
+
+impl_qgram_coefficient!(
+    Cosine => |_, counts| cosine_distance(counts),
+    Jaccard => |_, counts| jaccard_distance(distinct_intersect(counts)),
+    SorensenDice => |_, counts| sorensen_dice_distance(distinct_intersect(counts)),
+    Overlap => |_, counts| overlap_distance(distinct_intersect(counts)),
+    Tversky => |tversky, counts| tversky.tversky_distance(distinct_intersect(counts))
+);
+
+/// Evaluates the distance of the `metric` between two inputs with `len_a` and
+/// `len_b` elements, the `counts` of their q-grams are only computed if the
+/// metric is defined for the inputs.
+fn eval_qgram_counts<M: QGramCounts>(
+    metric: &M,
+    len_a: usize,
+    len_b: usize,
+    eq: impl FnOnce() -> bool,
+    counts: impl FnOnce() -> Vec<(usize, usize)>,
+) -> M::Dist {
+    match metric.distance_without_qgrams(len_a, len_b, eq) {
+        Some(dist) => dist,
+        None => metric.distance_from_counts(counts()),
+    }
+}
+
+/// Evaluates the distance of the `metric` between the slices `a` and `b`.
+fn slice_distance<M, A, B>(metric: &M, a: &[A], b: &[B]) -> M::Dist
+where
+    M: QGramCounts,
+    A: PartialEq + PartialEq<B>,
+    B: PartialEq,
+{
+    eval_qgram_counts(
+        metric,
+        a.len(),
+        b.len(),
+        || a == b,
+        || padded_eq_map(metric.q(), metric.padded(), a, b),
+    )
+}
+
+/// Like [`slice_distance`], but counts the q-grams via hashing.
+fn hashed_distance<M, T>(metric: &M, a: &[T], b: &[T]) -> M::Dist
+where
+    M: QGramCounts,
+    T: Hash + Ord,
+{
+    eval_qgram_counts(
+        metric,
+        a.len(),
+        b.len(),
+        || a == b,
+        || padded_eq_map_hashed(metric.q(), metric.padded(), a, b),
+    )
+}
+
+/// Evaluates the distance of the `metric` between the str the `profile` was
+/// created from and `s`.
+fn profile_distance<M: QGramCounts>(metric: &M, profile: &QGramProfile, s: &str) -> M::Dist {
+    assert_eq!(profile.q(), metric.q());
+    assert_eq!(profile.is_padded(), metric.padded());
+    let other = QGramProfile::build(s, metric.q(), metric.padded());
+    eval_qgram_counts(
+        metric,
+        profile.len,
+        other.len,
+        || profile.eq_without_qgrams(&other),
+        || profile.eq_counts(&other),
+    )
+}
+
+/// Like [`set_counts`], but of the padded inputs if the `metric` pads them.
+fn padded_set_counts<M, S, T>(metric: &M, a: S, b: T) -> (usize, usize, usize)
+where
+    M: QGramCounts,
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+    <T as IntoIterator>::Item: PartialEq,
+{
+    let q = metric.q();
+    if metric.padded() {
+        set_counts(q, pad(a, q), pad(b, q))
+    } else {
+        set_counts(q, a, b)
+    }
+}
+
+/// Implements `profile` and `distance_to_profile` for the q-gram metrics, to
+/// compare a prepared str with many others.
+macro_rules! impl_qgram_profile {
+    ($($metric:ident => $dist:ty),*) => {
+        $(
+            impl $metric {
+                /// Creates the [`QGramProfile`] of `s`, to compare it with other
+                #[doc = concat!(
+                    "strings via [`",
+                    stringify!($metric),
+                    "::distance_to_profile`]."
+                )]
+                pub fn profile(&self, s: &str) -> QGramProfile {
+                    QGramProfile::build(s, self.q, self.padded)
+                }
+
+                /// Evaluates the distance between the str the `profile` was
+                /// created from and `s`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if the `profile` was created for another fragment
+                /// length or padding.
+                pub fn distance_to_profile(&self, profile: &QGramProfile, s: &str) -> $dist {
+                    profile_distance(self, profile, s)
+                }
+            }
+        )*
+    };
+}
+
+impl_qgram_profile!(
+    QGram => usize,
+    Cosine => f64,
+    Jaccard => f64,
+    SorensenDice => f64,
+    Overlap => f64,
+    Tversky => f64
+);
+
+/// Implements `set_counts` for the set based q-gram metrics.
+macro_rules! impl_set_counts {
+    ($($metric:ty),*) => {
+        $(
+            impl $metric {
+                /// Returns the raw set counts the metric is derived from as a
+                /// tuple of `(|Q(s1, q)|, |Q(s2, q)|, |Q(s1, q) ∩ Q(s2, q)|)`.
+                ///
+                /// See [`set_counts`] for details.
+                pub fn set_counts<S, T>(&self, a: S, b: T) -> (usize, usize, usize)
+                where
+                    S: IntoIterator,
+                    T: IntoIterator,
+                    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+                    <T as IntoIterator>::Item: PartialEq,
+                {
+                    padded_set_counts(self, a, b)
+                }
+            }
+        )*
+    };
+}
+
+impl_set_counts!(Jaccard, SorensenDice, Overlap, Tversky);
+
+/// Represents a positional QGram metric where `q` is the length of a q-gram
+/// fragment and `window` the maximum difference of the positions of two
+/// matching q-grams.
+///
+/// Unlike [`QGram`], two equal q-grams only match if their positions in both
+/// inputs differ by at most `window`, so the order of the fragments matters.
+/// Each q-gram matches at most one q-gram of the other input. The distance
+/// corresponds to
+///
+/// ```text
+///     |P(s1, q)| + |P(s2, q)| - 2 * m
+/// ```
+///
+/// where `P(s, q)` denotes the list of all q-grams of length q of the str s and
+/// `m` the maximum number of matching pairs. For a `window` of at least the
+/// length of the longer input this is equal to the [`QGram`] distance.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, PositionalQGram, QGram};
+/// assert_eq!(QGram::new(1).str_distance("abcd", "cdab"), 0);
+/// assert_eq!(PositionalQGram::new(1, 1).str_distance("abcd", "cdab"), 8);
+/// assert_eq!(PositionalQGram::new(1, 2).str_distance("abcd", "cdab"), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PositionalQGram {
+    /// Length of the fragment
+    q: usize,
+    /// Whether the inputs are padded with `q - 1` boundary markers.
+    padded: bool,
+    /// The maximum difference of the positions of two matching q-grams.
+    window: usize,
+}
+
+impl PositionalQGram {
+    /// Creates a new [`PositionalQGram`] of length `q`, whose q-grams match if
+    /// their positions differ by at most `window`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn new(q: usize, window: usize) -> Self {
+        Self::try_new(q, window).unwrap()
+    }
+
+    /// Creates a new [`PositionalQGram`] of length `q`, whose q-grams match if
+    /// their positions differ by at most `window`, or an error if `q` is 0.
+    pub fn try_new(q: usize, window: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self {
+            q,
+            padded: false,
+            window,
+        })
+    }
+
+    /// Creates a new [`PositionalQGram`] of length `q`, that pads the inputs
+    /// with `q - 1` boundary markers on both sides, see
+    /// [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(q: usize, window: usize) -> Self {
+        Self::try_with_padding(q, window).unwrap()
+    }
+
+    /// Creates a new [`PositionalQGram`] like
+    /// [`PositionalQGram::with_padding`], or an error if `q` is 0.
+    pub fn try_with_padding(q: usize, window: usize) -> Result<Self, QGramError> {
+        Ok(Self {
+            padded: true,
+            ..Self::try_new(q, window)?
+        })
+    }
+
+    /// The number of matching pairs of q-grams within the window.
+    fn num_matches<A, B>(&self, a: &[A], b: &[B]) -> usize
+    where
+        A: PartialEq<B>,
+    {
+        let qgrams_b: Vec<_> = QGramIter::new(b, self.q).collect();
+        let mut matched = vec![false; qgrams_b.len()];
+        let mut num_matches = 0;
+        // matching every q-gram of a with the first unmatched q-gram of b within
+        // the window in order of their positions yields the maximum matching,
+        // since all windows have the same size
+        for (pos_a, qgram_a) in QGramIter::new(a, self.q).enumerate() {
+            let start = pos_a.saturating_sub(self.window);
+            let end = cmp::min(
+                pos_a.saturating_add(self.window).saturating_add(1),
+                qgrams_b.len(),
+            );
+            if let Some(pos_b) =
+                (start..end).find(|pos_b| !matched[*pos_b] && qgram_a == qgrams_b[*pos_b])
+            {
+                matched[pos_b] = true;
+                num_matches += 1;
+            }
+        }
+        num_matches
+    }
+
+    /// The number of q-grams of an input with `len` elements.
+    fn num_qgrams(&self, len: usize) -> usize {
+        if self.padded && len > 0 {
+            len + self.q - 1
+        } else {
+            (len + 1).saturating_sub(self.q)
+        }
+    }
+
+    /// The number of q-grams of both inputs that are not matched.
+    fn positional_distance<A, B>(&self, a: &[A], b: &[B]) -> usize
+    where
+        A: PartialEq<B>,
+    {
+        let num_matches = if self.padded {
+            self.num_matches(&pad(a, self.q), &pad(b, self.q))
+        } else {
+            self.num_matches(a, b)
+        };
+        self.num_qgrams(a.len()) + self.num_qgrams(b.len()) - 2 * num_matches
+    }
+}
+
+impl DistanceMetric for PositionalQGram {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.positional_distance(&a, &b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+
+        if let Some(dist) =
+            distance_without_qgrams(self.q, self.padded, a.len(), b.len(), || a == b)
+        {
+            return dist;
+        }
+        // the total number of q-grams of both inputs
+        let norm = self.num_qgrams(a.len()) + self.num_qgrams(b.len());
+        self.positional_distance(&a, &b) as f64 / norm as f64
+    }
+}
+
+/// The distinct q-grams of a str together with the number of times they
+/// appear in the str.
+///
+/// Comparing a str with many other strings via a profile avoids recounting
+/// its q-grams for each comparison.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, SorensenDice};
+/// let dice = SorensenDice::new(2);
+/// let profile = dice.profile("nacht");
+/// assert_eq!(dice.distance_to_profile(&profile, "night"), dice.str_distance("nacht", "night"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QGramProfile {
+    /// Length of the fragment
+    q: usize,
+    /// Whether the str was padded with `q - 1` boundary markers.
+    padded: bool,
+    /// The number of chars of the str.
+    len: usize,
+    /// How often each distinct q-gram appears.
+    counts: Map<Vec<char>, usize>,
+    /// How often each distinct q-gram with boundary markers appears.
+    boundary_counts: Map<Vec<Padded<char>>, usize>,
+    /// The chars of the str if it has no q-grams, to compare it by equality
+    /// instead, see [`distance_without_qgrams`].
+    short_chars: Vec<char>,
+}
+
+impl QGramProfile {
+    /// Creates the profile of all q-grams of length `q` of `s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn new(s: &str, q: usize) -> Self {
+        Self::build(s, q, false)
+    }
+
+    /// Creates the profile of all q-grams of length `q` of `s`, padded with
+    /// `q - 1` boundary markers on both sides, see [`QGram::with_padding`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is 0.
+    pub fn with_padding(s: &str, q: usize) -> Self {
+        Self::build(s, q, true)
+    }
+
+    /// Creates the profile like [`QGramProfile::new`], or an error if `q` is 0.
+    pub fn try_new(s: &str, q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self::new(s, q))
+    }
+
+    /// Creates the profile like [`QGramProfile::with_padding`], or an error
+    /// if `q` is 0.
+    pub fn try_with_padding(s: &str, q: usize) -> Result<Self, QGramError> {
+        if q == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self::with_padding(s, q))
+    }
+
+    fn build(s: &str, q: usize, padded: bool) -> Self {
+        let chars: Vec<_> = s.chars().collect();
+        let mut counts = Map::new();
+        let mut boundary_counts = Map::new();
+        if padded {
+            let padded_chars = pad(chars.iter().copied(), q);
+            for qgram in QGramIter::new(&padded_chars, q) {
+                match qgram.iter().map(|c| c.item()).collect::<Option<Vec<_>>>() {
+                    Some(qgram) => *counts.entry(qgram).or_insert(0) += 1,
+                    None => *boundary_counts.entry(qgram.to_vec()).or_insert(0) += 1,
+                }
+            }
+        } else {
+            for qgram in QGramIter::new(&chars, q) {
+                *counts.entry(qgram.to_vec()).or_insert(0) += 1;
+            }
+        }
+        let len = chars.len();
+        let short_chars = if counts.is_empty() && boundary_counts.is_empty() {
+            chars
+        } else {
+            Vec::new()
+        };
+        Self {
+            q,
+            padded,
+            len,
+            counts,
+            boundary_counts,
+            short_chars,
+        }
+    }
+
+    /// The length of the q-grams.
+    pub fn q(&self) -> usize {
+        self.q
+    }
+
+    /// The number of chars of the str the profile was created from.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the profile was created from an empty str.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the str was padded with boundary markers.
+    pub fn is_padded(&self) -> bool {
+        self.padded
+    }
+
+    /// How often the `qgram` appears in the str.
+    pub fn count(&self, qgram: &[char]) -> usize {
+        self.counts.get(qgram).copied().unwrap_or_default()
+    }
+
+    /// Iterator over all distinct q-grams and their number of occurrences.
+    ///
+    /// The q-grams of a padded profile that contain boundary markers are not
+    /// included.
+    pub fn iter(&self) -> impl Iterator<Item = (&[char], usize)> {
+        self.counts.iter().map(|(qgram, n)| (qgram.as_slice(), *n))
+    }
+
+    /// Whether the profiles were created from the same str, if one of them has
+    /// no q-grams, see [`distance_without_qgrams`].
+    fn eq_without_qgrams(&self, other: &QGramProfile) -> bool {
+        // the chars of a str with q-grams aren't kept, but its length differs
+        self.len == other.len && self.short_chars == other.short_chars
+    }
+
+    /// Returns a list of tuples with the numbers of times a q-gram appears in
+    /// this and the other profile, like [`eq_map`].
+    fn eq_counts(&self, other: &QGramProfile) -> Vec<(usize, usize)> {
+        let mut nums = join_counts(&self.counts, &other.counts);
+        nums.extend(join_counts(&self.boundary_counts, &other.boundary_counts));
+        nums
+    }
+}
+
+/// Returns a list of tuples with the numbers of times a key is counted in `a`
+/// and `b`.
+fn join_counts<K>(a: &Map<K, usize>, b: &Map<K, usize>) -> Vec<(usize, usize)>
+where
+    K: Hash + Ord,
+{
+    let mut nums: Vec<_> = a
+        .iter()
+        .map(|(key, n)| (*n, b.get(key).copied().unwrap_or_default()))
+        .collect();
+    nums.extend(
+        b.iter()
+            .filter(|(key, _)| !a.contains_key(*key))
+            .map(|(_, n)| (0, *n)),
+    );
+    nums
+}
+
+/// An element of an input that is padded with boundary markers, see [`pad`].
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+enum Padded<T> {
+    /// The synthetic marker before the first and after the last element.
+    Boundary,
+    /// An element of the input.
+    Item(T),
+}
+
+impl<T: Copy> Padded<T> {
+    /// The element of the input, if this is no boundary marker.
+    fn item(&self) -> Option<T> {
+        match self {
+            Padded::Boundary => None,
+            Padded::Item(item) => Some(*item),
+        }
+    }
+}
+
+impl<A, B> PartialEq<Padded<B>> for Padded<A>
+where
+    A: PartialEq<B>,
+{
+    fn eq(&self, other: &Padded<B>) -> bool {
+        match (self, other) {
+            (Padded::Boundary, Padded::Boundary) => true,
+            (Padded::Item(a), Padded::Item(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Eq> Eq for Padded<T> {}
+
+/// Wraps the `items` with `q - 1` boundary markers on both sides, so that the
+/// first and last items appear in as many q-grams as all others.
+///
+/// Empty inputs are not padded.
+fn pad<I: IntoIterator>(items: I, q: usize) -> Vec<Padded<I::Item>> {
+    let mut items = items.into_iter().map(Padded::Item).peekable();
+    if items.peek().is_none() {
+        return Vec::new();
+    }
+    let boundary = || iter::repeat_with(|| Padded::Boundary).take(q - 1);
+    boundary().chain(items).chain(boundary()).collect()
+}
+
+/// Returns a list of tuples with the numbers of times a q-gram appears in `a`
+/// and `b` like [`eq_map`], of the padded inputs if `padded`.
+fn padded_eq_map<A, B>(q: usize, padded: bool, a: &[A], b: &[B]) -> Vec<(usize, usize)>
+where
+    A: PartialEq + PartialEq<B>,
+    B: PartialEq,
+{
+    if padded {
+        let (a, b) = (pad(a, q), pad(b, q));
+        eq_map(QGramIter::new(&a, q), QGramIter::new(&b, q))
+    } else {
+        eq_map(QGramIter::new(a, q), QGramIter::new(b, q))
+    }
+}
+
+/// Like [`padded_eq_map`], but counts the q-grams via [`eq_map_hashed`].
+fn padded_eq_map_hashed<T>(q: usize, padded: bool, a: &[T], b: &[T]) -> Vec<(usize, usize)>
+where
+    T: Hash + Ord,
+{
+    if padded {
+        let (a, b) = (pad(a, q), pad(b, q));
+        eq_map_hashed(QGramIter::new(&a, q), QGramIter::new(&b, q))
+    } else {
+        eq_map_hashed(QGramIter::new(a, q), QGramIter::new(b, q))
+    }
+}
+
+/// A Iterator that behaves similar to [`std::slice::Chunks`], but increases the
+/// start index into the slice only by one each iteration.
+#[derive(Debug, Clone)]
+pub struct QGramIter<'a, T> {
+    items: &'a [T],
     index: usize,
     chunk_size: usize,
 }
@@ -405,12 +1933,20 @@ impl<'a, T> QGramIter<'a, T> {
     ///
     /// Panics if `q` is 0.
     pub fn new(items: &'a [T], chunk_size: usize) -> Self {
-        assert_ne!(chunk_size, 0);
-        Self {
+        Self::try_new(items, chunk_size).unwrap()
+    }
+
+    /// Constructs the iterator like [`QGramIter::new`], or returns an error if
+    /// the chunk size is 0.
+    pub fn try_new(items: &'a [T], chunk_size: usize) -> Result<Self, QGramError> {
+        if chunk_size == 0 {
+            return Err(QGramError::ZeroLength);
+        }
+        Ok(Self {
             items,
             chunk_size,
             index: 0,
-        }
+        })
     }
 
     /// Resets the index back the beginning.
@@ -450,9 +1986,77 @@ impl<'a, T> Iterator for QGramIter<'a, T> {
     }
 }
 
+/// Returns an iterator over all overlapping q-grams of `s` as str slices.
+///
+/// Unlike [`QGramIter`], this doesn't require the chars to be collected first.
+/// The q-grams consist of `q` chars, not bytes, so multibyte chars are never
+/// split. A `q` greater than the number of chars yields no q-grams.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::qgram::qgrams;
+/// let grams: Vec<_> = qgrams("héllo", 2).collect();
+/// assert_eq!(grams, vec!["hé", "él", "ll", "lo"]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `q` is 0.
+pub fn qgrams(s: &str, q: usize) -> impl Iterator<Item = &str> + Clone + '_ {
+    try_qgrams(s, q).unwrap()
+}
+
+/// Returns an iterator over all overlapping q-grams of `s` like [`qgrams`], or
+/// an error if `q` is 0.
+pub fn try_qgrams(
+    s: &str,
+    q: usize,
+) -> Result<impl Iterator<Item = &str> + Clone + '_, QGramError> {
+    if q == 0 {
+        return Err(QGramError::ZeroLength);
+    }
+    // the byte index after the last char of every q-gram
+    let ends = s
+        .char_indices()
+        .skip(q - 1)
+        .map(|(idx, c)| idx + c.len_utf8());
+    Ok(s.char_indices()
+        .map(|(idx, _)| idx)
+        .zip(ends)
+        .map(move |(start, end)| &s[start..end]))
+}
+
+/// The distance of the q-gram coefficients [`Cosine`], [`Jaccard`],
+/// [`SorensenDice`], [`Overlap`] and [`Tversky`], and of the normalized
+/// [`PositionalQGram`], if an input has no q-grams, i.e. if it is empty or
+/// shorter than `q` without padding.
+///
+/// The coefficients are undefined without q-grams, so equal inputs have a
+/// distance of `0.` and all others a distance of `1.`: two empty inputs are
+/// equal, whereas an empty input is as distant as possible from a non-empty
+/// one. Returns `None` if both inputs with `len_a` and `len_b` elements have
+/// q-grams, `eq` is only called otherwise.
+fn distance_without_qgrams(
+    q: usize,
+    padded: bool,
+    len_a: usize,
+    len_b: usize,
+    eq: impl FnOnce() -> bool,
+) -> Option<f64> {
+    let has_qgrams = |len: usize| if padded { len > 0 } else { len >= q };
+    if has_qgrams(len_a) && has_qgrams(len_b) {
+        None
+    } else if eq() {
+        Some(0.)
+    } else {
+        Some(1.)
+    }
+}
+
 /// Normalize the metric, so that it returns always a f64 between 0 and 1.
-/// If a str length < q, returns a == b
-fn normalized_qgram<Q, S, T>(metric: &Q, q: usize, a: S, b: T) -> Q::Dist
+/// If a str length < q and the inputs are not padded, returns a == b
+fn normalized_qgram<Q, S, T>(metric: &Q, q: usize, padded: bool, a: S, b: T) -> Q::Dist
 where
     Q: DistanceMetric<Dist = f64>,
     S: IntoIterator,
@@ -468,7 +2072,7 @@ where
     let len_a = a.clone().count();
     let len_b = b.clone().count();
 
-    if cmp::min(len_a, len_b) <= q {
+    if !padded && cmp::min(len_a, len_b) <= q {
         if a.eq(b) {
             0.
         } else {
@@ -479,12 +2083,85 @@ where
     }
 }
 
-fn count_distinct_intersect<S, T>(a: QGramIter<S>, b: QGramIter<T>) -> (usize, usize, usize)
+/// Counts the distinct q-grams of length `q` of both inputs.
+///
+/// Returns a tuple of
+///  - the number of distinct q-grams in `a`: `|Q(a, q)|`
+///  - the number of distinct q-grams in `b`: `|Q(b, q)|`
+///  - the number of distinct q-grams that occur in both: `|Q(a, q) ∩ Q(b, q)|`
+///
+/// These are the building blocks of all set based q-gram coefficients, e.g.
+/// the size of the union is `|Q(a, q)| + |Q(b, q)| - |Q(a, q) ∩ Q(b, q)|`.
+///
+/// # Panics
+///
+/// Panics if `q` is 0.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::qgram::set_counts;
+/// assert_eq!(set_counts(2, "night".chars(), "nacht".chars()), (4, 4, 1));
+/// ```
+pub fn set_counts<S, T>(q: usize, a: S, b: T) -> (usize, usize, usize)
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+    <T as IntoIterator>::Item: PartialEq,
+{
+    let a: Vec<_> = a.into_iter().collect();
+    let b: Vec<_> = b.into_iter().collect();
+
+    count_distinct_intersect(QGramIter::new(&a, q), QGramIter::new(&b, q))
+}
+
+/// Normalize the metric for str, so that it returns always a f64 between 0
+/// and 1. If a str length < q and the inputs are not padded, returns a == b
+fn normalized_qgram_str<Q>(metric: &Q, q: usize, padded: bool, a: &str, b: &str) -> Q::Dist
+where
+    Q: DistanceMetric<Dist = f64>,
+{
+    if !padded && cmp::min(a.chars().count(), b.chars().count()) <= q {
+        if a == b {
+            0.
+        } else {
+            1.
+        }
+    } else {
+        metric.str_distance(a, b)
+    }
+}
+
+/// Like [`normalized_qgram_str`], but also returns the distance of the
+/// metric, which is evaluated only once.
+fn qgram_str_both<Q>(metric: &Q, q: usize, padded: bool, a: &str, b: &str) -> (f64, f64)
+where
+    Q: DistanceMetric<Dist = f64>,
+{
+    let dist = metric.str_distance(a, b);
+    if !padded && cmp::min(a.chars().count(), b.chars().count()) <= q {
+        (dist, if a == b { 0. } else { 1. })
+    } else {
+        (dist, dist)
+    }
+}
+
+pub(crate) fn count_distinct_intersect<S, T>(
+    a: QGramIter<S>,
+    b: QGramIter<T>,
+) -> (usize, usize, usize)
 where
     S: PartialEq + PartialEq<T>,
     T: PartialEq,
 {
-    eq_map(a, b).into_iter().fold(
+    distinct_intersect(eq_map(a, b))
+}
+
+/// The number of distinct q-grams in a, in b and in both, based on the number
+/// of times each q-gram appears in a and b.
+fn distinct_intersect(counts: Vec<(usize, usize)>) -> (usize, usize, usize) {
+    counts.into_iter().fold(
         (0, 0, 0),
         |(num_dist_a, num_dist_b, num_intersect), (n1, n2)| {
             if n1 > 0 {
@@ -502,6 +2179,62 @@ where
     )
 }
 
+/// `||v(s1, q) - v(s2, q)||` based on the number of times each q-gram appears
+/// in s1 and s2.
+fn qgram_distance(counts: Vec<(usize, usize)>) -> usize {
+    counts.into_iter().map(|(n1, n2)| n1.abs_diff(n2)).sum()
+}
+
+/// `1 - v(s1, q).v(s2, q)  / ||v(s1, q)|| * ||v(s2, q)||` based on the number
+/// of times each q-gram appears in s1 and s2.
+fn cosine_distance(counts: Vec<(usize, usize)>) -> f64 {
+    let (norm_a, norm_b, norm_prod) = counts.into_iter().fold(
+        (0usize, 0usize, 0usize),
+        |(norm_a, norm_b, norm_prod), (n1, n2)| {
+            (norm_a + n1 * n1, norm_b + n2 * n2, norm_prod + n1 * n2)
+        },
+    );
+    1.0 - norm_prod as f64 / (sqrt(norm_a as f64) * sqrt(norm_b as f64))
+}
+
+/// `1 - |Q(s1, q) ∩ Q(s2, q)| / |Q(s1, q) ∪ Q(s2, q))|`
+pub(crate) fn jaccard_distance(
+    (num_dist_a, num_dist_b, num_intersect): (usize, usize, usize),
+) -> f64 {
+    1.0 - num_intersect as f64 / ((num_dist_a + num_dist_b) as f64 - num_intersect as f64)
+}
+
+/// `1 - 2 * |Q(s1, q) ∩ Q(s2, q)|  / (|Q(s1, q)| + |Q(s2, q))|)`
+pub(crate) fn sorensen_dice_distance(
+    (num_dist_a, num_dist_b, num_intersect): (usize, usize, usize),
+) -> f64 {
+    1.0 - 2.0 * num_intersect as f64 / (num_dist_a + num_dist_b) as f64
+}
+
+/// `1 - |Q(s1, q) ∩ Q(s2, q)|  / min(|Q(s1, q)|, |Q(s2, q)|)`
+pub(crate) fn overlap_distance(
+    (num_dist_a, num_dist_b, num_intersect): (usize, usize, usize),
+) -> f64 {
+    1.0 - num_intersect as f64 / cmp::min(num_dist_a, num_dist_b) as f64
+}
+
+/// Returns a list of tuples with the numbers of times a qgram appears in a and
+/// b, like [`eq_map`] but counts the q-grams via hashing, or via ordering
+/// without the `std` feature.
+fn eq_map_hashed<'a, T>(a: QGramIter<'a, T>, b: QGramIter<'a, T>) -> Vec<(usize, usize)>
+where
+    T: Hash + Ord,
+{
+    let mut counts: Map<&[T], (usize, usize)> = Map::new();
+    for qgram in a {
+        counts.entry(qgram).or_default().0 += 1;
+    }
+    for qgram in b {
+        counts.entry(qgram).or_default().1 += 1;
+    }
+    counts.into_values().collect()
+}
+
 /// Returns a list of tuples with the numbers of times a qgram appears in a and
 /// b
 ///
@@ -557,6 +2290,50 @@ mod tests {
         assert_eq!(QGram::new(4).str_distance("abcdefg", "defgabc"), 6);
     }
 
+    #[test]
+    fn qgram_normalized_different_len() {
+        assert_eq!(
+            format!("{:.6}", QGram::new(2).str_normalized("abc", "abcdefgh")),
+            "0.555556"
+        );
+        assert_eq!(
+            QGram::new(2).str_normalized("abc", "abcdefgh"),
+            QGram::new(2).str_normalized("abcdefgh", "abc")
+        );
+        assert_eq!(QGram::new(3).str_normalized("abc", "abcdefgh"), 1.);
+        assert_eq!(QGram::new(3).str_normalized("abc", "abc"), 0.);
+
+        // the inputs are too short to count their q-grams
+        assert_eq!(QGram::new(3).str_normalized("", ""), 0.);
+        assert_eq!(QGram::new(3).str_normalized("a", ""), 1.);
+        assert_eq!(QGram::new(3).str_distance_both("", "a"), (0, 1.));
+        assert_eq!(QGram::new(3).str_distance_within("ab", "ab", 0.), Some(0.));
+    }
+
+    #[test]
+    fn qgram_within() {
+        for (a, b) in [
+            ("abc", "abcdefgh"),
+            ("abc", "abc"),
+            ("ab", "cd"),
+            ("leia", "leela"),
+            // the length difference alone determines the distance
+            ("abcd", "abcde"),
+            ("ab", "abababababababab"),
+            ("", "abc"),
+        ]
+        .iter()
+        {
+            for qgram in [QGram::new(2), QGram::with_padding(2)].iter() {
+                for threshold in [0., 0.1, 0.125, 0.3, 0.6, 1.].iter() {
+                    let expected =
+                        Some(qgram.str_normalized(a, b)).filter(|dist| dist <= threshold);
+                    assert_eq!(qgram.str_distance_within(a, b, *threshold), expected);
+                }
+            }
+        }
+    }
+
     #[test]
     fn cosine_distance() {
         assert_eq!(Cosine::new(1).str_distance("", ""), 0.);
@@ -598,7 +2375,7 @@ mod tests {
     }
 
     #[test]
-    fn overlap_distance() {
+    fn sorensen_dice_edge_cases() {
         assert_eq!(SorensenDice::new(1).str_distance("", ""), 0.);
         assert_eq!(SorensenDice::new(1).str_distance("", "abc"), 1.);
         assert_eq!(SorensenDice::new(3).str_distance("abc", "abc"), 0.);
@@ -613,6 +2390,258 @@ mod tests {
         assert_eq!(SorensenDice::new(1).str_distance("nacht", "night"), 0.4);
     }
 
+    #[test]
+    fn padded_sorensen_dice() {
+        let dice = SorensenDice::new(2);
+        let padded = SorensenDice::with_padding(2);
+        // the bigrams of "ab" are `ab` and padded `#a`, `ab`, `b#`
+        assert_eq!(format!("{:.6}", dice.str_distance("ab", "abc")), "0.333333");
+        assert_eq!(
+            format!("{:.6}", padded.str_distance("ab", "abc")),
+            "0.428571"
+        );
+        // different prefixes and suffixes only count if padded
+        assert_eq!(dice.str_distance("abc", "cab"), 0.5);
+        assert_eq!(padded.str_distance("abc", "cab"), 0.75);
+        // inputs shorter than q have q-grams if padded
+        assert_eq!(dice.str_normalized("a", "ab"), 1.);
+        assert_eq!(
+            format!("{:.6}", padded.str_normalized("a", "ab")),
+            "0.600000"
+        );
+        assert_eq!(padded.str_distance("a", "a"), 0.);
+        assert_eq!(padded.str_normalized("a", "b"), 1.);
+        // empty inputs are not padded
+        assert_eq!(padded.str_distance("", ""), 0.);
+        assert_eq!(padded.str_distance("", "a"), 1.);
+        assert_eq!(padded.set_counts("".chars(), "a".chars()), (0, 2, 0));
+        // the markers are distinct from all chars
+        assert_eq!(padded.set_counts("ab".chars(), "#ab#".chars()), (3, 5, 1));
+
+        let words = ["", "a", "ab", "abc", "cab", "#a#", "nacht", "night"];
+        for a in words.iter() {
+            let profile = padded.profile(a);
+            assert!(profile.is_padded());
+            for b in words.iter() {
+                let dist = padded.str_distance(a, b);
+                assert_eq!(dist, padded.distance(a.chars(), b.chars()));
+                assert_eq!(dist, padded.distance_to_profile(&profile, b));
+                assert_eq!(dist, padded.str_normalized(a, b));
+                assert_eq!(dist, Tversky::with_padding(2, 0.5, 0.5).str_distance(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn padded_qgram() {
+        let padded = QGram::with_padding(2);
+        assert_eq!(QGram::new(2).str_distance("abc", "cab"), 2);
+        assert_eq!(padded.str_distance("abc", "cab"), 6);
+        assert_eq!(padded.distance("abc".chars(), "cab".chars()), 6);
+        assert_eq!(padded.str_normalized("abc", "cab"), 0.75);
+        assert_eq!(padded.normalized("abc".chars(), "cab".chars()), 0.75);
+        assert_eq!(padded.str_distance_within("abc", "cab", 0.5), None);
+        assert_eq!(padded.str_distance_within("abc", "cab", 0.75), Some(0.75));
+        assert_eq!(padded.str_normalized("a", ""), 1.);
+        assert_eq!(padded.str_normalized("a", "a"), 0.);
+        assert_eq!(padded.distance_to_profile(&padded.profile("abc"), "cab"), 6);
+        assert_eq!(padded.profile("ab").iter().count(), 1);
+        assert_eq!(padded.profile("ab"), QGramProfile::with_padding("ab", 2));
+        assert_ne!(padded.profile("ab"), QGramProfile::new("ab", 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn padded_profile_mismatch() {
+        let profile = QGramProfile::new("abc", 2);
+        QGram::with_padding(2).distance_to_profile(&profile, "cab");
+    }
+
+    #[test]
+    fn qgram_set_counts() {
+        let counts = (4, 4, 1);
+        assert_eq!(
+            Jaccard::new(2).set_counts("night".chars(), "nacht".chars()),
+            counts
+        );
+        assert_eq!(
+            SorensenDice::new(2).set_counts("night".chars(), "nacht".chars()),
+            counts
+        );
+        assert_eq!(
+            Overlap::new(2).set_counts("night".chars(), "nacht".chars()),
+            counts
+        );
+        assert_eq!(
+            Jaccard::new(2).set_counts("".chars(), "nacht".chars()),
+            (0, 4, 0)
+        );
+    }
+
+    #[test]
+    fn profile_distance() {
+        let pairs = [
+            ("nacht", "night"),
+            ("", ""),
+            ("", "abc"),
+            ("abc", "abc"),
+            ("monday", "montag"),
+            ("leia", "leela"),
+            ("aü☃", "aüaüafs"),
+            ("abcdefg", "defgabc"),
+        ];
+        for (a, b) in pairs.iter() {
+            for q in 1..4 {
+                let dist = QGram::new(q);
+                assert_eq!(
+                    dist.distance_to_profile(&dist.profile(a), b),
+                    dist.str_distance(a, b)
+                );
+                let dist = Cosine::new(q);
+                assert_eq!(
+                    format!("{:.12}", dist.distance_to_profile(&dist.profile(a), b)),
+                    format!("{:.12}", dist.str_distance(a, b))
+                );
+                let dist = Jaccard::new(q);
+                assert_eq!(
+                    dist.distance_to_profile(&dist.profile(a), b),
+                    dist.str_distance(a, b)
+                );
+                let dist = SorensenDice::new(q);
+                assert_eq!(
+                    dist.distance_to_profile(&dist.profile(a), b),
+                    dist.str_distance(a, b)
+                );
+                let dist = Overlap::new(q);
+                assert_eq!(
+                    dist.distance_to_profile(&dist.profile(a), b).to_bits(),
+                    dist.str_distance(a, b).to_bits()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn profile_counts() {
+        let profile = QGram::new(2).profile("banana");
+        assert_eq!(profile.len(), 6);
+        assert_eq!(profile.count(&['a', 'n']), 2);
+        assert_eq!(profile.count(&['n', 'a']), 2);
+        assert_eq!(profile.count(&['b', 'a']), 1);
+        assert_eq!(profile.count(&['a', 'b']), 0);
+        assert_eq!(profile.iter().count(), 3);
+    }
+
+    #[test]
+    fn overlap_counts() {
+        let (a, b) = ("night".chars(), "nacht".chars());
+        assert_eq!(
+            QGram::new(2).overlap_counts(a.clone(), b.clone()),
+            (4, 4, 1)
+        );
+        assert_eq!(
+            QGram::new(2).overlap_counts(a.clone(), b.clone()),
+            set_counts(2, a.clone(), b.clone())
+        );
+        // "#n" and "t#" are shared as well
+        assert_eq!(
+            QGram::with_padding(2).overlap_counts(a.clone(), b.clone()),
+            (6, 6, 3)
+        );
+        assert_eq!(QGram::new(1).overlap_counts(a, b), (5, 5, 3));
+        // repeated q-grams are counted once
+        assert_eq!(
+            QGram::new(2).overlap_counts("banana".chars(), "ban".chars()),
+            (3, 2, 2)
+        );
+        assert_eq!(
+            QGram::new(3).overlap_counts("ab".chars(), "".chars()),
+            (0, 0, 0)
+        );
+        let (a, b) = (["the", "quick", "fox"], ["the", "quick", "cat"]);
+        assert_eq!(QGram::new(2).overlap_counts(&a, &b), (2, 2, 1));
+    }
+
+    #[test]
+    fn profile_vec() {
+        let bigrams = |counts: &[(&str, usize)]| -> Vec<(String, usize)> {
+            counts.iter().map(|(q, n)| (q.to_string(), *n)).collect()
+        };
+        assert_eq!(
+            QGram::new(2).profile_vec("banana"),
+            bigrams(&[("an", 2), ("ba", 1), ("na", 2)])
+        );
+        assert_eq!(
+            QGram::new(1).profile_vec("banana"),
+            bigrams(&[("a", 3), ("b", 1), ("n", 2)])
+        );
+        assert_eq!(
+            QGram::new(3).profile_vec("a☃a☃"),
+            bigrams(&[("a☃a", 1), ("☃a☃", 1)])
+        );
+        // without the q-grams of the boundary markers
+        assert_eq!(
+            QGram::with_padding(2).profile_vec("ab"),
+            bigrams(&[("ab", 1)])
+        );
+        assert!(QGram::new(2).profile_vec("a").is_empty());
+        assert!(QGram::new(2).profile_vec("").is_empty());
+    }
+
+    #[test]
+    fn hashed_eq_map() {
+        fn sorted(mut v: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+            v.sort_unstable();
+            v
+        }
+        // pseudo random strings of length 1000
+        let mut seed = 42u32;
+        let mut random = || {
+            (0..1000)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                    (b'a' + (seed >> 16) as u8 % 8) as char
+                })
+                .collect::<String>()
+        };
+        let long_a = random();
+        let long_b = random();
+        let pairs = [
+            ("abc", "abc"),
+            ("abc", "abcdef"),
+            ("", "abc"),
+            ("banana", "ananas"),
+            (long_a.as_str(), long_b.as_str()),
+        ];
+        for (a, b) in pairs.iter() {
+            let a: Vec<_> = a.chars().collect();
+            let b: Vec<_> = b.chars().collect();
+            for q in 1..5 {
+                assert_eq!(
+                    sorted(eq_map(QGramIter::new(&a, q), QGramIter::new(&b, q))),
+                    sorted(eq_map_hashed(QGramIter::new(&a, q), QGramIter::new(&b, q)))
+                );
+            }
+        }
+        for q in 1..5 {
+            let (a, b) = (long_a.chars(), long_b.chars());
+            assert_eq!(
+                QGram::new(q).str_distance(&long_a, &long_b),
+                QGram::new(q).distance(a, b)
+            );
+            let (a, b) = (long_a.chars(), long_b.chars());
+            assert_eq!(
+                format!("{:.12}", Cosine::new(q).str_distance(&long_a, &long_b)),
+                format!("{:.12}", Cosine::new(q).distance(a, b))
+            );
+            let (a, b) = (long_a.chars(), long_b.chars());
+            assert_eq!(
+                Jaccard::new(q).str_distance(&long_a, &long_b),
+                Jaccard::new(q).distance(a, b)
+            );
+        }
+    }
+
     #[test]
     fn qgram_iter() {
         let s: Vec<_> = "hello".chars().collect();
@@ -631,6 +2660,17 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn str_qgrams() {
+        let grams: Vec<_> = qgrams("héllo", 2).collect();
+        assert_eq!(grams, vec!["hé", "él", "ll", "lo"]);
+        let grams: Vec<_> = qgrams("a☃ü", 1).collect();
+        assert_eq!(grams, vec!["a", "☃", "ü"]);
+        assert_eq!(qgrams("a☃ü", 3).collect::<Vec<_>>(), vec!["a☃ü"]);
+        assert_eq!(qgrams("a☃ü", 4).next(), None);
+        assert_eq!(qgrams("", 1).next(), None);
+    }
+
     #[test]
     fn empty_qgram() {
         let s: Vec<_> = "".chars().collect();
@@ -700,4 +2740,396 @@ mod tests {
 
         assert_eq!(eq_map(q1, q2), vec![(1, 1), (1, 1), (0, 1), (0, 1), (0, 1)]);
     }
+
+    #[test]
+    fn tversky_generalizes_jaccard_dice() {
+        let words = [
+            "", "a", "night", "nacht", "nightly", "kitten", "sitting", "ab☃c",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                let jaccard = Jaccard::new(2).str_normalized(a, b);
+                let tversky = Tversky::new(2, 1., 1.).str_normalized(a, b);
+                assert!((jaccard - tversky).abs() < 1e-12, "{} {}", a, b);
+
+                let dice = SorensenDice::new(2).normalized(a.chars(), b.chars());
+                let tversky = Tversky::new(2, 0.5, 0.5).normalized(a.chars(), b.chars());
+                assert!((dice - tversky).abs() < 1e-12, "{} {}", a, b);
+            }
+        }
+        assert_eq!(Tversky::new(2, 0., 0.).str_distance("ab", "cd"), 1.);
+        assert_eq!(Tversky::new(2, 0., 0.).str_distance("abc", "bcd"), 0.);
+    }
+
+    #[test]
+    fn overlap_distance() {
+        assert_eq!(Overlap::new(1).str_distance("", ""), 0.);
+        assert_eq!(Overlap::new(1).str_distance("", "abc"), 1.);
+        assert_eq!(Overlap::new(2).str_distance("abc", "abc"), 0.);
+        assert_eq!(Overlap::new(2).str_distance("abc", "xyz"), 1.);
+        // {ab, bc} is a subset of {ab, bc, cd}
+        assert_eq!(Overlap::new(2).str_distance("abc", "abcd"), 0.);
+        // {ni, ig, gh, ht} ∩ {na, ac, ch, ht} = {ht}
+        assert_eq!(Overlap::new(2).str_distance("night", "nacht"), 0.75);
+        // all bigrams of "night" are bigrams of "nightly"
+        assert_eq!(Overlap::new(2).str_distance("night", "nightly"), 0.);
+        assert_eq!(
+            Overlap::new(2).str_distance("night", "nightly"),
+            Overlap::new(2).distance("night".chars(), "nightly".chars())
+        );
+        assert_eq!(
+            Overlap::new(2).set_counts("night".chars(), "nacht".chars()),
+            (4, 4, 1)
+        );
+    }
+
+    #[test]
+    fn slice_and_generic_agree() {
+        let tokens: Vec<Vec<&str>> = [
+            "",
+            "the",
+            "the quick brown fox",
+            "the quick red fox",
+            "quick brown the fox",
+            "a b a b a b",
+        ]
+        .iter()
+        .map(|s| s.split_whitespace().collect())
+        .collect();
+        for a in tokens.iter() {
+            for b in tokens.iter() {
+                for q in 1..4 {
+                    assert_eq!(
+                        QGram::new(q).distance_slice(a, b),
+                        QGram::new(q).distance(a, b)
+                    );
+                    assert_eq!(
+                        Cosine::new(q).distance_slice(a, b).to_bits(),
+                        Cosine::new(q).distance(a, b).to_bits()
+                    );
+                    assert_eq!(
+                        Jaccard::new(q).distance_slice(a, b).to_bits(),
+                        Jaccard::new(q).distance(a, b).to_bits()
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            Jaccard::new(1).distance_slice(&[1, 2, 3], &[1u8, 2, 4]),
+            Jaccard::new(1).str_distance("abc", "abd")
+        );
+    }
+
+    #[test]
+    fn hashed_and_generic_agree() {
+        let tokens: Vec<Vec<u32>> = vec![
+            vec![],
+            vec![1],
+            vec![1, 2, 3, 4],
+            vec![1, 2, 4, 3],
+            vec![4, 3, 2, 1, 4, 3],
+            vec![7, 7, 7, 7],
+            vec![1, 7, 2, 7, 3],
+        ];
+        for a in tokens.iter() {
+            for b in tokens.iter() {
+                for q in 1..4 {
+                    assert_eq!(
+                        QGram::new(q).distance_hashed(a, b),
+                        QGram::new(q).distance(a, b)
+                    );
+                    assert_eq!(
+                        QGram::with_padding(q).distance_hashed(a, b),
+                        QGram::with_padding(q).distance(a, b)
+                    );
+                    // compare the bits to treat `NaN` as equal
+                    let agree = |hashed: f64, generic: f64| {
+                        assert_eq!(hashed.to_bits(), generic.to_bits(), "{:?} {:?} {}", a, b, q)
+                    };
+                    agree(
+                        Cosine::new(q).distance_hashed(a, b),
+                        Cosine::new(q).distance(a, b),
+                    );
+                    agree(
+                        Jaccard::new(q).distance_hashed(a, b),
+                        Jaccard::new(q).distance(a, b),
+                    );
+                    agree(
+                        SorensenDice::new(q).distance_hashed(a, b),
+                        SorensenDice::new(q).distance(a, b),
+                    );
+                    agree(
+                        Overlap::new(q).distance_hashed(a, b),
+                        Overlap::new(q).distance(a, b),
+                    );
+                    agree(
+                        Tversky::new(q, 0.5, 0.25).distance_hashed(a, b),
+                        Tversky::new(q, 0.5, 0.25).distance(a, b),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn positional_qgram() {
+        assert_eq!(QGram::new(1).str_distance("abcd", "cdab"), 0);
+        assert_eq!(PositionalQGram::new(1, 1).str_distance("abcd", "cdab"), 8);
+        assert_eq!(
+            PositionalQGram::new(1, 1).str_normalized("abcd", "cdab"),
+            1.
+        );
+        assert_eq!(PositionalQGram::new(2, 1).str_distance("abcd", "cdab"), 6);
+        assert_eq!(PositionalQGram::new(2, 2).str_distance("abcd", "cdab"), 2);
+        assert_eq!(PositionalQGram::new(2, 0).str_distance("abcd", "abcd"), 0);
+        assert_eq!(PositionalQGram::new(2, 0).str_distance("xabcd", "abcd"), 7);
+        assert_eq!(PositionalQGram::new(2, 1).str_distance("xabcd", "abcd"), 1);
+        assert_eq!(PositionalQGram::new(2, 1).str_distance("", "a"), 0);
+        assert_eq!(PositionalQGram::new(2, 1).str_normalized("", "a"), 1.);
+        assert_eq!(PositionalQGram::new(2, 1).str_normalized("", ""), 0.);
+        // each q-gram is matched at most once
+        assert_eq!(PositionalQGram::new(1, 5).str_distance("aa", "a"), 1);
+        assert_eq!(
+            PositionalQGram::with_padding(2, 0).str_distance("ab", "ab"),
+            0
+        );
+        assert_eq!(
+            PositionalQGram::with_padding(2, 0).str_distance("ab", "b"),
+            5
+        );
+        assert_eq!(
+            PositionalQGram::with_padding(2, 0).str_normalized("ab", "b"),
+            1.
+        );
+
+        // a large window ignores the positions
+        let words = [
+            "", "a", "abc", "leia", "leela", "achieve", "acheive", "aabaab",
+        ];
+        for a in words.iter() {
+            for b in words.iter() {
+                for q in 1..4 {
+                    assert_eq!(
+                        PositionalQGram::new(q, usize::MAX).str_distance(a, b),
+                        QGram::new(q).str_distance(a, b)
+                    );
+                    assert_eq!(
+                        PositionalQGram::with_padding(q, usize::MAX).str_distance(a, b),
+                        QGram::with_padding(q).str_distance(a, b)
+                    );
+                }
+            }
+        }
+    }
+
+    /// Asserts that all paths of `dist` follow [`distance_without_qgrams`] for
+    /// inputs without q-grams of length 3.
+    fn assert_without_qgrams<D>(dist: D, padded: bool, to_profile: impl Fn(&D, &str, &str) -> f64)
+    where
+        D: DistanceMetric<Dist = f64>,
+    {
+        let mut cases = vec![("", "", 0.), ("", "a", 1.), ("a", "", 1.), ("", "abcd", 1.)];
+        if padded {
+            // padded inputs have q-grams unless they are empty
+            cases.push(("a", "b", 1.));
+        } else {
+            cases.extend([
+                ("ab", "ab", 0.),
+                ("ab", "ba", 1.),
+                ("ab", "abcd", 1.),
+                ("abcd", "ab", 1.),
+                ("a☃", "a☃", 0.),
+            ]);
+        }
+        for (a, b, expected) in cases {
+            let (chars_a, chars_b): (Vec<_>, Vec<_>) = (a.chars().collect(), b.chars().collect());
+            assert_eq!(dist.str_distance(a, b), expected, "{:?} {:?}", a, b);
+            assert_eq!(
+                dist.distance(&chars_a, &chars_b),
+                expected,
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert_eq!(dist.str_normalized(a, b), expected, "{:?} {:?}", a, b);
+            assert_eq!(
+                dist.normalized(&chars_a, &chars_b),
+                expected,
+                "{:?} {:?}",
+                a,
+                b
+            );
+            assert_eq!(to_profile(&dist, a, b), expected, "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn inputs_without_qgrams() {
+        assert_without_qgrams(Cosine::new(3), false, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Jaccard::new(3), false, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(SorensenDice::new(3), false, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Overlap::new(3), false, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Tversky::new(3, 1., 0.), false, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Cosine::with_padding(3), true, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Overlap::with_padding(3), true, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_without_qgrams(Tversky::with_padding(3, 0.5, 0.5), true, |d, a, b| {
+            d.distance_to_profile(&d.profile(a), b)
+        });
+        assert_eq!(Cosine::new(3).distance_slice(&["a", "b"], &["a", "b"]), 0.);
+        assert_eq!(Jaccard::new(3).distance_slice(&["a", "b"], &["b", "a"]), 1.);
+        assert_eq!(
+            SorensenDice::new(3).distance_hashed(vec![1, 2], vec![1, 2]),
+            0.
+        );
+        assert_eq!(Overlap::new(3).distance_hashed(vec![1, 2], vec![2, 1]), 1.);
+
+        assert_eq!(PositionalQGram::new(3, 1).str_normalized("ab", "ab"), 0.);
+        assert_eq!(PositionalQGram::new(3, 1).str_normalized("ab", "ba"), 1.);
+        assert_eq!(PositionalQGram::new(3, 1).str_normalized("ab", "abcd"), 1.);
+        assert_eq!(
+            PositionalQGram::with_padding(3, 1).str_normalized("", ""),
+            0.
+        );
+        assert_eq!(
+            PositionalQGram::with_padding(3, 1).str_normalized("", "a"),
+            1.
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(QGram::try_new(0).unwrap_err(), QGramError::ZeroLength);
+        assert_eq!(Cosine::try_new(0).unwrap_err(), QGramError::ZeroLength);
+        assert_eq!(Jaccard::try_new(0).unwrap_err(), QGramError::ZeroLength);
+        assert_eq!(
+            SorensenDice::try_new(0).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert_eq!(Overlap::try_new(0).unwrap_err(), QGramError::ZeroLength);
+        assert_eq!(
+            Tversky::try_new(0, 1., 1.).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert_eq!(
+            Tversky::try_new(2, -1., 1.).unwrap_err(),
+            QGramError::NegativeWeight
+        );
+        assert_eq!(
+            Tversky::try_new(2, 1., f64::NAN).unwrap_err(),
+            QGramError::NanWeight
+        );
+        assert_eq!(
+            Tversky::try_new(2, f64::NAN, -1.).unwrap_err(),
+            QGramError::NanWeight
+        );
+        assert_eq!(
+            QGram::try_with_padding(0).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert_eq!(
+            Jaccard::try_with_padding(2)
+                .unwrap()
+                .str_distance("ab", "ba"),
+            Jaccard::with_padding(2).str_distance("ab", "ba")
+        );
+        assert_eq!(
+            Tversky::try_with_padding(2, -1., 1.).unwrap_err(),
+            QGramError::NegativeWeight
+        );
+        assert_eq!(
+            PositionalQGram::try_with_padding(0, 1).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert_eq!(
+            QGramProfile::try_new("abc", 0).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert_eq!(
+            QGramProfile::try_with_padding("abc", 2).unwrap(),
+            QGramProfile::with_padding("abc", 2)
+        );
+        assert!(try_qgrams("abc", 0).is_err());
+        assert_eq!(try_qgrams("abc", 2).unwrap().count(), 2);
+        assert_eq!(
+            PositionalQGram::try_new(0, 1).unwrap_err(),
+            QGramError::ZeroLength
+        );
+        assert!(QGramIter::try_new(&[1, 2], 0).is_err());
+        assert_eq!(
+            QGramError::ZeroLength.to_string(),
+            "the length of the q-grams must not be 0"
+        );
+
+        let jaccard = Jaccard::try_new(2).unwrap();
+        assert_eq!(
+            jaccard.str_distance("nacht", "night"),
+            Jaccard::new(2).str_distance("nacht", "night")
+        );
+        assert_eq!(
+            QGramIter::try_new(&[1, 2, 3], 2)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![&[1, 2][..], &[2, 3][..]]
+        );
+        assert!(Tversky::try_new(2, 0., 0.5).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_zero_length() {
+        Cosine::new(0);
+    }
+
+    #[test]
+    fn adaptive_jaccard() {
+        let adaptive = AdaptiveJaccard::new(2, 4);
+        assert_eq!((adaptive.max_q(), adaptive.len_per_q()), (2, 4));
+        assert_eq!(adaptive.q(0, 20), 1);
+        assert_eq!(adaptive.q(7, 20), 1);
+        assert_eq!(adaptive.q(8, 8), 2);
+        assert_eq!(adaptive.q(100, 200), 2);
+
+        // a single substituted char, for lengths from 1 to 12
+        let base = "abcdefghijkl";
+        let mut prev = 1.;
+        for len in 1..=base.len() {
+            let a = &base[..len];
+            let b = format!("{}X", &base[..len - 1]);
+            let fixed = Jaccard::new(2).str_normalized(a, &b);
+            let dist = adaptive.str_normalized(a, &b);
+            assert_eq!(dist, adaptive.distance(a.chars(), b.chars()));
+            if len < 3 {
+                // too short for a fixed bigram to tell the difference
+                assert_eq!(fixed, 1.);
+            }
+            if len > 1 {
+                assert!(dist < 1., "{}", len);
+                // the distance decreases with every char, unlike the jump of the fixed q
+                assert!(dist <= prev, "{}", len);
+            }
+            if len >= 8 {
+                assert_eq!(dist, fixed);
+            }
+            prev = dist;
+        }
+
+        assert_eq!(adaptive.str_distance("", ""), 0.);
+        assert_eq!(adaptive.str_distance("", "a"), 1.);
+        assert_eq!(adaptive.str_distance("a", "a"), 0.);
+        assert_eq!(adaptive.str_distance("a", "b"), 1.);
+    }
 }
@@ -1,3 +1,7 @@
+use alloc::vec;
+use core::cmp;
+
+use crate::utils::{eq_by, Flipped};
 use crate::DistanceMetric;
 
 /// The distance between two strings is defined as one minus  the number of
@@ -5,6 +9,10 @@ use crate::DistanceMetric;
 /// strings. Matching characters are those in the longest common subsequence
 /// plus, recursively, matching characters in the unmatched region on either
 /// side of the longest common subsequence.
+///
+/// If there are multiple longest common substrings, the matching characters
+/// depend on which one is chosen. The larger number of matching characters of
+/// both input orders is used, so that the distance is symmetric.
 pub struct RatcliffObershelp;
 
 impl DistanceMetric for RatcliffObershelp {
@@ -19,18 +27,7 @@ impl DistanceMetric for RatcliffObershelp {
         <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
         <T as IntoIterator>::Item: PartialEq,
     {
-        let a = a.into_iter();
-        let b = b.into_iter();
-        let len_a = a.clone().count();
-        let len_b = b.clone().count();
-
-        let matched = SequenceMatcher::new(a, b, len_a, len_b).match_sequences();
-
-        if len_a + len_b == 0 {
-            0.
-        } else {
-            1.0 - 2. * matched as f64 / (len_a + len_b) as f64
-        }
+        ratcliff_obershelp(a.into_iter(), b.into_iter())
     }
     fn normalized<S, T>(&self, a: S, b: T) -> f64
     where
@@ -45,6 +42,54 @@ impl DistanceMetric for RatcliffObershelp {
     }
 }
 
+impl RatcliffObershelp {
+    /// Evaluates the distance between `a` and `b` like
+    /// [`DistanceMetric::distance`], but compares the elements with the
+    /// predicate `eq` instead of `PartialEq`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::RatcliffObershelp;
+    /// let eq = |a: &char, b: &char| a.eq_ignore_ascii_case(b);
+    /// assert_eq!(RatcliffObershelp.distance_by("Kitten".chars(), "kITTEN".chars(), eq), 0.);
+    /// ```
+    pub fn distance_by<S, T, F>(&self, a: S, b: T, eq: F) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        F: Fn(&<S as IntoIterator>::Item, &<T as IntoIterator>::Item) -> bool,
+    {
+        ratcliff_obershelp(eq_by(a, &eq), eq_by(b, &eq))
+    }
+}
+
+/// The distance of [`RatcliffObershelp`] between `a` and `b`.
+fn ratcliff_obershelp<S, T>(a: S, b: T) -> f64
+where
+    S: Iterator + Clone,
+    T: Iterator + Clone,
+    <S as Iterator>::Item: PartialEq<<T as Iterator>::Item>,
+{
+    let len_a = a.clone().count();
+    let len_b = b.clone().count();
+
+    // the longest common substring is ambiguous if there are multiple of
+    // the same length, so both directions are matched to be symmetric
+    let matched = cmp::max(
+        SequenceMatcher::new(a.clone(), b.clone(), len_a, len_b).match_sequences(),
+        SequenceMatcher::new(b.map(Flipped), a.map(Flipped), len_b, len_a).match_sequences(),
+    );
+
+    if len_a + len_b == 0 {
+        0.
+    } else {
+        1.0 - 2. * matched as f64 / (len_a + len_b) as f64
+    }
+}
+
 struct SequenceMatcher<S, T>
 where
     S: Iterator + Clone,
@@ -57,10 +102,18 @@ where
     len1: usize,
     /// The length of iterator s2
     len2: usize,
+}
+
+/// A region of both iters that is not matched yet.
+struct Region {
     /// Index where the to start matching on s1
     start1: usize,
+    /// The number of items of s1 in the region
+    len1: usize,
     /// Index where the to start matching on s2
     start2: usize,
+    /// The number of items of s2 in the region
+    len2: usize,
 }
 
 impl<S, T> SequenceMatcher<S, T>
@@ -71,54 +124,56 @@ where
 {
     #[inline]
     fn new(s1: S, s2: T, len1: usize, len2: usize) -> Self {
-        Self {
-            len1,
-            len2,
-            s1,
-            s2,
-            start1: 0,
-            start2: 0,
-        }
+        Self { s1, s2, len1, len2 }
     }
 
     /// Finds the longest substr of both iters the recursively find the
     /// longest substr of both tails.
+    ///
+    /// The regions that are left to match are kept on a stack instead of
+    /// recursing, since the regions may be nested as deep as the iters are
+    /// long.
     fn match_sequences(self) -> usize {
-        let subseq = longest_common_subsequence(
-            self.s1.clone().skip(self.start1).take(self.len1),
-            self.s2.clone().skip(self.start2).take(self.len2),
-            self.len1,
-            self.len2,
-        );
+        let mut ctn = 0;
+        let mut regions = vec![Region {
+            start1: 0,
+            len1: self.len1,
+            start2: 0,
+            len2: self.len2,
+        }];
 
-        if subseq.is_empty() {
-            // stop if there is no common substring
-            return 0;
-        }
+        while let Some(region) = regions.pop() {
+            let subseq = longest_common_subsequence(
+                self.s1.clone().skip(region.start1).take(region.len1),
+                self.s2.clone().skip(region.start2).take(region.len2),
+                region.len1,
+                region.len2,
+            );
 
-        let mut ctn = subseq.len;
+            if subseq.is_empty() {
+                // stop if there is no common substring
+                continue;
+            }
 
-        // add the longest common substring that happens before
-        let before = SequenceMatcher {
-            s1: self.s1.clone(),
-            s2: self.s2.clone(),
-            len1: subseq.s1_idx,
-            len2: subseq.s2_idx,
-            start1: self.start1,
-            start2: self.start2,
-        };
-        ctn += before.match_sequences();
-
-        // add the longest common substring that happens after
-        let after = SequenceMatcher {
-            s1: self.s1,
-            s2: self.s2,
-            len1: self.len1 - (subseq.s1_idx + subseq.len),
-            len2: self.len2 - (subseq.s2_idx + subseq.len),
-            start1: self.start1 + subseq.s1_idx + subseq.len,
-            start2: self.start2 + subseq.s2_idx + subseq.len,
-        };
-        ctn + after.match_sequences()
+            ctn += subseq.len;
+
+            // the region that happens before the longest common substring
+            regions.push(Region {
+                start1: region.start1,
+                len1: subseq.s1_idx,
+                start2: region.start2,
+                len2: subseq.s2_idx,
+            });
+
+            // the region that happens after the longest common substring
+            regions.push(Region {
+                start1: region.start1 + subseq.s1_idx + subseq.len,
+                len1: region.len1 - (subseq.s1_idx + subseq.len),
+                start2: region.start2 + subseq.s2_idx + subseq.len,
+                len2: region.len2 - (subseq.s2_idx + subseq.len),
+            });
+        }
+        ctn
     }
 }
 
@@ -196,6 +251,57 @@ mod tests {
         assert!(subs.is_empty());
     }
 
+    /// The matching chars of the recursive definition.
+    fn recursive_matches(a: &[char], b: &[char]) -> usize {
+        let subseq = longest_common_subsequence(a.iter(), b.iter(), a.len(), b.len());
+        if subseq.is_empty() {
+            return 0;
+        }
+        subseq.len
+            + recursive_matches(&a[..subseq.s1_idx], &b[..subseq.s2_idx])
+            + recursive_matches(
+                &a[subseq.s1_idx + subseq.len..],
+                &b[subseq.s2_idx + subseq.len..],
+            )
+    }
+
+    #[test]
+    fn matches_recursive_definition() {
+        let alphabet = ['a', 'b', 'c', '☃'];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        for _ in 0..200 {
+            let a: Vec<char> = (0..next() % 30).map(|_| alphabet[next() % 4]).collect();
+            let b: Vec<char> = (0..next() % 30).map(|_| alphabet[next() % 4]).collect();
+            let matched =
+                SequenceMatcher::new(a.iter(), b.iter(), a.len(), b.len()).match_sequences();
+            assert_eq!(matched, recursive_matches(&a, &b));
+        }
+    }
+
+    #[test]
+    fn deep_regions() {
+        // every char matches on its own and leaves the rest as the only
+        // unmatched region, which nests the regions as deep as the input is
+        // long
+        let a: Vec<char> = (0..300)
+            .map(|i| char::from_u32(0x4e00 + i).unwrap())
+            .collect();
+        let b: Vec<char> = a.iter().flat_map(|c| vec![*c, '-']).collect();
+        let dist = std::thread::Builder::new()
+            .stack_size(32 * 1024)
+            .spawn(move || RatcliffObershelp.distance(&a, &b))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert!((dist - 1. / 3.).abs() < 1e-12);
+    }
+
     #[test]
     fn ratcliff_obershelp() {
         assert_eq!(RatcliffObershelp.str_distance("", "kitten"), 1.0);
@@ -0,0 +1,253 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use crate::qgram::{jaccard_distance, sorensen_dice_distance};
+use crate::utils::Map;
+use crate::DistanceMetric;
+
+/// The SetJaccard distance compares the sets of distinct elements of both
+/// inputs, e.g. two lists of tags.
+///
+/// Unlike [`crate::Jaccard`] the elements are compared as they are, without
+/// slicing the inputs into q-grams first. For `str`s the elements are the
+/// chars.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - |A ∩ B| / |A ∪ B|
+/// ```
+///
+/// If both inputs are empty a value of `0.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, SetJaccard};
+/// let dist = SetJaccard.distance(&["red", "blue"], &["blue", "green"]);
+/// assert!((dist - 2. / 3.).abs() < 1e-12);
+/// assert_eq!(SetJaccard.distance(&["red", "red", "blue"], &["blue", "red"]), 0.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetJaccard;
+
+/// The SetDice distance compares the sets of distinct elements of both inputs,
+/// e.g. two lists of tags.
+///
+/// Unlike [`crate::SorensenDice`] the elements are compared as they are,
+/// without slicing the inputs into q-grams first. For `str`s the elements are
+/// the chars.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - 2 * |A ∩ B| / (|A| + |B|)
+/// ```
+///
+/// If both inputs are empty a value of `0.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, SetDice};
+/// assert_eq!(SetDice.distance(&["red", "blue"], &["blue", "green"]), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SetDice;
+
+/// Implements [`DistanceMetric`] and `distance_hashed` for metrics of the
+/// number of distinct elements in a, in b and in both.
+macro_rules! impl_set_metric {
+    ($($metric:ty => $dist:path),*) => {
+        $(
+            impl $metric {
+                /// Evaluates the distance between `a` and `b` like
+                /// [`DistanceMetric::distance`], but collects the sets via
+                /// hashing.
+                ///
+                /// This requires the elements to implement `Hash` and `Ord`,
+                /// but avoids the quadratic number of comparisons of the
+                /// generic distance. Without the `std` feature the sets are
+                /// collected via ordering instead, so `Ord` is required with
+                /// the `std` feature as well.
+                pub fn distance_hashed<S, T>(&self, a: S, b: T) -> f64
+                where
+                    S: IntoIterator,
+                    T: IntoIterator<Item = <S as IntoIterator>::Item>,
+                    <S as IntoIterator>::Item: Hash + Ord,
+                {
+                    set_distance(count_distinct_hashed(a, b), $dist)
+                }
+            }
+
+            impl DistanceMetric for $metric {
+                type Dist = f64;
+
+                fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+                where
+                    S: IntoIterator,
+                    T: IntoIterator,
+                    <S as IntoIterator>::IntoIter: Clone,
+                    <T as IntoIterator>::IntoIter: Clone,
+                    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+                    <T as IntoIterator>::Item: PartialEq,
+                {
+                    set_distance(count_distinct(a, b), $dist)
+                }
+
+                fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+                where
+                    S: AsRef<str>,
+                    T: AsRef<str>,
+                {
+                    self.distance_hashed(a.as_ref().chars(), b.as_ref().chars())
+                }
+
+                fn normalized<S, T>(&self, a: S, b: T) -> f64
+                where
+                    S: IntoIterator,
+                    T: IntoIterator,
+                    <S as IntoIterator>::IntoIter: Clone,
+                    <T as IntoIterator>::IntoIter: Clone,
+                    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+                    <T as IntoIterator>::Item: PartialEq,
+                {
+                    self.distance(a, b)
+                }
+
+                fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+                where
+                    S: AsRef<str>,
+                    T: AsRef<str>,
+                {
+                    self.str_distance(a, b)
+                }
+            }
+        )*
+    };
+}
+
+impl_set_metric!(SetJaccard => jaccard_distance, SetDice => sorensen_dice_distance);
+
+/// Applies `dist` to the number of distinct elements in a, in b and in both,
+/// unless both sets are empty.
+fn set_distance(counts: (usize, usize, usize), dist: fn((usize, usize, usize)) -> f64) -> f64 {
+    if counts.0 + counts.1 == 0 {
+        0.
+    } else {
+        dist(counts)
+    }
+}
+
+/// The number of distinct elements in a, in b and in both.
+fn count_distinct<S, T>(a: S, b: T) -> (usize, usize, usize)
+where
+    S: IntoIterator,
+    T: IntoIterator,
+    <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+    <T as IntoIterator>::Item: PartialEq,
+{
+    let mut distinct_a: Vec<<S as IntoIterator>::Item> = Vec::new();
+    for x in a {
+        if !distinct_a.contains(&x) {
+            distinct_a.push(x);
+        }
+    }
+    let mut distinct_b: Vec<<T as IntoIterator>::Item> = Vec::new();
+    for y in b {
+        if !distinct_b.contains(&y) {
+            distinct_b.push(y);
+        }
+    }
+    let num_intersect = distinct_a
+        .iter()
+        .filter(|x| distinct_b.iter().any(|y| *x == y))
+        .count();
+    (distinct_a.len(), distinct_b.len(), num_intersect)
+}
+
+/// The number of distinct elements in a, in b and in both, like
+/// [`count_distinct`] but collects the sets via hashing, or via ordering
+/// without the `std` feature.
+fn count_distinct_hashed<S, T>(a: S, b: T) -> (usize, usize, usize)
+where
+    S: IntoIterator,
+    T: IntoIterator<Item = <S as IntoIterator>::Item>,
+    <S as IntoIterator>::Item: Hash + Ord,
+{
+    // whether an element is in a and whether it is in b
+    let mut sets: Map<<S as IntoIterator>::Item, (bool, bool)> = Map::new();
+    for x in a {
+        sets.entry(x).or_default().0 = true;
+    }
+    for y in b {
+        sets.entry(y).or_default().1 = true;
+    }
+    sets.values().fold(
+        (0, 0, 0),
+        |(num_dist_a, num_dist_b, num_intersect), &(in_a, in_b)| {
+            (
+                num_dist_a + in_a as usize,
+                num_dist_b + in_b as usize,
+                num_intersect + (in_a && in_b) as usize,
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Jaccard, SorensenDice};
+
+    #[test]
+    fn set_jaccard() {
+        let (a, b) = (["red", "blue"], ["blue", "green"]);
+        assert!((SetJaccard.distance(&a, &b) - 2. / 3.).abs() < 1e-12);
+        assert_eq!(
+            SetJaccard.distance(&a, &b),
+            SetJaccard.distance_hashed(&a, &b)
+        );
+        assert_eq!(SetJaccard.distance(&a, &b), SetJaccard.distance(&b, &a));
+        // duplicates and the order don't matter
+        assert_eq!(
+            SetJaccard.distance(&["red", "blue", "red"], &["blue", "red"]),
+            0.
+        );
+        assert_eq!(SetJaccard.distance(&a, &["yellow"]), 1.);
+        assert_eq!(
+            SetJaccard.distance(Vec::<&str>::new(), Vec::<&str>::new()),
+            0.
+        );
+        assert_eq!(SetJaccard.distance(&a, &[] as &[&str]), 1.);
+        assert_eq!(SetJaccard.distance_hashed(Vec::<u8>::new(), Vec::new()), 0.);
+
+        for (s1, s2) in [("", ""), ("abc", "ccc"), ("kitten", "sitting")].iter() {
+            assert_eq!(
+                SetJaccard.str_distance(s1, s2),
+                Jaccard::new(1).str_distance(s1, s2)
+            );
+            assert_eq!(
+                SetJaccard.str_distance(s1, s2),
+                SetJaccard.distance(s1.chars(), s2.chars())
+            );
+        }
+    }
+
+    #[test]
+    fn set_dice() {
+        let (a, b) = (["red", "blue"], ["blue", "green"]);
+        assert_eq!(SetDice.distance(&a, &b), 0.5);
+        assert_eq!(SetDice.distance_hashed(&a, &b), 0.5);
+        assert_eq!(SetDice.distance(&["a", "a", "b"], &["b", "a"]), 0.);
+        assert_eq!(SetDice.distance(Vec::<&str>::new(), Vec::<&str>::new()), 0.);
+        assert_eq!(SetDice.distance(&a, &[] as &[&str]), 1.);
+
+        for (s1, s2) in [("", ""), ("monday", "montag"), ("nacht", "night")].iter() {
+            assert_eq!(
+                SetDice.str_distance(s1, s2),
+                SorensenDice::new(1).str_distance(s1, s2)
+            );
+        }
+    }
+}
@@ -0,0 +1,519 @@
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::utils::DelimDistinct;
+use crate::DistanceMetric;
+
+/// The default number of elements [`Sift4`] looks ahead to find a match.
+const DEFAULT_MAX_OFFSET: usize = 5;
+
+/// The simplest variant of the Sift4 algorithm by Siderite Zackwehdex, a fast
+/// `O(n)` approximation of the [`crate::Levenshtein`] distance.
+///
+/// Both inputs are walked simultaneously. Whenever the elements differ, the
+/// next `max_offset` elements of both inputs are searched for a match and the
+/// walk is continued from there. The distance is the length of the longer
+/// input minus the number of elements matched this way.
+///
+/// The result is close to, but not always equal to the Levenshtein distance.
+/// It may also be lower, since an element can be matched after the walk
+/// skipped past its counterpart in the other input. The metric is not
+/// symmetric either, the walk searches ahead in the first input before the
+/// second one. A common prefix and suffix are skipped before the walk.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Levenshtein, Sift4};
+/// assert_eq!(Sift4::default().str_distance("kitten", "sitting"), 3);
+/// assert_eq!(Sift4::new(5).str_distance("", "abc"), 3);
+/// // undercounts the Levenshtein distance and depends on the order
+/// assert_eq!(Sift4::default().str_distance("cbc", "bcd"), 1);
+/// assert_eq!(*Levenshtein::default().str_distance("cbc", "bcd"), 2);
+/// assert_eq!(Sift4::default().str_distance("bcd", "cbc"), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sift4 {
+    /// How many elements are searched ahead for a match.
+    max_offset: usize,
+    /// The distance at which the evaluation stops.
+    ///
+    /// If set, the walk is aborted as soon as the intermediate distance
+    /// reaches this value, in which case the returned distance is at least
+    /// `max_distance`.
+    max_distance: Option<usize>,
+}
+
+impl Default for Sift4 {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_OFFSET)
+    }
+}
+
+impl Sift4 {
+    /// Creates a new [`Sift4`] that, whenever the current elements differ,
+    /// compares each of them with the `max_offset` elements of the other input
+    /// that start at the current position.
+    ///
+    /// A larger offset finds matches after longer insertions or deletions, at
+    /// the cost of more comparisons per mismatch. An offset of 0 or 1 only
+    /// compares the elements at the same position.
+    pub fn new(max_offset: usize) -> Self {
+        Self {
+            max_offset,
+            max_distance: None,
+        }
+    }
+
+    /// Creates a new [`Sift4`] like [`Sift4::new`], that stops the walk as
+    /// soon as the intermediate distance reaches `max_distance`.
+    ///
+    /// The returned distance is then at least `max_distance`, but not
+    /// necessarily the distance of the full walk.
+    pub fn with_max_distance(max_offset: usize, max_distance: usize) -> Self {
+        Self {
+            max_offset,
+            max_distance: Some(max_distance),
+        }
+    }
+
+    /// Walks both slices and returns the approximated distance.
+    fn sift<A, B>(&self, s1: &[A], s2: &[B]) -> usize
+    where
+        A: PartialEq<B>,
+    {
+        let (l1, l2) = (s1.len(), s2.len());
+        let (mut c1, mut c2) = (0, 0);
+        // the length of the matched subsequence and the current run of it
+        let mut lcss = 0;
+        let mut local_cs = 0;
+
+        while c1 < l1 && c2 < l2 {
+            if s1[c1] == s2[c2] {
+                local_cs += 1;
+            } else {
+                lcss += local_cs;
+                local_cs = 0;
+                if let Some(max_distance) = self.max_distance {
+                    let dist = cmp::max(c1, c2) - lcss;
+                    if dist >= max_distance {
+                        return dist;
+                    }
+                }
+                if c1 != c2 {
+                    c1 = cmp::max(c1, c2);
+                    c2 = c1;
+                }
+                for offset in 0..self.max_offset {
+                    if c1 + offset >= l1 && c2 + offset >= l2 {
+                        break;
+                    }
+                    if c1 + offset < l1 && c2 < l2 && s1[c1 + offset] == s2[c2] {
+                        c1 += offset;
+                        local_cs += 1;
+                        break;
+                    }
+                    if c2 + offset < l2 && c1 < l1 && s1[c1] == s2[c2 + offset] {
+                        c2 += offset;
+                        local_cs += 1;
+                        break;
+                    }
+                }
+            }
+            c1 += 1;
+            c2 += 1;
+        }
+        lcss += local_cs;
+        cmp::max(l1, l2) - lcss
+    }
+}
+
+impl DistanceMetric for Sift4 {
+    type Dist = usize;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        // exclude matching prefix and suffix
+        let delim = DelimDistinct::new_skip_take(a.into_iter(), b.into_iter());
+        if delim.remaining_s1() == 0 || delim.remaining_s2() == 0 {
+            return cmp::max(delim.remaining_s1(), delim.remaining_s2());
+        }
+        let s1: Vec<_> = delim.distinct_s1.collect();
+        let s2: Vec<_> = delim.distinct_s2.collect();
+        self.sift(&s1, &s2)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let max_len = cmp::max(a.clone().count(), b.clone().count());
+        if max_len == 0 {
+            0.
+        } else {
+            (self.distance(a, b) as f64 / max_len as f64).min(1.)
+        }
+    }
+}
+
+/// The variants of the Sift algorithms by Siderite Zackwehdex, that trade the
+/// accuracy of the approximated [`crate::Levenshtein`] distance for speed
+/// differently.
+///
+/// All variants depend on the order of the inputs, see [`Sift`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SiftAlgorithm {
+    /// Sift3, the fastest and least accurate variant.
+    ///
+    /// Both inputs are walked with a single cursor and the distance is the
+    /// average length of both inputs minus the number of matched elements.
+    /// Transpositions are not detected, so swapped elements count like
+    /// substitutions, and the distance may be fractional.
+    Simple,
+    /// The common Sift4, which walks both inputs with separate cursors and
+    /// counts each transposed pair of matches once.
+    ///
+    /// This is a bit slower than [`SiftAlgorithm::Simple`], but usually much
+    /// closer to the Levenshtein distance.
+    #[default]
+    Common,
+    /// The extended Sift4, which looks up every match in the previous matches
+    /// to detect transposed tokens, i.e. elements, and weighs them.
+    ///
+    /// Transpositions cost more the farther the tokens moved, and longer runs
+    /// of matches are rewarded, so that inputs with long common substrings
+    /// are considered closer. This is the slowest variant and deviates the
+    /// most from the Levenshtein distance, but ranks similar inputs better.
+    Extended,
+}
+
+/// A match of the elements at `c1` and `c2`, whose later matches are checked
+/// for transpositions.
+#[derive(Debug, Clone, Copy)]
+struct Offset {
+    c1: usize,
+    c2: usize,
+    trans: bool,
+}
+
+/// The Sift distance approximates the [`crate::Levenshtein`] distance with the
+/// Sift3 or Sift4 algorithm, selected via [`SiftAlgorithm`].
+///
+/// Unlike [`Sift4`], which always uses the simplest variant of Sift4, the
+/// distance is a float, since Sift3 averages the lengths of both inputs and
+/// the extended Sift4 weighs transpositions and matches. A common prefix and
+/// suffix are skipped before the walk.
+///
+/// None of the variants is symmetric: the walk searches ahead in the first
+/// input before the second one and continues from the first match it finds,
+/// so swapping the inputs can change the distance.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Sift, SiftAlgorithm};
+/// let sift3 = Sift::new(SiftAlgorithm::Simple, 5);
+/// assert_eq!(sift3.str_distance("kitten", "sitting"), 2.5);
+/// assert_eq!(Sift::default().str_distance("kitten", "sitting"), 3.);
+/// // transposed elements are detected by Sift4 only
+/// assert_eq!(sift3.str_distance("abcd", "acbd"), 2.);
+/// assert_eq!(Sift::default().str_distance("abcd", "acbd"), 1.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sift {
+    /// The variant of the algorithm.
+    algorithm: SiftAlgorithm,
+    /// How many elements are searched ahead for a match.
+    max_offset: usize,
+}
+
+impl Default for Sift {
+    fn default() -> Self {
+        Self::new(SiftAlgorithm::default(), DEFAULT_MAX_OFFSET)
+    }
+}
+
+impl Sift {
+    /// Creates a new [`Sift`] of the `algorithm` that searches `max_offset`
+    /// elements ahead for a match.
+    pub fn new(algorithm: SiftAlgorithm, max_offset: usize) -> Self {
+        Self {
+            algorithm,
+            max_offset,
+        }
+    }
+
+    /// The variant of the algorithm.
+    pub fn algorithm(&self) -> SiftAlgorithm {
+        self.algorithm
+    }
+
+    /// How many elements are searched ahead for a match.
+    pub fn max_offset(&self) -> usize {
+        self.max_offset
+    }
+
+    /// Walks both slices with a single cursor and returns the average length
+    /// minus the number of matches.
+    fn sift3<A, B>(&self, s1: &[A], s2: &[B]) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        let (l1, l2) = (s1.len(), s2.len());
+        let (mut c, mut offset1, mut offset2) = (0, 0, 0);
+        let mut lcs = 0;
+        while c + offset1 < l1 && c + offset2 < l2 {
+            if s1[c + offset1] == s2[c + offset2] {
+                lcs += 1;
+            } else {
+                offset1 = 0;
+                offset2 = 0;
+                for offset in 0..self.max_offset {
+                    if c + offset < l1 && s1[c + offset] == s2[c] {
+                        offset1 = offset;
+                        break;
+                    }
+                    if c + offset < l2 && s1[c] == s2[c + offset] {
+                        offset2 = offset;
+                        break;
+                    }
+                }
+            }
+            c += 1;
+        }
+        (l1 + l2) as f64 / 2. - lcs as f64
+    }
+
+    /// Walks both slices with separate cursors and returns the length of the
+    /// longer slice minus the matches plus the transpositions, which are
+    /// weighted if `extended`.
+    fn sift4<A, B>(&self, s1: &[A], s2: &[B], extended: bool) -> f64
+    where
+        A: PartialEq<B>,
+    {
+        // rewards longer runs of matches in the extended variant
+        let run_len = |local_cs: usize| {
+            if extended && local_cs > 0 {
+                local_cs as f64 - 1. / (local_cs as f64 + 1.)
+            } else {
+                local_cs as f64
+            }
+        };
+        let (l1, l2) = (s1.len(), s2.len());
+        let (mut c1, mut c2) = (0, 0);
+        let mut lcss = 0.;
+        let mut local_cs = 0;
+        let mut trans = 0.;
+        let mut offsets: Vec<Offset> = Vec::new();
+
+        while c1 < l1 && c2 < l2 {
+            if s1[c1] == s2[c2] {
+                local_cs += 1;
+                let mut is_trans = false;
+                let mut idx = 0;
+                while idx < offsets.len() {
+                    let offset = offsets[idx];
+                    if c1 <= offset.c1 || c2 <= offset.c2 {
+                        is_trans = c1.abs_diff(c2) >= offset.c1.abs_diff(offset.c2);
+                        let cost = if extended {
+                            // transpositions over a longer distance cost more
+                            1. + c1.abs_diff(offset.c1) as f64 / 9.
+                        } else {
+                            1.
+                        };
+                        if is_trans {
+                            trans += cost;
+                        } else if !offset.trans {
+                            offsets[idx].trans = true;
+                            trans += cost;
+                        }
+                        break;
+                    } else if c1 > offset.c2 && c2 > offset.c1 {
+                        offsets.remove(idx);
+                    } else {
+                        idx += 1;
+                    }
+                }
+                offsets.push(Offset {
+                    c1,
+                    c2,
+                    trans: is_trans,
+                });
+                c1 += 1;
+                c2 += 1;
+            } else {
+                lcss += run_len(local_cs);
+                local_cs = 0;
+                if c1 != c2 {
+                    c1 = cmp::min(c1, c2);
+                    c2 = c1;
+                }
+                // continue after the next match within the offset, if any
+                let (mut next1, mut next2) = (c1 + 1, c2 + 1);
+                for offset in 0..self.max_offset {
+                    if c1 + offset >= l1 && c2 + offset >= l2 {
+                        break;
+                    }
+                    if c1 + offset < l1 && s1[c1 + offset] == s2[c2] {
+                        next1 = c1 + offset;
+                        next2 = c2;
+                        break;
+                    }
+                    if c2 + offset < l2 && s1[c1] == s2[c2 + offset] {
+                        next1 = c1;
+                        next2 = c2 + offset;
+                        break;
+                    }
+                }
+                c1 = next1;
+                c2 = next2;
+            }
+            if c1 >= l1 || c2 >= l2 {
+                lcss += run_len(local_cs);
+                local_cs = 0;
+                c1 = cmp::min(c1, c2);
+                c2 = c1;
+            }
+        }
+        lcss += run_len(local_cs);
+        (cmp::max(l1, l2) as f64 - lcss + trans).max(0.)
+    }
+}
+
+impl DistanceMetric for Sift {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        // exclude matching prefix and suffix
+        let delim = DelimDistinct::new_skip_take(a.into_iter(), b.into_iter());
+        if delim.remaining_s1() == 0 || delim.remaining_s2() == 0 {
+            let (l1, l2) = (delim.remaining_s1(), delim.remaining_s2());
+            return match self.algorithm {
+                SiftAlgorithm::Simple => (l1 + l2) as f64 / 2.,
+                _ => cmp::max(l1, l2) as f64,
+            };
+        }
+        let s1: Vec<_> = delim.distinct_s1.collect();
+        let s2: Vec<_> = delim.distinct_s2.collect();
+        match self.algorithm {
+            SiftAlgorithm::Simple => self.sift3(&s1, &s2),
+            SiftAlgorithm::Common => self.sift4(&s1, &s2, false),
+            SiftAlgorithm::Extended => self.sift4(&s1, &s2, true),
+        }
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let max_len = cmp::max(a.clone().count(), b.clone().count());
+        if max_len == 0 {
+            0.
+        } else {
+            (self.distance(a, b) / max_len as f64).min(1.)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Levenshtein;
+
+    #[test]
+    fn sift4_approximates_levenshtein() {
+        let pairs = [
+            ("kitten", "sitting"),
+            ("saturday", "sunday"),
+            ("rosettacode", "raisethysword"),
+            ("abcdef", "badcfe"),
+            ("This is a test", "This is the test"),
+            ("", "abc"),
+            ("abc", "abc"),
+        ];
+        for (a, b) in pairs.iter() {
+            let sift = Sift4::default().str_distance(a, b);
+            let lev = *Levenshtein::default().str_distance(a, b);
+            assert!(sift.abs_diff(lev) <= 2, "{} {}: {} {}", a, b, sift, lev);
+        }
+        // not exact
+        assert_eq!(Sift4::default().str_distance("abcdef", "badcfe"), 3);
+        assert_eq!(*Levenshtein::default().str_distance("abcdef", "badcfe"), 4);
+    }
+
+    #[test]
+    fn sift4_max_distance() {
+        let sift = Sift4::with_max_distance(5, 2);
+        assert!(sift.str_distance("abcdefghij", "jihgfedcba") >= 2);
+        assert_eq!(sift.str_distance("kitten", "kitten"), 0);
+        assert_eq!(Sift4::new(5).normalized(&[1, 2, 3], &[1, 2, 4]), 1. / 3.);
+    }
+
+    #[test]
+    fn sift_algorithms() {
+        let sift = |algorithm| Sift::new(algorithm, 5);
+        let (a, b) = ("abcdef", "badcfe");
+        assert_eq!(sift(SiftAlgorithm::Simple).str_distance(a, b), 6.);
+        assert_eq!(sift(SiftAlgorithm::Common).str_distance(a, b), 3.);
+        assert_eq!(sift(SiftAlgorithm::Extended).str_distance(a, b), 4.5);
+        // the common variant may agree with the simplest variant Sift4,
+        // but it tracks transpositions and is not the same in general
+        assert_eq!(
+            sift(SiftAlgorithm::Common).str_distance(a, b),
+            Sift4::default().str_distance(a, b) as f64
+        );
+        assert_eq!(
+            sift(SiftAlgorithm::Common).str_distance("cbacdb", "daca"),
+            4.
+        );
+        assert_eq!(Sift4::default().str_distance("cbacdb", "daca"), 5);
+        assert_eq!(
+            sift(SiftAlgorithm::Simple).str_distance("kitten", "sitting"),
+            2.5
+        );
+
+        for algorithm in [
+            SiftAlgorithm::Simple,
+            SiftAlgorithm::Common,
+            SiftAlgorithm::Extended,
+        ]
+        .iter()
+        {
+            let sift = sift(*algorithm);
+            assert_eq!(sift.algorithm(), *algorithm);
+            assert_eq!(sift.str_distance("kitten", "kitten"), 0.);
+            assert_eq!(sift.str_normalized("", ""), 0.);
+            assert_eq!(sift.normalized(&[1, 2, 3], &[4, 5, 6]), 1.);
+        }
+        assert_eq!(Sift::default().algorithm(), SiftAlgorithm::Common);
+    }
+}
@@ -0,0 +1,338 @@
+use core::cmp;
+
+use crate::qgram::qgrams;
+use crate::DistanceMetric;
+
+/// A cheap summary of a str that allows rejecting pairs of strings, that are
+/// obviously too different, before evaluating an expensive metric.
+///
+/// The signature consists of the number of chars, the first and last char and
+/// a 64 bit set of all chars, where each char is mapped to a bit by its code
+/// point modulo 64.
+///
+/// # Soundness
+///
+/// [`Signature::could_match`] derives a lower bound for the number of edits
+/// needed to transform one str into the other:
+///  - the difference in length
+///  - the number of bits that are set for only one of the strings, since every
+///    such bit belongs to at least one char that needs to be edited
+///  - one edit, if the first or last chars differ
+///
+/// Rejections are therefore sound for [`crate::Levenshtein`] and
+/// [`crate::DamerauLevenshtein`], whose normalized distance is the number of
+/// edits divided by the length of the longer str. For all other metrics a
+/// rejection is only a heuristic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature {
+    /// The number of chars
+    len: usize,
+    /// The first char, if any
+    first: Option<char>,
+    /// The last char, if any
+    last: Option<char>,
+    /// Set of all chars, mapped to a bit by their code point modulo 64.
+    char_set: u64,
+}
+
+impl Signature {
+    /// Computes the signature of `s`.
+    pub fn compute(s: &str) -> Self {
+        let mut len = 0;
+        let mut char_set = 0u64;
+        for c in s.chars() {
+            len += 1;
+            char_set |= 1 << (c as u32 % 64);
+        }
+        Self {
+            len,
+            first: s.chars().next(),
+            last: s.chars().next_back(),
+            char_set,
+        }
+    }
+
+    /// The number of chars of the str.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the str was empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// A lower bound of the number of edits between both strings.
+    fn min_edits(&self, other: &Signature) -> usize {
+        let len_diff = self.len.abs_diff(other.len);
+        let only_self = (self.char_set & !other.char_set).count_ones() as usize;
+        let only_other = (other.char_set & !self.char_set).count_ones() as usize;
+        let ends = if self.first != other.first || self.last != other.last {
+            1
+        } else {
+            0
+        };
+        cmp::max(cmp::max(len_diff, ends), cmp::max(only_self, only_other))
+    }
+
+    /// Returns `false` if the normalized distance of the strings is guaranteed
+    /// to exceed `max_normalized`, see [`Signature`] for which metrics this is
+    /// sound.
+    pub fn could_match(&self, other: &Signature, max_normalized: f64) -> bool {
+        let max_len = cmp::max(self.len, other.len);
+        if max_len == 0 {
+            return true;
+        }
+        self.min_edits(other) as f64 / max_len as f64 <= max_normalized
+    }
+}
+
+/// Computes the 64 bit SimHash of the q-grams of length `q` of `s`.
+///
+/// Every q-gram is hashed to 64 bits and each bit of the SimHash is set if
+/// more q-grams have that bit set than unset. Small edits only change few
+/// q-grams and therefore few bits, so the [`simhash_distance`] of near
+/// duplicates is small, which makes it a cheap filter for near duplicate
+/// detection at scale.
+///
+/// The q-grams are hashed with a fixed function, so the SimHash of a str is
+/// stable across runs and platforms. A str without q-grams, i.e. if it is
+/// empty or shorter than `q`, has a SimHash of `0`.
+///
+/// # Panics
+///
+/// Panics if `q` is 0.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{simhash, simhash_distance};
+/// let a = simhash("the quick brown fox jumps over the lazy dog", 3);
+/// let b = simhash("the quick brown fox jumped over the lazy dog", 3);
+/// let c = simhash("lorem ipsum dolor sit amet, consectetur", 3);
+/// assert!(simhash_distance(a, b) < simhash_distance(a, c));
+/// ```
+pub fn simhash(s: &str, q: usize) -> u64 {
+    // the number of q-grams with each bit set minus those with the bit unset
+    let mut weights = [0i64; 64];
+    for qgram in qgrams(s, q) {
+        let hash = hash_qgram(qgram);
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) == 0 {
+                *weight -= 1;
+            } else {
+                *weight += 1;
+            }
+        }
+    }
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0, |simhash, (bit, _)| simhash | (1 << bit))
+}
+
+/// The Hamming distance between two SimHashes, i.e. the number of bits that
+/// differ, see [`simhash`].
+pub fn simhash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Hashes the bytes of the `qgram` with FNV-1a, followed by the finalizer of
+/// SplitMix64 to spread the bits evenly.
+fn hash_qgram(qgram: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in qgram.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+/// `Signed` short circuits the normalized distance of its `inner`
+/// [`DistanceMetric`] to `1.0`, if the [`Signature`]s of both strings prove
+/// that their normalized distance exceeds `max_normalized`.
+///
+/// For other types than strings, only the lengths are compared.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Levenshtein, Signed};
+/// let dist = Signed::new(Levenshtein::default(), 0.2);
+/// assert_eq!(dist.str_distance("kitten", "xyz"), 1.);
+/// assert_eq!(dist.str_distance("kitten", "kittens"), Levenshtein::default().str_normalized("kitten", "kittens"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Signed<D: DistanceMetric> {
+    /// The base distance to evaluate.
+    inner: D,
+    /// The normalized distance above which pairs may be rejected.
+    max_normalized: f64,
+}
+
+impl<D: DistanceMetric> Signed<D> {
+    /// Create a new [`Signed`] distance metric using distance `D` as base.
+    pub fn new(inner: D, max_normalized: f64) -> Self {
+        Self {
+            inner,
+            max_normalized,
+        }
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Signed<D> {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a = a.into_iter();
+        let b = b.into_iter();
+        let len_a = a.clone().count();
+        let len_b = b.clone().count();
+        let max_len = cmp::max(len_a, len_b);
+        let len_diff = len_a.abs_diff(len_b);
+        if max_len > 0 && len_diff as f64 / max_len as f64 > self.max_normalized {
+            return 1.;
+        }
+        self.inner.normalized(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let (a, b) = (a.as_ref(), b.as_ref());
+        if !Signature::compute(a).could_match(&Signature::compute(b), self.max_normalized) {
+            return 1.;
+        }
+        self.inner.str_normalized(a, b)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DamerauLevenshtein, Levenshtein};
+
+    const WORDS: [&str; 12] = [
+        "",
+        "a",
+        "kitten",
+        "sitting",
+        "mitten",
+        "kitchen",
+        "smitten",
+        "nettik",
+        "abc",
+        "cab",
+        "a☃c",
+        "abcdefghijklmnopqrstuvwxyz0123456789",
+    ];
+
+    #[test]
+    fn signature_never_rejects_matches() {
+        for a in WORDS.iter() {
+            for b in WORDS.iter() {
+                let sig_a = Signature::compute(a);
+                let sig_b = Signature::compute(b);
+                for threshold in [0., 0.1, 0.2, 0.3, 0.5, 0.8, 1.].iter() {
+                    let lev = Levenshtein::default().str_normalized(a, b);
+                    let damerau = DamerauLevenshtein::default().str_normalized(a, b);
+                    if lev <= *threshold || damerau <= *threshold {
+                        assert!(sig_a.could_match(&sig_b, *threshold), "{} {}", a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn signature_rejects() {
+        let kitten = Signature::compute("kitten");
+        assert_eq!(kitten.len(), 6);
+        assert!(!kitten.could_match(&Signature::compute("xyz"), 0.5));
+        assert!(!kitten.could_match(&Signature::compute("nettik"), 0.));
+        assert!(kitten.could_match(&Signature::compute("mitten"), 0.2));
+        assert!(Signature::compute("").could_match(&Signature::compute(""), 0.));
+    }
+
+    #[test]
+    fn signed_levenshtein() {
+        let dist = Signed::new(Levenshtein::default(), 0.3);
+        assert_eq!(dist.str_distance("kitten", "xyz"), 1.);
+        assert_eq!(
+            dist.str_distance("kitten", "sitting"),
+            Levenshtein::default().str_normalized("kitten", "sitting")
+        );
+        assert_eq!(dist.distance(&[1, 2, 3], &[1, 2, 3, 4, 5, 6]), 1.);
+        assert_eq!(dist.distance(&[1, 2, 3], &[1, 2, 3]), 0.);
+    }
+
+    #[test]
+    fn simhash_near_duplicates() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        let edits = [
+            "the quick brown fox jumps over the lazy dog!",
+            "the quick brown fox jumped over the lazy dog",
+            "the quick brown fax jumps over the lazy dog",
+            "The quick brown fox jumps over the lazy dog",
+        ];
+        let unrelated = [
+            "lorem ipsum dolor sit amet, consectetur adipiscing",
+            "pack my box with five dozen liquor jugs",
+            "sphinx of black quartz, judge my vow",
+        ];
+        let hash = simhash(text, 3);
+        assert_eq!(hash, simhash(text, 3));
+        assert_eq!(simhash_distance(hash, hash), 0);
+        let max_edit = edits
+            .iter()
+            .map(|edit| simhash_distance(hash, simhash(edit, 3)))
+            .max()
+            .unwrap();
+        let min_unrelated = unrelated
+            .iter()
+            .map(|other| simhash_distance(hash, simhash(other, 3)))
+            .min()
+            .unwrap();
+        // about half of the bits differ for unrelated strings
+        assert!(max_edit <= 12, "{}", max_edit);
+        assert!(min_unrelated >= 20, "{}", min_unrelated);
+
+        assert_eq!(simhash("", 3), 0);
+        assert_eq!(simhash("ab", 3), 0);
+        assert_ne!(simhash("abc", 3), 0);
+        assert_eq!(simhash_distance(0, u64::MAX), 64);
+    }
+}
@@ -1,23 +1,277 @@
-use crate::DistanceMetric;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::qgram::{count_distinct_intersect, overlap_distance, QGramIter};
+use crate::utils::{ln, sqrt, Map};
+use crate::{Cosine, DistanceMetric, DistanceValue, SetDice};
 
 /// A TokenSet distance modifies the distance of its `inner` [`DistanceMetric`]
 /// to adjust for differences in word orders and word numbers by comparing the
 /// intersection of two str with each str.
 ///
 /// http://chairnerd.seatgeek.com/fuzzywuzzy-fuzzy-string-matching-in-python/
+///
+/// The distances of the intersection to each str and of both str are combined
+/// by the [`Combiner`], which is [`Combiner::Min`] by default.
+///
+/// The str are split into words by the [`Tokenizer`], which is
+/// [`Tokenizer::Whitespace`] by default.
 pub struct TokenSet<D: DistanceMetric> {
     /// The base distance to modify.
     inner: D,
+    /// How the str are split into words.
+    tokenizer: Tokenizer,
+    /// How the distances are combined.
+    combiner: Combiner,
 }
 
 impl<D: DistanceMetric> TokenSet<D> {
     /// Create a new [`TokenSet`] distance metric using distance `D` as base.
     pub fn new(inner: D) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            tokenizer: Tokenizer::Whitespace,
+            combiner: Combiner::Min,
+        }
+    }
+
+    /// Splits the str into words with the `tokenizer` instead of
+    /// [`Tokenizer::Whitespace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, RatcliffObershelp, TokenSet};
+    /// # use str_distance::token::Tokenizer;
+    /// let s1 = "Real-Madrid,vs,FC-Barcelona";
+    /// let s2 = "Barcelona vs Real Madrid";
+    /// assert_eq!(TokenSet::new(RatcliffObershelp).with_tokenizer(Tokenizer::Alphanumeric).str_distance(s1, s2), 0.);
+    /// ```
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// The [`Combiner`] of the distances.
+    pub fn combiner(&self) -> &Combiner {
+        &self.combiner
+    }
+
+    /// The [`Tokenizer`] that splits the str into words.
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+}
+
+impl<D: DistanceMetric<Dist = f64>> TokenSet<D> {
+    /// Combines the distances with the `combiner` instead of
+    /// [`Combiner::Min`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weights of a [`Combiner::Weighted`] are invalid, see
+    /// [`TokenSet::try_with_combiner`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{DistanceMetric, RatcliffObershelp, TokenSet};
+    /// # use str_distance::token::{Combiner, Tokenizer};
+    /// let s1 = "Real Madrid vs FC Barcelona";
+    /// let s2 = "Barcelona vs Real Madrid";
+    /// assert_eq!(TokenSet::new(RatcliffObershelp).with_combiner(Combiner::Min).str_distance(s1, s2), 0.);
+    /// assert!(TokenSet::new(RatcliffObershelp).with_combiner(Combiner::Mean).str_distance(s1, s2) > 0.);
+    ///
+    /// let token_set = TokenSet::new(RatcliffObershelp)
+    ///     .with_tokenizer(Tokenizer::Alphanumeric)
+    ///     .with_combiner(Combiner::Mean);
+    /// assert_eq!(token_set.str_distance("a-b", "b a"), 0.);
+    /// ```
+    pub fn with_combiner(self, combiner: Combiner) -> Self {
+        self.try_with_combiner(combiner).unwrap()
+    }
+
+    /// Combines the distances with the `combiner` like
+    /// [`TokenSet::with_combiner`], or returns an error if the weights of a
+    /// [`Combiner::Weighted`] are negative, NaN or all 0.
+    pub fn try_with_combiner(mut self, combiner: Combiner) -> Result<Self, CombinerError> {
+        combiner.validate()?;
+        self.combiner = combiner;
+        Ok(self)
+    }
+}
+
+/// Determines how [`TokenSet`] and [`TokenSort`] split a str into words.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Tokenizer {
+    /// Split on whitespace, see [`str::split_whitespace`].
+    #[default]
+    Whitespace,
+    /// Split on every char that is not alphanumeric, e.g. punctuation and
+    /// whitespace, see [`char::is_alphanumeric`].
+    Alphanumeric,
+    /// Split with a custom function.
+    Custom(fn(&str) -> Vec<&str>),
+}
+
+impl Tokenizer {
+    /// Splits `s` into its words, empty words are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::token::Tokenizer;
+    /// assert_eq!(Tokenizer::Alphanumeric.tokenize("Real-Madrid, vs"), vec!["Real", "Madrid", "vs"]);
+    /// let by_comma = Tokenizer::Custom(|s| s.split(',').collect());
+    /// assert_eq!(by_comma.tokenize("a b,,c"), vec!["a b", "c"]);
+    /// ```
+    pub fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        match self {
+            Tokenizer::Whitespace => s.split_whitespace().collect(),
+            Tokenizer::Alphanumeric => s
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            Tokenizer::Custom(tokenize) => tokenize(s)
+                .into_iter()
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Determines how [`TokenSet`] combines the distances of the intersection to
+/// the first str, the intersection to the second str and of both str.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Combiner {
+    /// The minimum of the three distances.
+    #[default]
+    Min,
+    /// The arithmetic mean of the three distances.
+    Mean,
+    /// The weighted mean of the distances of the intersection to the first
+    /// str, the intersection to the second str and of both str.
+    Weighted(f64, f64, f64),
+}
+
+impl Combiner {
+    /// Combines the distances of the intersection to the first str, the
+    /// intersection to the second str and of both str.
+    ///
+    /// Returns `f64::NAN` if all weights of a [`Combiner::Weighted`] are 0,
+    /// which [`TokenSet::with_combiner`] rejects.
+    pub fn combine(&self, dists: [f64; 3]) -> f64 {
+        match *self {
+            Combiner::Min => min_dist(dists),
+            Combiner::Mean => dists.iter().sum::<f64>() / 3.,
+            Combiner::Weighted(inter_a, inter_b, a_b) => {
+                (inter_a * dists[0] + inter_b * dists[1] + a_b * dists[2])
+                    / (inter_a + inter_b + a_b)
+            }
+        }
+    }
+
+    /// Checks that the weights of a [`Combiner::Weighted`] are neither
+    /// negative nor NaN, and not all 0.
+    fn validate(&self) -> Result<(), CombinerError> {
+        if let Combiner::Weighted(inter_a, inter_b, a_b) = *self {
+            let weights = [inter_a, inter_b, a_b];
+            if weights.iter().any(|weight| weight.is_nan()) {
+                return Err(CombinerError::NanWeight);
+            }
+            if weights.iter().any(|weight| *weight < 0.) {
+                return Err(CombinerError::NegativeWeight);
+            }
+            if weights.iter().all(|weight| *weight == 0.) {
+                return Err(CombinerError::ZeroWeights);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error of [`TokenSet::try_with_combiner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinerError {
+    /// A weight of [`Combiner::Weighted`] is negative.
+    NegativeWeight,
+    /// A weight of [`Combiner::Weighted`] is NaN.
+    NanWeight,
+    /// All weights of [`Combiner::Weighted`] are 0.
+    ZeroWeights,
+}
+
+impl fmt::Display for CombinerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CombinerError::NegativeWeight => f.write_str("the weights must not be negative"),
+            CombinerError::NanWeight => f.write_str("the weights must not be NaN"),
+            CombinerError::ZeroWeights => f.write_str("the weights must not all be 0"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CombinerError {}
+
+/// A distance that [`TokenSet`] can combine with its [`Combiner`].
+///
+/// Only `f64` distances can be averaged, so [`TokenSet::with_combiner`]
+/// requires a metric with `f64` distances and all other distances are
+/// combined with [`Combiner::Min`].
+pub trait Combine: PartialOrd + Sized {
+    /// Combines the distances of the intersection to the first str, the
+    /// intersection to the second str and of both str.
+    fn combine(combiner: &Combiner, dists: [Self; 3]) -> Self;
+}
+
+impl Combine for f64 {
+    fn combine(combiner: &Combiner, dists: [Self; 3]) -> Self {
+        combiner.combine(dists)
     }
 }
 
-impl<D: DistanceMetric> DistanceMetric for TokenSet<D> {
+impl Combine for usize {
+    fn combine(_: &Combiner, dists: [Self; 3]) -> Self {
+        min_dist(dists)
+    }
+}
+
+impl Combine for DistanceValue {
+    fn combine(_: &Combiner, dists: [Self; 3]) -> Self {
+        min_dist(dists)
+    }
+}
+
+/// The minimum of the three distances.
+///
+/// Distances that can't be compared with themselves, i.e. `NaN`, are treated
+/// as the maximal distance, so they are only returned if all distances are
+/// `NaN`. Of equal distances the first one is returned.
+fn min_dist<V: PartialOrd>(dists: [V; 3]) -> V {
+    let is_nan = |dist: &V| dist.partial_cmp(dist).is_none();
+    IntoIterator::into_iter(dists)
+        .reduce(|min, dist| {
+            let less = match dist.partial_cmp(&min) {
+                Some(ordering) => ordering == Ordering::Less,
+                None => is_nan(&min) && !is_nan(&dist),
+            };
+            if less {
+                dist
+            } else {
+                min
+            }
+        })
+        .expect("three distances")
+}
+
+impl<D> DistanceMetric for TokenSet<D>
+where
+    D: DistanceMetric,
+    <D as DistanceMetric>::Dist: Combine,
+{
     type Dist = <D as DistanceMetric>::Dist;
 
     fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
@@ -32,27 +286,19 @@ impl<D: DistanceMetric> DistanceMetric for TokenSet<D> {
         let a = a.into_iter();
         let b = b.into_iter();
 
-        let intersect = b.clone().filter(|x| a.clone().any(|y| y == *x));
+        // the intersection keeps the order of the input it is compared with
+        let intersect_a = a.clone().filter(|x| b.clone().any(|y| *x == y));
+        let intersect_b = b.clone().filter(|y| a.clone().any(|x| x == *y));
 
-        if intersect.clone().count() == 0 {
+        if intersect_a.clone().count() == 0 {
             return self.inner.distance(a, b);
         }
 
-        let dist_inter_a = self.inner.distance(a.clone(), intersect.clone());
-        let dist_inter_b = self.inner.distance(intersect, b.clone());
+        let dist_inter_a = self.inner.distance(intersect_a, a.clone());
+        let dist_inter_b = self.inner.distance(intersect_b, b.clone());
         let dist_a_b = self.inner.distance(a, b);
 
-        if dist_inter_a < dist_inter_b {
-            if dist_inter_a < dist_a_b {
-                dist_inter_a
-            } else {
-                dist_a_b
-            }
-        } else if dist_inter_b < dist_a_b {
-            dist_inter_b
-        } else {
-            dist_a_b
-        }
+        Combine::combine(&self.combiner, [dist_inter_a, dist_inter_b, dist_a_b])
     }
 
     fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
@@ -61,12 +307,12 @@ impl<D: DistanceMetric> DistanceMetric for TokenSet<D> {
         T: AsRef<str>,
     {
         let a = a.as_ref();
-        let mut words_a: Vec<_> = a.split_whitespace().collect();
+        let mut words_a = self.tokenizer.tokenize(a);
         words_a.sort();
         words_a.dedup_by(|a, b| a == b);
 
         let b = b.as_ref();
-        let mut words_b: Vec<_> = b.split_whitespace().collect();
+        let mut words_b = self.tokenizer.tokenize(b);
         words_b.sort();
         words_b.dedup_by(|a, b| a == b);
 
@@ -88,17 +334,7 @@ impl<D: DistanceMetric> DistanceMetric for TokenSet<D> {
         let dist_inter_b = self.inner.str_distance(intersect, &b);
         let dist_a_b = self.inner.str_distance(a, &b);
 
-        if dist_inter_a < dist_inter_b {
-            if dist_inter_a < dist_a_b {
-                dist_inter_a
-            } else {
-                dist_a_b
-            }
-        } else if dist_inter_b < dist_a_b {
-            dist_inter_b
-        } else {
-            dist_a_b
-        }
+        Combine::combine(&self.combiner, [dist_inter_a, dist_inter_b, dist_a_b])
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -117,10 +353,49 @@ impl<D: DistanceMetric> DistanceMetric for TokenSet<D> {
 /// `TokenSort` modifies the inner string distance `dist` to adjust for
 /// differences in word orders by reording words alphabetically.
 ///
+/// Unlike [`TokenSet`], duplicate words are kept.
+///
+/// The str are split into words by the [`Tokenizer`], which is
+/// [`Tokenizer::Whitespace`] by default.
+///
 /// For other types than strings this is just a delegate to the inner metric.
+///
+/// http://chairnerd.seatgeek.com/fuzzywuzzy-fuzzy-string-matching-in-python/
 pub struct TokenSort<D: DistanceMetric> {
     /// The base distance to modify.
     inner: D,
+    /// How the str are split into words.
+    tokenizer: Tokenizer,
+}
+
+impl<D: DistanceMetric> TokenSort<D> {
+    /// Create a new [`TokenSort`] distance metric using distance `D` as base.
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            tokenizer: Tokenizer::Whitespace,
+        }
+    }
+
+    /// Splits the str into words with the `tokenizer` instead of
+    /// [`Tokenizer::Whitespace`].
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// The [`Tokenizer`] that splits the str into words.
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+}
+
+/// Splits the str into words and joins the alphabetically sorted words with a
+/// single space.
+fn sort_tokens(tokenizer: &Tokenizer, s: &str) -> String {
+    let mut words = tokenizer.tokenize(s);
+    words.sort_unstable();
+    words.join(" ")
 }
 
 impl<D> DistanceMetric for TokenSort<D>
@@ -146,11 +421,10 @@ where
         S: AsRef<str>,
         T: AsRef<str>,
     {
-        let mut a: Vec<_> = a.as_ref().split_whitespace().collect();
-        a.sort();
-        let mut b: Vec<_> = b.as_ref().split_whitespace().collect();
-        b.sort();
-        self.distance(a.join(" ").chars(), b.join(" ").chars())
+        self.inner.str_distance(
+            sort_tokens(&self.tokenizer, a.as_ref()),
+            sort_tokens(&self.tokenizer, b.as_ref()),
+        )
     }
 
     fn normalized<S, T>(&self, a: S, b: T) -> f64
@@ -164,27 +438,1259 @@ where
     {
         self.inner.normalized(a, b)
     }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.inner.str_normalized(
+            sort_tokens(&self.tokenizer, a.as_ref()),
+            sort_tokens(&self.tokenizer, b.as_ref()),
+        )
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::RatcliffObershelp;
+/// The overlap coefficient of the sets of words of both inputs.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - |W(s1) ∩ W(s2)| / min(|W(s1)|, |W(s2)|)
+/// ```
+///
+/// where `W(s)` denotes the set of whitespace separated words of the str s.
+/// This is the [`crate::Overlap`] distance with whole words instead of q-grams
+/// as set elements, so that the distance is `0.` if all words of one str are
+/// also words of the other str.
+///
+/// For other types than strings every element is treated as a word.
+///
+/// If both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, TokenOverlap};
+/// assert_eq!(TokenOverlap.str_distance("Real Madrid", "Real Madrid vs FC Barcelona"), 0.);
+/// assert_eq!(TokenOverlap.str_distance("Real Madrid", "Real Betis"), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenOverlap;
 
-    use super::*;
+impl DistanceMetric for TokenOverlap {
+    type Dist = f64;
 
-    #[test]
-    fn token_set_ratcliff() {
-        let s1 = "Real Madrid vs FC Barcelona";
-        let s2 = "Barcelona vs Real Madrid";
-        assert_eq!(TokenSet::new(RatcliffObershelp).str_distance(s1, s2), 0.0);
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
 
-        let s2 = "Barcelona vs Rel Madrid";
-        assert_eq!(
-            format!(
-                "{:.6}",
-                TokenSet::new(RatcliffObershelp).str_distance(s1, s2)
-            ),
-            "0.080000"
+        // edge case where an input is empty
+        if a.is_empty() || b.is_empty() {
+            return if a.len() == b.len() { 0. } else { 1. };
+        }
+
+        // every word is a fragment of length 1
+        overlap_distance(count_distinct_intersect(
+            QGramIter::new(&a, 1),
+            QGramIter::new(&b, 1),
+        ))
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.distance(a.as_ref().split_whitespace(), b.as_ref().split_whitespace())
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The Sørensen-Dice distance of the sets of words of both inputs, i.e. the
+/// Dice coefficient of token sets that is common in record linkage.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - 2 * |W(s1) ∩ W(s2)| / (|W(s1)| + |W(s2)|)
+/// ```
+///
+/// where `W(s)` denotes the set of whitespace separated words of the str s.
+/// This is the [`SetDice`] distance of the words, i.e. the
+/// [`crate::SorensenDice`] distance with whole words instead of q-grams as set
+/// elements. Unlike [`TokenSet`] no other metric is applied to the words, they
+/// either match or don't.
+///
+/// For other types than strings every element is treated as a word, like
+/// [`SetDice`].
+///
+/// If both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, TokenDice};
+/// assert_eq!(TokenDice.str_distance("the quick brown fox", "the lazy brown dog"), 0.5);
+/// assert_eq!(TokenDice.str_distance("Real Madrid", "Madrid Real"), 0.);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenDice;
+
+impl DistanceMetric for TokenDice {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        SetDice.distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        SetDice.distance_hashed(a.as_ref().split_whitespace(), b.as_ref().split_whitespace())
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The cosine distance of the term frequency vectors of the words of two str,
+/// i.e. the bag-of-words cosine distance.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1 - tf(s1).tf(s2) / ||tf(s1)|| * ||tf(s2)||
+/// ```
+///
+/// where `tf(s)` denotes the vec that contains the number of times each
+/// whitespace separated word appears in the str s. This is the
+/// [`crate::Cosine`] distance with whole words instead of q-grams, and
+/// follows its convention for empty inputs: If both inputs are empty a value
+/// of `0.` is returned. If one input is empty and the other is not, a value
+/// of `1.` is returned.
+///
+/// For other types than strings every element is treated as a word.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, TokenCosine};
+/// assert!(TokenCosine.str_distance("new york", "york new").abs() < 1e-12);
+/// assert!((TokenCosine.str_distance("new york", "new jersey") - 0.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenCosine;
+
+impl DistanceMetric for TokenCosine {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        // every word is a fragment of length 1
+        Cosine::new(1).distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        Cosine::new(1).distance_hashed(a.as_ref().split_whitespace(), b.as_ref().split_whitespace())
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// A [`TokenCosine`] distance, that weights the term frequency of each word
+/// by its inverse document frequency (IDF) in a background corpus, so that
+/// words that appear in many documents count less.
+///
+/// The document frequencies are computed once by [`TfIdf::from_corpus`], the
+/// resulting metric then compares any two str. The IDF of a word is
+///
+/// ```text
+///     ln((1 + N) / (1 + df(w))) + 1
+/// ```
+///
+/// where `N` denotes the number of documents of the corpus and `df(w)` the
+/// number of documents the word `w` appears in. Words that are not part of the
+/// corpus get the highest weight.
+///
+/// The str are split into words on whitespace, words are case sensitive. If
+/// both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// For other types than strings the IDF is unknown, so this is the same as
+/// [`TokenCosine`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, TfIdf, TokenCosine};
+/// let tfidf = TfIdf::from_corpus(&["the cat", "the dog", "the bird"]);
+/// // sharing the rare "cat" counts more than sharing the common "the"
+/// assert!(tfidf.str_distance("cat food", "cat toy") < tfidf.str_distance("the food", "the toy"));
+/// assert_eq!(TokenCosine.str_distance("cat food", "cat toy"), TokenCosine.str_distance("the food", "the toy"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TfIdf {
+    /// The number of documents of the corpus.
+    num_docs: usize,
+    /// The number of documents each word appears in.
+    doc_freqs: Map<String, usize>,
+}
+
+impl TfIdf {
+    /// Computes the document frequencies of all words of the `docs`.
+    pub fn from_corpus<I, S>(docs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut num_docs = 0;
+        let mut doc_freqs: Map<String, usize> = Map::new();
+        for doc in docs {
+            num_docs += 1;
+            let mut words: Vec<_> = doc.as_ref().split_whitespace().collect();
+            words.sort_unstable();
+            words.dedup();
+            for word in words {
+                match doc_freqs.get_mut(word) {
+                    Some(freq) => *freq += 1,
+                    None => {
+                        doc_freqs.insert(word.to_string(), 1);
+                    }
+                }
+            }
+        }
+        Self {
+            num_docs,
+            doc_freqs,
+        }
+    }
+
+    /// The number of documents of the corpus.
+    pub fn num_docs(&self) -> usize {
+        self.num_docs
+    }
+
+    /// The number of documents of the corpus the `word` appears in.
+    pub fn doc_freq(&self, word: &str) -> usize {
+        self.doc_freqs.get(word).copied().unwrap_or_default()
+    }
+
+    /// The inverse document frequency the term frequency of `word` is
+    /// weighted with.
+    pub fn idf(&self, word: &str) -> f64 {
+        ln((1 + self.num_docs) as f64 / (1 + self.doc_freq(word)) as f64) + 1.
+    }
+}
+
+impl DistanceMetric for TfIdf {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        TokenCosine.distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let mut counts: Map<&str, (usize, usize)> = Map::new();
+        for word in a.as_ref().split_whitespace() {
+            counts.entry(word).or_default().0 += 1;
+        }
+        for word in b.as_ref().split_whitespace() {
+            counts.entry(word).or_default().1 += 1;
+        }
+
+        let (mut norm_a, mut norm_b, mut norm_prod) = (0., 0., 0.);
+        for (word, (n1, n2)) in counts {
+            let idf = self.idf(word);
+            let (w1, w2) = (n1 as f64 * idf, n2 as f64 * idf);
+            norm_a += w1 * w1;
+            norm_b += w2 * w2;
+            norm_prod += w1 * w2;
+        }
+
+        // edge case where an input is empty
+        if norm_a == 0. || norm_b == 0. {
+            return if norm_a == norm_b { 0. } else { 1. };
+        }
+        1. - norm_prod / (sqrt(norm_a) * sqrt(norm_b))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// The Soft TF-IDF distance is a [`TfIdf`] cosine distance, where words that
+/// are merely similar according to the inner distance `dist` contribute as
+/// well, e.g. a misspelled name.
+///
+/// The similarity corresponds to
+///
+/// ```text
+///     Σ_{w ∈ CLOSE(θ, s1, s2)} V(w, s1) * V(w', s2) * sim(w, w')
+/// ```
+///
+/// where `V(w, s)` denotes the TF-IDF weight of the word `w` in the str `s`,
+/// normalized to a unit vector, `sim(w, w')` is `1 - dist.str_normalized(w,
+/// w')` and `w'` is the word of `s2` that is most similar to `w`.
+/// `CLOSE(θ, s1, s2)` contains the words of `s1` whose most similar word has
+/// a similarity of at least the threshold `θ`. The distance is `1 -
+/// similarity`, but never below `0.`.
+///
+/// This was proposed by Cohen, Ravikumar and Fienberg (2003) for matching
+/// names, typically with a [`crate::JaroWinkler`] inner distance and a
+/// threshold of `0.9`. Like the original definition the distance is not
+/// symmetric, since the words of `s1` are matched with the words of `s2`.
+///
+/// The str are split into words on whitespace, words are case sensitive. If
+/// both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// For other types than strings the IDF is unknown, so this is the same as
+/// [`TokenCosine`].
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, JaroWinkler, SoftTfIdf, TfIdf};
+/// let corpus = ["John Smith", "Jane Doe", "Mary Smith"];
+/// let soft = SoftTfIdf::from_corpus(&corpus, JaroWinkler::default(), 0.9);
+/// let tfidf = TfIdf::from_corpus(&corpus);
+/// // "Jon" is similar enough to "John" to contribute
+/// assert!(soft.str_distance("John Smith", "Jon Smith") < tfidf.str_distance("John Smith", "Jon Smith"));
+/// ```
+pub struct SoftTfIdf<D: DistanceMetric> {
+    /// The document frequencies to weight the words with.
+    tfidf: TfIdf,
+    /// The base distance to compare the words with.
+    inner: D,
+    /// The minimum similarity of two words to contribute.
+    threshold: f64,
+}
+
+impl<D: DistanceMetric> SoftTfIdf<D> {
+    /// Creates a new [`SoftTfIdf`] distance that weights the words with the
+    /// document frequencies of `tfidf` and compares them with distance `D`.
+    /// Words contribute if their similarity is at least the `threshold`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `threshold` is not between `0.` and `1.`.
+    pub fn new(tfidf: TfIdf, inner: D, threshold: f64) -> Self {
+        assert!((0. ..=1.).contains(&threshold));
+        Self {
+            tfidf,
+            inner,
+            threshold,
+        }
+    }
+
+    /// Computes the document frequencies of all words of the `docs`, see
+    /// [`TfIdf::from_corpus`], and creates a new [`SoftTfIdf`] distance like
+    /// [`SoftTfIdf::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `threshold` is not between `0.` and `1.`.
+    pub fn from_corpus<I, S>(docs: I, inner: D, threshold: f64) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::new(TfIdf::from_corpus(docs), inner, threshold)
+    }
+
+    /// The document frequencies the words are weighted with.
+    pub fn tfidf(&self) -> &TfIdf {
+        &self.tfidf
+    }
+
+    /// The minimum similarity of two words to contribute.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// The distinct words of `s` ordered by the words, together with their
+    /// TF-IDF weights normalized to a unit vector.
+    fn unit_weights<'a>(&self, s: &'a str) -> Vec<(&'a str, f64)> {
+        let mut words: Vec<_> = s.split_whitespace().collect();
+        words.sort_unstable();
+        let mut weights: Vec<(&str, f64)> = Vec::with_capacity(words.len());
+        for word in words {
+            match weights.last_mut() {
+                Some((last, weight)) if *last == word => *weight += 1.,
+                _ => weights.push((word, 1.)),
+            }
+        }
+        let mut norm = 0.;
+        for (word, weight) in weights.iter_mut() {
+            *weight *= self.tfidf.idf(word);
+            norm += *weight * *weight;
+        }
+        let norm = sqrt(norm);
+        for (_, weight) in weights.iter_mut() {
+            *weight /= norm;
+        }
+        weights
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for SoftTfIdf<D> {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        TokenCosine.distance(a, b)
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let weights_a = self.unit_weights(a.as_ref());
+        let weights_b = self.unit_weights(b.as_ref());
+
+        // edge case where an input is empty
+        if weights_a.is_empty() || weights_b.is_empty() {
+            return if weights_a.len() == weights_b.len() {
+                0.
+            } else {
+                1.
+            };
+        }
+
+        let mut similarity = 0.;
+        for (word_a, weight_a) in weights_a.iter() {
+            // the first most similar word of b
+            let mut best: Option<(f64, f64)> = None;
+            for (word_b, weight_b) in weights_b.iter() {
+                let sim = 1. - self.inner.str_normalized(word_a, word_b);
+                if matches!(best, Some((best_sim, _)) if best_sim >= sim) {
+                    continue;
+                }
+                best = Some((sim, *weight_b));
+            }
+            if let Some((sim, weight_b)) = best {
+                if sim >= self.threshold {
+                    similarity += weight_a * weight_b * sim;
+                }
+            }
+        }
+        (1. - similarity).max(0.)
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+/// `Partial` modifies the inner distance `dist` to return the minimum
+/// normalized distance between the shorter input and any substring of the
+/// longer input that has the same length as the shorter one.
+///
+/// http://chairnerd.seatgeek.com/fuzzywuzzy-fuzzy-string-matching-in-python/
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Levenshtein, Partial};
+/// assert_eq!(Partial::new(Levenshtein::default()).str_distance("New York", "New York Mets"), 0.);
+/// ```
+pub struct Partial<D: DistanceMetric> {
+    /// The base distance to modify.
+    inner: D,
+}
+
+impl<D: DistanceMetric> Partial<D> {
+    /// Create a new [`Partial`] distance metric using distance `D` as base.
+    pub fn new(inner: D) -> Self {
+        Self { inner }
+    }
+
+    /// Evaluates the distance like [`DistanceMetric::distance`], together
+    /// with the index of the first element of the best matching window in the
+    /// longer input.
+    ///
+    /// If several windows match equally well the first one is reported. The
+    /// offset is `0` if both inputs have the same length or if an input is
+    /// empty. For str the offset counts chars, not bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use str_distance::{Levenshtein, Partial};
+    /// let partial = Partial::new(Levenshtein::default());
+    /// assert_eq!(partial.distance_with_offset("York".chars(), "New York Mets".chars()), (0., 4));
+    /// ```
+    pub fn distance_with_offset<S, T>(&self, a: S, b: T) -> (f64, usize)
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+
+        if a.len() == b.len() {
+            return (self.inner.normalized(a.iter(), b.iter()), 0);
+        }
+        if a.is_empty() || b.is_empty() {
+            return (1., 0);
+        }
+
+        let mut best = (1., 0);
+        if a.len() < b.len() {
+            for (offset, qgram) in QGramIter::new(&b, a.len()).enumerate() {
+                let dist = self.inner.normalized(a.iter(), qgram.iter());
+                if dist < best.0 {
+                    best = (dist, offset);
+                }
+            }
+        } else {
+            for (offset, qgram) in QGramIter::new(&a, b.len()).enumerate() {
+                let dist = self.inner.normalized(qgram.iter(), b.iter());
+                if dist < best.0 {
+                    best = (dist, offset);
+                }
+            }
+        }
+        best
+    }
+}
+
+impl<D: DistanceMetric> DistanceMetric for Partial<D> {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance_with_offset(a, b).0
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+}
+
+/// `MongeElkan` matches every word of the first str with its most similar word
+/// of the second str according to the inner distance `dist`, and averages the
+/// normalized distances of those best matches.
+///
+/// The distance corresponds to
+///
+/// ```text
+///     1/|W(s1)| * Σ_{w1 ∈ W(s1)} min_{w2 ∈ W(s2)} dist(w1, w2)
+/// ```
+///
+/// where `W(s)` denotes the whitespace separated words of the str s. This
+/// tolerates differences in word orders as well as misspelled words, which
+/// makes it suitable to compare multi word names.
+///
+/// The distance is not symmetric by default, the [`MongeElkan::symmetric`]
+/// variant averages the distances of both directions.
+///
+/// For other types than strings every element is treated as a word, and the
+/// distance of two words is `0.` if they are equal and `1.` otherwise.
+///
+/// If both inputs are empty a value of `0.` is returned. If one input is empty
+/// and the other is not, a value of `1.` is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use str_distance::{DistanceMetric, Levenshtein, MongeElkan};
+/// let monge_elkan = MongeElkan::new(Levenshtein::default());
+/// assert_eq!(monge_elkan.str_distance("Paul Adam", "Adam Paul"), 0.);
+/// assert_eq!(monge_elkan.str_distance("Paul", "Paul Adam"), 0.);
+/// assert_eq!(MongeElkan::symmetric(Levenshtein::default()).str_distance("Paul", "Paul Adam"), 0.25);
+/// ```
+pub struct MongeElkan<D: DistanceMetric> {
+    /// The base distance to compare the words with.
+    inner: D,
+    /// Whether the distances of both directions are averaged.
+    symmetric: bool,
+}
+
+impl<D: DistanceMetric> MongeElkan<D> {
+    /// Create a new [`MongeElkan`] distance metric using distance `D` to
+    /// compare the words.
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            symmetric: false,
+        }
+    }
+
+    /// Create a new symmetric [`MongeElkan`] distance metric using distance
+    /// `D` to compare the words, that averages the distances from the first to
+    /// the second str and from the second to the first str.
+    pub fn symmetric(inner: D) -> Self {
+        Self {
+            inner,
+            symmetric: true,
+        }
+    }
+
+    /// Whether the distances of both directions are averaged.
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Averages the minimum distance of every word in `a` to the words in `b`,
+    /// and of every word in `b` to the words in `a` if symmetric.
+    fn monge_elkan<A, B>(&self, a: &[A], b: &[B], dist: impl Fn(&A, &B) -> f64 + Copy) -> f64 {
+        // edge case where an input is empty
+        if a.is_empty() || b.is_empty() {
+            return if a.len() == b.len() { 0. } else { 1. };
+        }
+        let dist_a_b = mean_min_dist(a, b, dist);
+        if self.symmetric {
+            (dist_a_b + mean_min_dist(b, a, |y, x| dist(x, y))) / 2.
+        } else {
+            dist_a_b
+        }
+    }
+}
+
+/// The mean of the minimum distances of every element of `a` to the elements
+/// of `b`, where both are not empty.
+fn mean_min_dist<A, B>(a: &[A], b: &[B], dist: impl Fn(&A, &B) -> f64) -> f64 {
+    a.iter()
+        .map(|x| b.iter().map(|y| dist(x, y)).fold(f64::INFINITY, f64::min))
+        .sum::<f64>()
+        / a.len() as f64
+}
+
+impl<D: DistanceMetric> DistanceMetric for MongeElkan<D> {
+    type Dist = f64;
+
+    fn distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        let a: Vec<_> = a.into_iter().collect();
+        let b: Vec<_> = b.into_iter().collect();
+        self.monge_elkan(&a, &b, |x, y| if x == y { 0. } else { 1. })
+    }
+
+    fn str_distance<S, T>(&self, a: S, b: T) -> Self::Dist
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let a: Vec<_> = a.as_ref().split_whitespace().collect();
+        let b: Vec<_> = b.as_ref().split_whitespace().collect();
+        self.monge_elkan(&a, &b, |x, y| self.inner.str_normalized(x, y))
+    }
+
+    fn normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: IntoIterator,
+        T: IntoIterator,
+        <S as IntoIterator>::IntoIter: Clone,
+        <T as IntoIterator>::IntoIter: Clone,
+        <S as IntoIterator>::Item: PartialEq + PartialEq<<T as IntoIterator>::Item>,
+        <T as IntoIterator>::Item: PartialEq,
+    {
+        self.distance(a, b)
+    }
+
+    fn str_normalized<S, T>(&self, a: S, b: T) -> f64
+    where
+        S: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.str_distance(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JaroWinkler, Levenshtein, RatcliffObershelp};
+
+    use super::*;
+
+    #[test]
+    fn token_set_ratcliff() {
+        let s1 = "Real Madrid vs FC Barcelona";
+        let s2 = "Barcelona vs Real Madrid";
+        assert_eq!(TokenSet::new(RatcliffObershelp).str_distance(s1, s2), 0.0);
+
+        let s2 = "Barcelona vs Rel Madrid";
+        assert_eq!(
+            format!(
+                "{:.6}",
+                TokenSet::new(RatcliffObershelp).str_distance(s1, s2)
+            ),
+            "0.080000"
+        );
+    }
+
+    #[test]
+    fn token_set_min() {
+        assert_eq!(min_dist([0.3, 0.1, 0.2]), 0.1);
+        assert_eq!(min_dist([0.1, 0.3, 0.2]), 0.1);
+        assert_eq!(min_dist([0.3, 0.2, 0.1]), 0.1);
+        assert_eq!(min_dist([0.2, 0.2, 0.3]), 0.2);
+        // `NaN` is the maximal distance
+        assert_eq!(min_dist([f64::NAN, 0.5, 0.7]), 0.5);
+        assert_eq!(min_dist([0.3, f64::NAN, 0.1]), 0.1);
+        assert_eq!(min_dist([0.7, 0.5, f64::NAN]), 0.5);
+        assert_eq!(min_dist([f64::NAN, f64::NAN, 0.9]), 0.9);
+        assert!(min_dist([f64::NAN; 3]).is_nan());
+        assert_eq!(min_dist([3, 1, 2]), 1);
+
+        for (s1, s2) in [
+            ("Real Madrid vs FC Barcelona", "Barcelona vs Rel Madrid"),
+            ("fuzzy wuzzy was a bear", "wuzzy fuzzy was a bear"),
+            ("New York", "New York Mets"),
+            ("Paul Adam", "Adam Paul Smith"),
+        ]
+        .iter()
+        {
+            let (mut words_a, mut words_b): (Vec<_>, Vec<_>) = (
+                s1.split_whitespace().collect(),
+                s2.split_whitespace().collect(),
+            );
+            words_a.sort_unstable();
+            words_b.sort_unstable();
+            let intersect: Vec<_> = words_b
+                .iter()
+                .filter(|word| words_a.contains(word))
+                .cloned()
+                .collect();
+            let (a, b, intersect) = (words_a.join(" "), words_b.join(" "), intersect.join(" "));
+            let expected = RatcliffObershelp
+                .str_distance(&intersect, &a)
+                .min(RatcliffObershelp.str_distance(&intersect, &b))
+                .min(RatcliffObershelp.str_distance(&a, &b));
+            assert_eq!(
+                TokenSet::new(RatcliffObershelp).str_distance(s1, s2),
+                expected,
+                "{} {}",
+                s1,
+                s2
+            );
+        }
+        assert_eq!(Combiner::Min.combine([f64::NAN, 0.4, 0.6]), 0.4);
+    }
+
+    #[test]
+    fn token_set_combiner() {
+        let s1 = "Real Madrid vs FC Barcelona";
+        let s2 = "Barcelona vs Rel Madrid";
+        let min = TokenSet::new(RatcliffObershelp).str_distance(s1, s2);
+        assert_eq!(
+            TokenSet::new(RatcliffObershelp)
+                .with_combiner(Combiner::Min)
+                .str_distance(s1, s2),
+            min
+        );
+        let mean = TokenSet::new(RatcliffObershelp)
+            .with_combiner(Combiner::Mean)
+            .str_distance(s1, s2);
+        assert_eq!(format!("{:.6}", mean), "0.116384");
+        assert!(mean > min);
+        // only use the distance of both str
+        assert_eq!(
+            TokenSet::new(RatcliffObershelp)
+                .with_combiner(Combiner::Weighted(0., 0., 2.))
+                .str_distance(s1, s2),
+            RatcliffObershelp
+                .str_distance("Barcelona FC Madrid Real vs", "Barcelona Madrid Rel vs")
+        );
+        assert_eq!(
+            *TokenSet::new(RatcliffObershelp).combiner(),
+            Combiner::default()
+        );
+
+        let try_weighted = |weights: (f64, f64, f64)| -> Result<Combiner, CombinerError> {
+            let combiner = Combiner::Weighted(weights.0, weights.1, weights.2);
+            TokenSet::new(RatcliffObershelp)
+                .try_with_combiner(combiner)
+                .map(|token_set| *token_set.combiner())
+        };
+        assert_eq!(try_weighted((0., 0., 0.)), Err(CombinerError::ZeroWeights));
+        assert_eq!(
+            try_weighted((1., -1., 1.)),
+            Err(CombinerError::NegativeWeight)
+        );
+        assert_eq!(
+            try_weighted((1., f64::NAN, 1.)),
+            Err(CombinerError::NanWeight)
+        );
+        assert_eq!(
+            try_weighted((0., 1., 0.)),
+            Ok(Combiner::Weighted(0., 1., 0.))
+        );
+
+        // the tokenizer and the combiner can be combined in any order
+        let tokenized = TokenSet::new(RatcliffObershelp)
+            .with_combiner(Combiner::Mean)
+            .with_tokenizer(Tokenizer::Alphanumeric);
+        assert_eq!(*tokenized.combiner(), Combiner::Mean);
+        assert!(matches!(tokenized.tokenizer(), Tokenizer::Alphanumeric));
+    }
+
+    #[test]
+    #[should_panic]
+    fn token_set_zero_weights() {
+        TokenSet::new(RatcliffObershelp).with_combiner(Combiner::Weighted(0., 0., 0.));
+    }
+
+    #[test]
+    fn token_sort_ratcliff() {
+        let s1 = "New York Mets";
+        let s2 = "Mets New York";
+        assert_eq!(TokenSort::new(RatcliffObershelp).str_distance(s1, s2), 0.);
+        assert_eq!(TokenSort::new(RatcliffObershelp).str_normalized(s1, s2), 0.);
+        assert_eq!(
+            *TokenSort::new(Levenshtein::default()).str_distance("a b b", "b a"),
+            2
+        );
+        assert_eq!(
+            TokenSort::new(Levenshtein::default()).str_normalized("Mets  New York", s1),
+            0.
+        );
+    }
+
+    #[test]
+    fn partial_substring() {
+        let s1 = "abc";
+        let s2 = "xxxxabcxxxx";
+        assert_eq!(
+            Partial::new(Levenshtein::default()).str_distance(s1, s2),
+            0.
+        );
+        assert_eq!(
+            Partial::new(Levenshtein::default()).str_distance(s2, s1),
+            0.
+        );
+        assert_eq!(
+            format!("{:.6}", Levenshtein::default().str_normalized(s1, s2)),
+            "0.727273"
+        );
+
+        assert_eq!(
+            format!(
+                "{:.6}",
+                Partial::new(Levenshtein::default()).str_distance("abd", s2)
+            ),
+            "0.333333"
+        );
+        assert_eq!(
+            Partial::new(Levenshtein::default()).str_distance("", s2),
+            1.
+        );
+        assert_eq!(
+            Partial::new(Levenshtein::default()).str_distance("", ""),
+            0.
+        );
+    }
+
+    #[test]
+    fn partial_offset() {
+        let partial = Partial::new(Levenshtein::default());
+        let sentence = "the quick brown fox jumps over the lazy dog";
+        let offset = |needle: &str| {
+            let (dist, offset) = partial.distance_with_offset(needle.chars(), sentence.chars());
+            assert_eq!(
+                partial.distance_with_offset(sentence.chars(), needle.chars()),
+                (dist, offset)
+            );
+            assert_eq!(dist, partial.str_distance(needle, sentence));
+            (dist, offset)
+        };
+        assert_eq!(offset("lazy"), (0., 35));
+        assert_eq!(offset("lazi"), (0.25, 35));
+        assert_eq!(offset("quick"), (0., 4));
+        // the first of equal windows
+        assert_eq!(offset("the"), (0., 0));
+        assert_eq!(offset(sentence), (0., 0));
+        assert_eq!(offset(""), (1., 0));
+        // offsets count chars
+        assert_eq!(
+            partial.distance_with_offset("dog".chars(), "☃☃ dog".chars()),
+            (0., 3)
+        );
+        assert_eq!(
+            partial.distance_with_offset(&[3, 4], &[1, 2, 3, 4]),
+            (0., 2)
+        );
+    }
+
+    #[test]
+    fn token_cosine() {
+        assert_eq!(TokenCosine.str_distance("", ""), 0.);
+        assert_eq!(TokenCosine.str_distance("", "a b"), 1.);
+        assert_eq!(TokenCosine.str_distance("  ", "a b"), 1.);
+        assert!(
+            TokenCosine
+                .str_distance("the cat sat", "sat the  cat")
+                .abs()
+                < 1e-12
+        );
+        assert_eq!(TokenCosine.str_distance("the cat sat", "a dog ran"), 1.);
+        // 2 of 3 words shared
+        let dist = TokenCosine.str_distance("the cat sat", "the cat ran");
+        assert!((dist - (1. - 2. / 3.)).abs() < 1e-12);
+        // term frequencies (2, 1) and (1, 1)
+        let dist = TokenCosine.str_distance("the cat the", "the cat");
+        assert!((dist - (1. - 3. / (5f64.sqrt() * 2f64.sqrt()))).abs() < 1e-12);
+        assert_eq!(
+            TokenCosine.distance(&["the", "cat", "the"], &["the", "cat"]),
+            TokenCosine.str_distance("the cat the", "the cat")
+        );
+        // case sensitive
+        assert_eq!(TokenCosine.str_distance("The", "the"), 1.);
+    }
+
+    #[test]
+    fn tfidf() {
+        let corpus = [
+            "the quick brown fox",
+            "the lazy dog",
+            "the cat sat on the mat",
+            "a zebra in the zoo",
+        ];
+        let tfidf = TfIdf::from_corpus(corpus.iter());
+        assert_eq!(tfidf.num_docs(), 4);
+        // words are counted once per document
+        assert_eq!(tfidf.doc_freq("the"), 4);
+        assert_eq!(tfidf.doc_freq("zebra"), 1);
+        assert_eq!(tfidf.doc_freq("unicorn"), 0);
+        assert_eq!(tfidf.idf("the"), 1.);
+        assert!(tfidf.idf("zebra") < tfidf.idf("unicorn"));
+
+        // the rare shared word pulls the documents closer than the common one
+        let rare = tfidf.str_distance("zebra runs fast", "zebra sleeps late");
+        let common = tfidf.str_distance("the runs fast", "the sleeps late");
+        assert!(rare < common, "{} {}", rare, common);
+        assert_eq!(
+            TokenCosine.str_distance("zebra runs fast", "zebra sleeps late"),
+            TokenCosine.str_distance("the runs fast", "the sleeps late")
+        );
+
+        // without a corpus all words are weighted equally
+        let empty = TfIdf::from_corpus(Vec::<&str>::new());
+        let dist = empty.str_distance("the cat the", "the cat");
+        assert!((dist - TokenCosine.str_distance("the cat the", "the cat")).abs() < 1e-12);
+
+        assert_eq!(tfidf.str_distance("", ""), 0.);
+        assert_eq!(tfidf.str_distance("", "the"), 1.);
+        assert_eq!(tfidf.str_distance("the zoo", "a cat"), 1.);
+        assert!(tfidf.str_distance("the zoo", "zoo  the").abs() < 1e-12);
+        assert_eq!(tfidf.distance(&[1, 2], &[3]), 1.);
+    }
+
+    #[test]
+    fn token_overlap() {
+        assert_eq!(TokenOverlap.str_distance("", ""), 0.);
+        assert_eq!(TokenOverlap.str_distance("", "a b"), 1.);
+        assert_eq!(TokenOverlap.str_distance("new york", "york new"), 0.);
+        // subset of words
+        assert_eq!(TokenOverlap.str_distance("new  york", "new york mets"), 0.);
+        assert_eq!(TokenOverlap.str_distance("new york city", "york mets"), 0.5);
+        // duplicate words are counted once
+        assert_eq!(TokenOverlap.str_distance("a a b", "b c c d"), 0.5);
+        assert_eq!(TokenOverlap.str_distance("abc", "xyz"), 1.);
+        assert_eq!(TokenOverlap.distance(&[1, 2], &[2, 3, 4]), 0.5);
+    }
+
+    #[test]
+    fn token_dice() {
+        // {the, brown} of {the, quick, brown, fox} and {the, lazy, brown, dog}
+        assert_eq!(
+            TokenDice.str_distance("the quick brown fox", "the lazy brown dog"),
+            1. - 2. * 2. / 8.
+        );
+        assert_eq!(TokenDice.str_distance("", ""), 0.);
+        assert_eq!(TokenDice.str_distance("", "a b"), 1.);
+        assert_eq!(TokenDice.str_distance("new  york", "york new"), 0.);
+        // duplicate words are counted once
+        assert_eq!(TokenDice.str_distance("a a b", "b c c"), 0.5);
+        assert_eq!(TokenDice.str_distance("abc", "xyz"), 1.);
+        assert_eq!(TokenDice.distance(&[1, 2], &[2, 3]), 0.5);
+        assert_eq!(
+            TokenDice.str_normalized("new york city", "new york mets"),
+            1. - 4. / 6.
+        );
+    }
+
+    #[test]
+    fn token_set_tokenizer() {
+        let s1 = "Real-Madrid,vs,FC-Barcelona";
+        let s2 = "Barcelona vs Real Madrid";
+        assert!(TokenSet::new(RatcliffObershelp).str_distance(s1, s2) > 0.);
+        let by_punctuation =
+            TokenSet::new(RatcliffObershelp).with_tokenizer(Tokenizer::Alphanumeric);
+        assert_eq!(by_punctuation.str_distance(s1, s2), 0.);
+
+        let by_comma = Tokenizer::Custom(|s| s.split(',').collect());
+        let token_set = TokenSet::new(RatcliffObershelp).with_tokenizer(by_comma);
+        assert_eq!(token_set.str_distance("New York,Mets", "Mets,New York"), 0.);
+        assert!(token_set.str_distance("New York,Mets", "York New,Mets") > 0.);
+    }
+
+    #[test]
+    fn token_sort_tokenizer() {
+        let s1 = "York;New Mets";
+        let s2 = "Mets, New-York";
+        assert!(TokenSort::new(RatcliffObershelp).str_distance(s1, s2) > 0.);
+        let token_sort = TokenSort::new(RatcliffObershelp).with_tokenizer(Tokenizer::Alphanumeric);
+        assert_eq!(token_sort.str_distance(s1, s2), 0.);
+        assert_eq!(token_sort.str_normalized(s1, s2), 0.);
+        assert_eq!(Tokenizer::Alphanumeric.tokenize("--"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn soft_tfidf() {
+        let corpus = [
+            "John Smith",
+            "Jane Smith",
+            "John Doe",
+            "Mary Johnson",
+            "Peter Parker",
+        ];
+        let soft = SoftTfIdf::from_corpus(corpus.iter(), JaroWinkler::default(), 0.9);
+        let tfidf = TfIdf::from_corpus(corpus.iter());
+        assert_eq!(soft.tfidf().num_docs(), 5);
+        assert_eq!(soft.threshold(), 0.9);
+
+        // "Jon" and "John" are similar enough to contribute
+        let soft_dist = soft.str_distance("John Smith", "Jon Smith");
+        let tfidf_dist = tfidf.str_distance("John Smith", "Jon Smith");
+        assert!(soft_dist < tfidf_dist, "{} {}", soft_dist, tfidf_dist);
+        assert!(soft_dist > 0.);
+        // "Jane" is too different from "John"
+        assert!(
+            (soft.str_distance("John Smith", "Jane Smith")
+                - tfidf.str_distance("John Smith", "Jane Smith"))
+            .abs()
+                < 1e-12
+        );
+
+        // with a threshold of 1 only equal words contribute
+        let exact = SoftTfIdf::from_corpus(corpus.iter(), JaroWinkler::default(), 1.);
+        for (a, b) in [
+            ("John Smith", "Jon Smith"),
+            ("John Smith", "Smith John"),
+            ("Mary Johnson", "Mary Jones"),
+            ("John John Doe", "John Doe"),
+        ]
+        .iter()
+        {
+            let dist = exact.str_distance(a, b);
+            assert!(
+                (dist - tfidf.str_distance(a, b)).abs() < 1e-12,
+                "{} {}",
+                a,
+                b
+            );
+        }
+
+        assert!(soft.str_distance("John Smith", "John Smith") < 1e-12);
+        assert_eq!(soft.str_distance("", ""), 0.);
+        assert_eq!(soft.str_distance("John", " "), 1.);
+        assert_eq!(soft.str_distance("John", "Parker"), 1.);
+        assert_eq!(soft.distance(&["a", "b"], &["c"]), 1.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn soft_tfidf_threshold() {
+        SoftTfIdf::new(
+            TfIdf::from_corpus(Vec::<&str>::new()),
+            Levenshtein::default(),
+            1.5,
+        );
+    }
+
+    #[test]
+    fn monge_elkan() {
+        let monge_elkan = MongeElkan::new(Levenshtein::default());
+        assert!(Levenshtein::default().str_normalized("Paul Adam", "Adam Paul") > 0.);
+        assert_eq!(monge_elkan.str_distance("Paul Adam", "Adam Paul"), 0.);
+        assert_eq!(monge_elkan.str_distance("", ""), 0.);
+        assert_eq!(monge_elkan.str_distance("Paul", " "), 1.);
+        // "Pual" is two substitutions away from "Paul"
+        assert_eq!(monge_elkan.str_distance("Pual Adam", "Adam Paul"), 0.25);
+        assert_eq!(monge_elkan.str_distance("Paul", "Paul Adam"), 0.);
+        assert_eq!(monge_elkan.str_distance("Paul Adam", "Paul"), 0.5);
+        assert!(!monge_elkan.is_symmetric());
+
+        let symmetric = MongeElkan::symmetric(Levenshtein::default());
+        assert_eq!(symmetric.str_distance("Paul", "Paul Adam"), 0.25);
+        assert_eq!(
+            symmetric.str_distance("Paul", "Paul Adam"),
+            symmetric.str_distance("Paul Adam", "Paul")
+        );
+        assert_eq!(
+            MongeElkan::new(RatcliffObershelp).distance(&[1, 2], &[2, 3, 1]),
+            0.
+        );
+        assert_eq!(
+            MongeElkan::symmetric(RatcliffObershelp).distance(&[1, 2], &[2, 3, 1]),
+            1. / 6.
         );
     }
 }
@@ -1,13 +1,135 @@
-/// Return the shorter str as first index
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The map that counts the elements of `str`s.
+///
+/// Without the `std` feature there is no `HashMap`, so the `BTreeMap` of
+/// `alloc` is used instead.
+#[cfg(feature = "std")]
+pub(crate) type Map<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::btree_map::Entry;
+/// The entry of a [`Map`].
+#[cfg(feature = "std")]
+pub(crate) use std::collections::hash_map::Entry;
+
+/// The square root of `x`, which is not part of `core`.
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.sqrt();
+    #[cfg(not(feature = "std"))]
+    return libm::sqrt(x);
+}
+
+/// Rounds `x` half away from zero, which is not part of `core`.
+#[inline]
+pub(crate) fn round(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.round();
+    #[cfg(not(feature = "std"))]
+    return libm::round(x);
+}
+
+/// The largest integer less than or equal to `x`, which is not part of `core`.
+#[inline]
+pub(crate) fn floor(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.floor();
+    #[cfg(not(feature = "std"))]
+    return libm::floor(x);
+}
+
+/// The smallest integer greater than or equal to `x`, which is not part of
+/// `core`.
+#[inline]
+pub(crate) fn ceil(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.ceil();
+    #[cfg(not(feature = "std"))]
+    return libm::ceil(x);
+}
+
+/// The natural logarithm of `x`, which is not part of `core`.
+#[inline]
+pub(crate) fn ln(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    return x.ln();
+    #[cfg(not(feature = "std"))]
+    return libm::log(x);
+}
+
+/// Return the str with fewer chars as first index.
+///
+/// The chars are counted instead of the bytes, since the metrics iterate over
+/// the chars and may require the shorter input first.
 #[inline]
 pub(crate) fn order_by_len_asc<'a>(s1: &'a str, s2: &'a str) -> (&'a str, &'a str) {
-    if s1.len() <= s2.len() {
+    if s1.chars().count() <= s2.chars().count() {
         (s1, s2)
     } else {
         (s2, s1)
     }
 }
 
+/// Wraps the elements of an input to swap the order of two inputs, which
+/// compares the elements of the second input with the elements of the first
+/// via `PartialEq` of the first.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Flipped<T>(pub T);
+
+impl<A, B> PartialEq<Flipped<B>> for Flipped<A>
+where
+    B: PartialEq<A>,
+{
+    #[inline]
+    fn eq(&self, other: &Flipped<B>) -> bool {
+        other.0 == self.0
+    }
+}
+
+/// Wraps the elements of an input to compare them with the elements of the
+/// other input via the predicate `eq` instead of `PartialEq`.
+pub(crate) struct EqBy<'a, T, F> {
+    item: T,
+    eq: &'a F,
+}
+
+impl<'a, A, B, F> PartialEq<EqBy<'a, B, F>> for EqBy<'a, A, F>
+where
+    F: Fn(&A, &B) -> bool,
+{
+    #[inline]
+    fn eq(&self, other: &EqBy<'a, B, F>) -> bool {
+        (self.eq)(&self.item, &other.item)
+    }
+}
+
+/// Wraps the elements of `iter`, so that they are compared via `eq`.
+pub(crate) fn eq_by<I, F>(iter: I, eq: &F) -> impl Iterator<Item = EqBy<'_, I::Item, F>> + Clone
+where
+    I: IntoIterator,
+    I::IntoIter: Clone,
+{
+    iter.into_iter().map(move |item| EqBy { item, eq })
+}
+
+/// Compares two scores, values that are not comparable to themselves (e.g.
+/// `f64::NAN`) are considered greater than all other values.
+#[inline]
+pub(crate) fn cmp_score<V: PartialOrd>(a: &V, b: &V) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ord) => ord,
+        None => match (a.partial_cmp(a).is_none(), b.partial_cmp(b).is_none()) {
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ => Ordering::Equal,
+        },
+    }
+}
+
 #[inline]
 pub(crate) fn count_eq<S, T>(mut s1_iter: S, mut s2_iter: T) -> usize
 where
@@ -102,7 +224,7 @@ where
     pub(crate) fn new_skip_take(
         a: S,
         b: T,
-    ) -> DelimDistinct<std::iter::Skip<std::iter::Take<S>>, std::iter::Skip<std::iter::Take<T>>>
+    ) -> DelimDistinct<core::iter::Skip<core::iter::Take<S>>, core::iter::Skip<core::iter::Take<T>>>
     {
         // collecting is a little tedious here, but we can't rely on the iters also
         // being DoubleEnded
@@ -135,6 +257,14 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn score_order() {
+        assert_eq!(cmp_score(&0.1, &0.2), Ordering::Less);
+        assert_eq!(cmp_score(&f64::NAN, &0.2), Ordering::Greater);
+        assert_eq!(cmp_score(&0.1, &f64::NAN), Ordering::Less);
+        assert_eq!(cmp_score(&f64::NAN, &f64::NAN), Ordering::Equal);
+    }
+
     #[test]
     fn delim_different() {
         let s1 = "kitten";
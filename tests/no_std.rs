@@ -0,0 +1,26 @@
+//! Calls the core metrics from a `#![no_std]` crate.
+//!
+//! Run with `cargo test --no-default-features --features libm` to check that
+//! the crate builds without `std`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use str_distance::{Cosine, DistanceMetric, DistanceValue, Jaro, Levenshtein, QGram};
+
+#[test]
+fn compile_test() {
+    assert_eq!(
+        Levenshtein::default().str_distance("kitten", "sitting"),
+        DistanceValue::Exact(3)
+    );
+    assert_eq!(
+        *Levenshtein::default().distance(vec![1, 2, 3], vec![1, 3]),
+        1
+    );
+    assert_eq!(QGram::new(2).str_distance("abc", "abd"), 2);
+    assert_eq!(Cosine::new(2).str_distance("abc", "xyz"), 1.);
+    assert_eq!(Jaro.str_distance("foo", "foo"), 0.);
+}
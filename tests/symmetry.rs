@@ -0,0 +1,300 @@
+//! Checks that the symmetric metrics are symmetric, i.e. that the order of
+//! the inputs doesn't change the distance, for all short strs and random
+//! longer ones, and that the documented exceptions are indeed asymmetric.
+
+use std::fmt::Debug;
+
+use str_distance::*;
+
+/// The chars of the exhaustively checked short strs: a multibyte char whose
+/// byte length differs from its char length and a space to separate words.
+const SHORT_ALPHABET: &[char] = &['a', 'b', '☃', ' '];
+
+/// The chars of the random longer strs, which also cover case differences.
+const ALPHABET: &[char] = &['a', 'b', 'c', 'd', 'A', 'B', '☃', 'é', ' '];
+
+/// All strs of at most 3 chars of [`SHORT_ALPHABET`].
+fn short_strs() -> Vec<String> {
+    let mut strs = vec![String::new()];
+    let mut last = strs.clone();
+    for _ in 0..3 {
+        last = last
+            .iter()
+            .flat_map(|s| {
+                SHORT_ALPHABET.iter().map(move |c| {
+                    let mut s = s.clone();
+                    s.push(*c);
+                    s
+                })
+            })
+            .collect();
+        strs.extend(last.iter().cloned());
+    }
+    strs
+}
+
+/// A xorshift generator, so the random pairs are the same in every run.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+
+    fn string(&mut self, max_len: usize) -> String {
+        let len = self.below(max_len + 1);
+        (0..len)
+            .map(|_| ALPHABET[self.below(ALPHABET.len())])
+            .collect()
+    }
+}
+
+/// Random pairs of up to 30 chars. Half of them are independent, the other
+/// half differ by a few edits, so they share prefixes, suffixes and words.
+fn random_pairs() -> Vec<(String, String)> {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    (0..300)
+        .map(|i| {
+            let a = rng.string(30);
+            if i % 2 == 0 {
+                return (a, rng.string(30));
+            }
+            let mut b: Vec<char> = a.chars().collect();
+            for _ in 0..=rng.below(4) {
+                let c = ALPHABET[rng.below(ALPHABET.len())];
+                let pos = rng.below(b.len() + 1);
+                match rng.below(3) {
+                    0 => b.insert(pos, c),
+                    _ if pos == b.len() => b.push(c),
+                    1 => b[pos] = c,
+                    _ => {
+                        b.remove(pos);
+                    }
+                }
+            }
+            (a, b.into_iter().collect())
+        })
+        .collect()
+}
+
+/// All pairs of [`short_strs`] and the [`random_pairs`].
+fn pairs() -> Vec<(String, String)> {
+    let short = short_strs();
+    let mut pairs: Vec<_> = short
+        .iter()
+        .flat_map(|a| short.iter().map(move |b| (a.clone(), b.clone())))
+        .collect();
+    pairs.extend(random_pairs());
+    pairs
+}
+
+/// A corpus for the metrics that weigh words by their frequency.
+fn corpus() -> Vec<String> {
+    random_pairs().into_iter().map(|(a, _)| a).collect()
+}
+
+/// Whether two distances are equal, treating `NaN`s as equal and ignoring
+/// rounding errors of floats.
+fn equal<V: Debug + PartialEq>(a: &V, b: &V) -> bool {
+    if a == b {
+        return true;
+    }
+    let (a, b) = (format!("{:?}", a), format!("{:?}", b));
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => (a - b).abs() < 1e-12 || (a.is_nan() && b.is_nan()),
+        _ => a == b,
+    }
+}
+
+/// Asserts that `dist` is symmetric for all [`pairs`], for str and chars.
+fn assert_symmetric<D>(name: &str, dist: D)
+where
+    D: DistanceMetric,
+    D::Dist: Debug + PartialEq,
+{
+    for (a, b) in pairs().iter() {
+        let (ab, ba) = (dist.str_distance(a, b), dist.str_distance(b, a));
+        assert!(
+            equal(&ab, &ba),
+            "{}: str_distance({:?}, {:?}) = {:?}, but reversed {:?}",
+            name,
+            a,
+            b,
+            ab,
+            ba
+        );
+        let (ab, ba) = (dist.str_normalized(a, b), dist.str_normalized(b, a));
+        assert!(
+            equal(&ab, &ba),
+            "{}: str_normalized({:?}, {:?}) = {:?}, but reversed {:?}",
+            name,
+            a,
+            b,
+            ab,
+            ba
+        );
+        let (a, b): (Vec<_>, Vec<_>) = (a.chars().collect(), b.chars().collect());
+        let (ab, ba) = (dist.distance(&a, &b), dist.distance(&b, &a));
+        assert!(
+            equal(&ab, &ba),
+            "{}: distance({:?}, {:?}) = {:?}, but reversed {:?}",
+            name,
+            a,
+            b,
+            ab,
+            ba
+        );
+    }
+}
+
+#[test]
+fn edit_metrics_are_symmetric() {
+    assert_symmetric("Levenshtein", Levenshtein::default());
+    assert_symmetric("Levenshtein(2)", Levenshtein::with_max_distance(2));
+    assert_symmetric("DamerauLevenshtein", DamerauLevenshtein::default());
+    assert_symmetric(
+        "DamerauLevenshtein(2)",
+        DamerauLevenshtein::with_max_distance(2),
+    );
+    assert_symmetric(
+        "DamerauLevenshtein transposition",
+        DamerauLevenshtein::with_transposition_cost(2),
+    );
+    assert_symmetric("CaseAwareLevenshtein", CaseAwareLevenshtein::new(0.5));
+    // symmetric as long as the cost function is symmetric
+    assert_symmetric(
+        "CostFnLevenshtein",
+        Levenshtein::with_cost_fn(|c1, c2| if c1.eq_ignore_ascii_case(&c2) { 0 } else { 2 }),
+    );
+    // symmetric as long as the substitution matrix is symmetric
+    assert_symmetric(
+        "ProbabilisticLevenshtein",
+        ProbabilisticLevenshtein::new(vec![(('u', 'v'), 0.5f64.ln()), (('v', 'u'), 0.5f64.ln())]),
+    );
+    assert_symmetric("Indel", Indel);
+    assert_symmetric("Gotoh", Gotoh::new(1., 1., 1., 0.5));
+    assert_symmetric("SmithWaterman", SmithWaterman::new(1., 1., 1.));
+    assert_symmetric("NeedlemanWunsch", NeedlemanWunsch::new(1., 1., 1.));
+}
+
+#[test]
+fn jaro_metrics_are_symmetric() {
+    assert_symmetric("Jaro", Jaro);
+    assert_symmetric("JaroWinkler", JaroWinkler::default());
+    assert_symmetric("CustomJaro", Jaro::with_params(1., 1.));
+    assert_symmetric(
+        "Winkler max boost",
+        Winkler::with_config(Jaro, WinklerConfig::with_max_boost(0.5, 0.7, 10)),
+    );
+    assert_symmetric("Winkler(Levenshtein)", Winkler::new(Levenshtein::default()));
+}
+
+#[test]
+fn qgram_metrics_are_symmetric() {
+    for q in 1..4 {
+        assert_symmetric("QGram", QGram::new(q));
+        assert_symmetric("Cosine", Cosine::new(q));
+        assert_symmetric("Jaccard", Jaccard::new(q));
+        assert_symmetric("SorensenDice", SorensenDice::new(q));
+        assert_symmetric("Overlap", Overlap::new(q));
+        // only symmetric if both weights are equal
+        assert_symmetric("Tversky", Tversky::new(q, 0.5, 0.5));
+        assert_symmetric("PositionalQGram", PositionalQGram::new(q, 2));
+        assert_symmetric("QGram padded", QGram::with_padding(q));
+        assert_symmetric("Cosine padded", Cosine::with_padding(q));
+        assert_symmetric("Jaccard padded", Jaccard::with_padding(q));
+        assert_symmetric("SorensenDice padded", SorensenDice::with_padding(q));
+        assert_symmetric("Overlap padded", Overlap::with_padding(q));
+        assert_symmetric("Tversky padded", Tversky::with_padding(q, 0.5, 0.5));
+        assert_symmetric(
+            "PositionalQGram padded",
+            PositionalQGram::with_padding(q, 2),
+        );
+    }
+    assert_symmetric("AdaptiveJaccard", AdaptiveJaccard::new(3, 3));
+}
+
+#[test]
+fn other_metrics_are_symmetric() {
+    assert_symmetric("RatcliffObershelp", RatcliffObershelp);
+    assert_symmetric("Bag", Bag);
+    assert_symmetric("CharHistogram", CharHistogram);
+    assert_symmetric("LengthRatio", LengthRatio);
+    assert_symmetric("Hamming", Hamming);
+    assert_symmetric("Soundex", Soundex);
+    assert_symmetric("DoubleMetaphone", DoubleMetaphone);
+    assert_symmetric("Caverphone", Caverphone::default());
+    assert_symmetric("Mra", Mra);
+    assert_symmetric("Editex", Editex);
+    assert_symmetric("SetJaccard", SetJaccard);
+    assert_symmetric("SetDice", SetDice);
+    assert_symmetric(
+        "Ensemble",
+        Ensemble::new(vec![
+            (Box::new(Levenshtein::default()), 1.),
+            (Box::new(JaroWinkler::default()), 2.),
+        ]),
+    );
+    assert_symmetric("Signed", Signed::new(Levenshtein::default(), 0.3));
+    assert_symmetric("Truncated", Truncated::new(Levenshtein::default(), 5));
+    assert_symmetric("Reversed", Reversed::new(JaroWinkler::default()));
+    assert_symmetric(
+        "Normalized",
+        normalize::Normalized::new(Levenshtein::default()),
+    );
+}
+
+#[test]
+fn token_metrics_are_symmetric() {
+    assert_symmetric("TokenSet", TokenSet::new(RatcliffObershelp));
+    assert_symmetric(
+        "TokenSet(Levenshtein)",
+        TokenSet::new(Levenshtein::default()),
+    );
+    assert_symmetric("TokenSort", TokenSort::new(RatcliffObershelp));
+    assert_symmetric("TokenSort(Jaro)", TokenSort::new(Jaro));
+    assert_symmetric("TokenOverlap", TokenOverlap);
+    assert_symmetric("TokenDice", TokenDice);
+    assert_symmetric("TokenCosine", TokenCosine);
+    assert_symmetric("TfIdf", TfIdf::from_corpus(corpus()));
+    assert_symmetric("Partial", Partial::new(RatcliffObershelp));
+    assert_symmetric("Partial(Levenshtein)", Partial::new(Levenshtein::default()));
+    assert_symmetric("Partial(JaroWinkler)", Partial::new(JaroWinkler::default()));
+    assert_symmetric(
+        "MongeElkan symmetric",
+        MongeElkan::symmetric(Levenshtein::default()),
+    );
+}
+
+#[test]
+fn sift4_is_asymmetric() {
+    // the greedy scan matches the "b" and "c" of "cbc" with "bcd" only in
+    // this order
+    assert_eq!(Sift4::default().str_distance("cbc", "bcd"), 1);
+    assert_eq!(Sift4::default().str_distance("bcd", "cbc"), 2);
+    assert_eq!(*Levenshtein::default().str_distance("cbc", "bcd"), 2);
+}
+
+#[test]
+fn sift_is_asymmetric() {
+    let sift3 = Sift::new(SiftAlgorithm::Simple, 5);
+    let (a, b) = ("h edfgbe", "eeg  gebeffgacbdfdghchf  gdg");
+    assert_eq!(sift3.str_distance(a, b), 17.);
+    assert_eq!(sift3.str_distance(b, a), 16.);
+
+    let common = Sift::new(SiftAlgorithm::Common, 5);
+    assert_eq!(common.str_distance("aba", "ba☃"), 1.);
+    assert_eq!(common.str_distance("ba☃", "aba"), 2.);
+}
+
+#[test]
+fn soft_tfidf_is_asymmetric() {
+    // "John" is only matched with its most similar word of the second str,
+    // whereas in reverse both words are matched with "John"
+    let soft = SoftTfIdf::from_corpus(corpus(), JaroWinkler::default(), 0.9);
+    assert!((soft.str_distance("John", "Jon Jonh") - 0.340034).abs() < 1e-6);
+    assert_eq!(soft.str_distance("Jon Jonh", "John"), 0.);
+}